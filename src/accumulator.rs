@@ -0,0 +1,201 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, PoisonError};
+
+use crate::DefaultHashMap;
+
+/// Collects [`DefaultHashMap`] shards from multiple threads and merges them into one map at the
+/// end.
+///
+/// Every worker takes its own [`AccumulatorShard`] with [`Accumulator::shard`] and writes to it
+/// without any synchronization. When a shard is dropped its map is handed back to the
+/// accumulator, and [`Accumulator::merge`] combines all of them with a user supplied function.
+/// For counting workloads this avoids the contention of a shared concurrent map.
+///
+/// # Example
+/// ```
+/// use defaultdict::Accumulator;
+///
+/// let words = ["a", "b", "a", "c", "a", "b"];
+/// let accumulator = Accumulator::<&str, u32>::new();
+///
+/// std::thread::scope(|scope| {
+///     for chunk in words.chunks(2) {
+///         let accumulator = &accumulator;
+///         scope.spawn(move || {
+///             let mut shard = accumulator.shard();
+///             for word in chunk {
+///                 *shard.get_mut(word) += 1;
+///             }
+///         });
+///     }
+/// });
+///
+/// let counts = accumulator.merge(|total, count| *total += count);
+///
+/// assert_eq!(&3, counts.get(&"a"));
+/// assert_eq!(&2, counts.get(&"b"));
+/// assert_eq!(&1, counts.get(&"c"));
+/// ```
+#[derive(Debug)]
+pub struct Accumulator<K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _shards: Mutex<Vec<DefaultHashMap<K, V>>>,
+}
+
+impl<K, V> Accumulator<K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an [`Accumulator`] without any shards.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _shards: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Merges every returned shard into one map. The first value of a key is moved into the
+    /// result, every later value of the same key is passed to `combine` together with the value
+    /// collected so far.
+    ///
+    /// Shards that are still alive are not part of the result, so all of them should be dropped
+    /// before merging. Scoped threads guarantee this.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Accumulator;
+    ///
+    /// let accumulator = Accumulator::new();
+    /// accumulator.shard().insert(1, 5);
+    /// accumulator.shard().insert(1, 7);
+    ///
+    /// let maximum = accumulator.merge(|max: &mut i32, value| *max = (*max).max(value));
+    ///
+    /// assert_eq!(&7, maximum.get(&1));
+    /// ```
+    pub fn merge<F>(self, mut combine: F) -> DefaultHashMap<K, V>
+    where
+        F: FnMut(&mut V, V),
+    {
+        let mut shards = self
+            ._shards
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_iter();
+        let mut merged = shards.next().unwrap_or_default();
+        for mut shard in shards {
+            for (key, value) in shard.drain() {
+                match merged.entry(key) {
+                    Entry::Occupied(entry) => combine(entry.into_mut(), value),
+                    Entry::Vacant(entry) => {
+                        entry.insert(value);
+                    }
+                }
+            }
+        }
+        merged
+    }
+
+    /// Returns a new, empty shard. The shard is handed back to the accumulator when it is
+    /// dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Accumulator;
+    ///
+    /// let accumulator: Accumulator<i8, i8> = Accumulator::new();
+    /// {
+    ///     let mut shard = accumulator.shard();
+    ///     *shard.get_mut(&1) += 1;
+    /// }
+    ///
+    /// assert_eq!(1, accumulator.shard_count());
+    /// ```
+    #[must_use]
+    pub fn shard(&self) -> AccumulatorShard<'_, K, V> {
+        AccumulatorShard {
+            _map: DefaultHashMap::new(),
+            _accumulator: self,
+        }
+    }
+
+    /// Returns the number of shards that have been handed back.
+    pub fn shard_count(&self) -> usize {
+        self._shards
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+}
+
+impl<K, V> Default for Accumulator<K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`DefaultHashMap`] owned by a single worker of an [`Accumulator`].
+///
+/// The map is available through [`Deref`] and [`DerefMut`] and is handed back to the accumulator
+/// when the shard is dropped. Empty shards are discarded.
+#[derive(Debug)]
+pub struct AccumulatorShard<'a, K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _map: DefaultHashMap<K, V>,
+    _accumulator: &'a Accumulator<K, V>,
+}
+
+impl<K, V> Deref for AccumulatorShard<'_, K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    type Target = DefaultHashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self._map
+    }
+}
+
+impl<K, V> DerefMut for AccumulatorShard<'_, K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self._map
+    }
+}
+
+impl<K, V> Drop for AccumulatorShard<'_, K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn drop(&mut self) {
+        if self._map.is_empty() {
+            return;
+        }
+        let map = std::mem::take(&mut self._map);
+        self._accumulator
+            ._shards
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(map);
+    }
+}
@@ -0,0 +1,218 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+
+use crate::DefaultHashMap;
+
+/// An adjacency list built on a [`DefaultHashMap`] from each node to the nodes it has an edge to.
+///
+/// In undirected mode every edge is stored in both directions. Nodes without edges have no
+/// neighbors and a degree of zero.
+///
+/// # Example
+/// ```
+/// use defaultdict::AdjacencyMap;
+///
+/// let mut graph = AdjacencyMap::new();
+/// graph.add_edge("a", "b");
+/// graph.add_edge("a", "c");
+///
+/// assert_eq!(&["b", "c"], graph.neighbors(&"a"));
+/// assert_eq!(2, graph.degree(&"a"));
+/// assert_eq!(0, graph.degree(&"b"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdjacencyMap<N>
+where
+    N: Eq + Hash,
+{
+    _edges: DefaultHashMap<N, Vec<N>>,
+    _undirected: bool,
+}
+
+impl<N> AdjacencyMap<N>
+where
+    N: Eq + Hash,
+{
+    /// Creates an empty directed graph.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::AdjacencyMap;
+    ///
+    /// let graph: AdjacencyMap<u8> = AdjacencyMap::new();
+    ///
+    /// assert!(graph.is_empty());
+    /// assert!(!graph.is_undirected());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _edges: DefaultHashMap::new(),
+            _undirected: false,
+        }
+    }
+
+    /// Creates an empty undirected graph.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::AdjacencyMap;
+    ///
+    /// let mut graph = AdjacencyMap::undirected();
+    /// graph.add_edge(1, 2);
+    ///
+    /// assert_eq!(&[1], graph.neighbors(&2));
+    /// ```
+    #[must_use]
+    pub fn undirected() -> Self {
+        Self {
+            _edges: DefaultHashMap::new(),
+            _undirected: true,
+        }
+    }
+
+    /// Adds an edge between two nodes. In undirected mode the reverse edge is added as well,
+    /// unless the edge is a self-loop.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::AdjacencyMap;
+    ///
+    /// let mut graph = AdjacencyMap::new();
+    /// graph.add_edge(1, 2);
+    /// graph.add_edge(1, 2);
+    ///
+    /// assert_eq!(&[2, 2], graph.neighbors(&1));
+    /// ```
+    pub fn add_edge(&mut self, from: N, to: N)
+    where
+        N: Clone,
+    {
+        if self._undirected && from != to {
+            self._edges.get_mut(&to).push(from.clone());
+        }
+        self._edges.get_mut(&from).push(to);
+    }
+
+    /// Returns a reference to the wrapped [`DefaultHashMap`].
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<N, Vec<N>> {
+        &self._edges
+    }
+
+    /// Returns `true` if there is an edge from one node to the other.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::AdjacencyMap;
+    ///
+    /// let mut graph = AdjacencyMap::new();
+    /// graph.add_edge(1, 2);
+    ///
+    /// assert!(graph.contains_edge(&1, &2));
+    /// assert!(!graph.contains_edge(&2, &1));
+    /// ```
+    pub fn contains_edge(&self, from: &N, to: &N) -> bool {
+        self.neighbors(from).contains(to)
+    }
+
+    /// Returns the number of edges leaving the node.
+    #[inline]
+    pub fn degree(&self, node: &N) -> usize {
+        self.neighbors(node).len()
+    }
+
+    /// Consumes the graph and returns the wrapped [`DefaultHashMap`].
+    #[inline]
+    pub fn into_inner(self) -> DefaultHashMap<N, Vec<N>> {
+        self._edges
+    }
+
+    /// Returns `true` if the graph has no edges.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._edges.is_empty()
+    }
+
+    /// Returns `true` if edges are stored in both directions.
+    #[inline]
+    pub fn is_undirected(&self) -> bool {
+        self._undirected
+    }
+
+    /// Returns the nodes the node has an edge to, in the order the edges were added.
+    #[inline]
+    pub fn neighbors(&self, node: &N) -> &[N] {
+        self._edges.get(node)
+    }
+
+    /// An iterator visiting all nodes that have at least one edge, in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::AdjacencyMap;
+    ///
+    /// let mut graph = AdjacencyMap::undirected();
+    /// graph.add_edge(1, 2);
+    ///
+    /// let mut nodes: Vec<_> = graph.nodes().collect();
+    /// nodes.sort();
+    ///
+    /// assert_eq!(vec![&1, &2], nodes);
+    /// ```
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self._edges.keys()
+    }
+
+    /// Removes every edge from one node to the other, returning how many were removed. In
+    /// undirected mode the reverse edges are removed as well.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::AdjacencyMap;
+    ///
+    /// let mut graph = AdjacencyMap::undirected();
+    /// graph.add_edge(1, 2);
+    ///
+    /// assert_eq!(1, graph.remove_edge(&2, &1));
+    /// assert!(graph.is_empty());
+    /// ```
+    pub fn remove_edge(&mut self, from: &N, to: &N) -> usize
+    where
+        N: Clone,
+    {
+        let removed = self.remove_directed(from, to);
+        if self._undirected && from != to {
+            self.remove_directed(to, from);
+        }
+        removed
+    }
+
+    /// Removes the edges in one direction, dropping the node if it has no edges left.
+    fn remove_directed(&mut self, from: &N, to: &N) -> usize
+    where
+        N: Clone,
+    {
+        if !self._edges.contains_key(from) {
+            return 0;
+        }
+        let neighbors = self._edges.get_mut(from);
+        let before = neighbors.len();
+        neighbors.retain(|neighbor| neighbor != to);
+        let removed = before - neighbors.len();
+        if neighbors.is_empty() {
+            let _ = self._edges.remove(from);
+        }
+        removed
+    }
+}
+
+impl<N> Default for AdjacencyMap<N>
+where
+    N: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
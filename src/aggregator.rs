@@ -0,0 +1,204 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+use std::panic;
+use std::sync::mpsc::{self, SendError, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::DefaultHashMap;
+
+/// A function that reads the map on the worker thread.
+type Read<K, V> = Box<dyn FnOnce(&DefaultHashMap<K, V>) + Send>;
+
+/// A request sent from a handle to the worker of an [`Aggregator`].
+enum Message<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    Update(K, D),
+    Read(Read<K, V>),
+    Finish,
+}
+
+/// Owns a [`DefaultHashMap`] on a background thread and applies the updates that producers send
+/// to it through a channel.
+///
+/// Producers get a cheap, cloneable [`AggregatorSender`] and send `(key, delta)` pairs without
+/// sharing any memory. The worker folds every delta into the value of its key, starting from the
+/// default value. [`Aggregator::snapshot`] returns a copy of the map so far and
+/// [`Aggregator::finish`] stops the worker and returns the map.
+///
+/// # Example
+/// ```
+/// use defaultdict::Aggregator;
+///
+/// let aggregator = Aggregator::spawn(|count: &mut u32, delta: u32| *count += delta);
+///
+/// std::thread::scope(|scope| {
+///     for word in ["a", "b", "a"] {
+///         let sender = aggregator.sender();
+///         scope.spawn(move || sender.send(word, 1).unwrap());
+///     }
+/// });
+///
+/// let counts = aggregator.finish();
+///
+/// assert_eq!(&2, counts.get(&"a"));
+/// assert_eq!(&1, counts.get(&"b"));
+/// ```
+pub struct Aggregator<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _sender: AggregatorSender<K, V, D>,
+    _worker: Option<JoinHandle<DefaultHashMap<K, V>>>,
+}
+
+impl<K, V, D> Aggregator<K, V, D>
+where
+    K: Eq + Hash + Send + 'static,
+    V: Default + Send + 'static,
+    D: Send + 'static,
+{
+    /// Starts the worker thread. Every delta that is sent is applied to the value of its key with
+    /// `apply`.
+    #[must_use]
+    pub fn spawn<F>(mut apply: F) -> Self
+    where
+        F: FnMut(&mut V, D) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            let mut map = DefaultHashMap::new();
+            for message in receiver {
+                match message {
+                    Message::Update(key, delta) => apply(map.entry(key).or_default(), delta),
+                    Message::Read(read) => read(&map),
+                    Message::Finish => break,
+                }
+            }
+            map
+        });
+        Self {
+            _sender: AggregatorSender { _sender: sender },
+            _worker: Some(worker),
+        }
+    }
+}
+
+impl<K, V, D> Aggregator<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Stops the worker and returns the aggregated map. Updates that were sent before this call
+    /// are included, later updates are rejected.
+    ///
+    /// # Panics
+    /// Resumes the panic of the worker if `apply` panicked.
+    pub fn finish(mut self) -> DefaultHashMap<K, V> {
+        let worker = self._worker.take().expect("the worker is only taken once");
+        let _ = self._sender._sender.send(Message::Finish);
+        worker
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+
+    /// Sends a delta for the key to the worker. This is a shortcut for sending through
+    /// [`Aggregator::sender`].
+    #[inline]
+    pub fn send(&self, key: K, delta: D) -> Result<(), SendError<(K, D)>> {
+        self._sender.send(key, delta)
+    }
+
+    /// Returns a new handle to send updates to the worker.
+    #[must_use]
+    pub fn sender(&self) -> AggregatorSender<K, V, D> {
+        self._sender.clone()
+    }
+
+    /// Returns a copy of the map with every update that was sent before this call applied.
+    ///
+    /// # Panics
+    /// Panics if the worker panicked.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Aggregator;
+    ///
+    /// let aggregator = Aggregator::spawn(|total: &mut i64, delta: i64| *total += delta);
+    /// aggregator.send("balance", 10).unwrap();
+    /// aggregator.send("balance", -3).unwrap();
+    ///
+    /// assert_eq!(&7, aggregator.snapshot().get(&"balance"));
+    /// ```
+    pub fn snapshot(&self) -> DefaultHashMap<K, V>
+    where
+        K: Clone + Send + 'static,
+        V: Clone + Send + 'static,
+    {
+        let (reply, result) = mpsc::channel();
+        let read: Read<K, V> = Box::new(move |map| {
+            let _ = reply.send(map.clone());
+        });
+        self._sender
+            ._sender
+            .send(Message::Read(read))
+            .ok()
+            .and_then(|_| result.recv().ok())
+            .expect("the aggregator worker panicked")
+    }
+}
+
+impl<K, V, D> Drop for Aggregator<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn drop(&mut self) {
+        if let Some(worker) = self._worker.take() {
+            let _ = self._sender._sender.send(Message::Finish);
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A handle to send updates to the worker of an [`Aggregator`]. Cloning the handle is cheap.
+pub struct AggregatorSender<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _sender: Sender<Message<K, V, D>>,
+}
+
+impl<K, V, D> AggregatorSender<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Sends a delta for the key to the worker. If the aggregator has finished the update is
+    /// returned in the error.
+    pub fn send(&self, key: K, delta: D) -> Result<(), SendError<(K, D)>> {
+        self._sender
+            .send(Message::Update(key, delta))
+            .map_err(|SendError(message)| match message {
+                Message::Update(key, delta) => SendError((key, delta)),
+                _ => unreachable!("only updates are sent through this method"),
+            })
+    }
+}
+
+impl<K, V, D> Clone for AggregatorSender<K, V, D>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _sender: self._sender.clone(),
+        }
+    }
+}
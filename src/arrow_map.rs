@@ -0,0 +1,233 @@
+#![deny(missing_docs)]
+
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{
+    Array, ArrayRef, ArrowPrimitiveType, BooleanArray, PrimitiveArray, RecordBatch, StringArray,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use crate::DefaultHashMap;
+
+/// A type that can be stored in a non-nullable Arrow array. This requires the `arrow` feature.
+///
+/// # Example
+/// ```
+/// use defaultdict::ArrowValue;
+///
+/// let array = u32::to_array(vec![1, 2, 3]);
+///
+/// assert_eq!(vec![1, 2, 3], u32::from_array(&array).unwrap());
+/// assert!(i64::from_array(&array).is_err());
+/// ```
+pub trait ArrowValue: Sized {
+    /// Returns the Arrow data type of the arrays of this type.
+    fn data_type() -> DataType;
+
+    /// Builds an array from the values.
+    fn to_array(values: Vec<Self>) -> ArrayRef;
+
+    /// Reads the values of an array. Fails if the array has a different data type or contains
+    /// nulls.
+    fn from_array(array: &dyn Array) -> Result<Vec<Self>, ArrowError>;
+}
+
+macro_rules! arrow_primitive {
+    ( $( ($ty:ty, $arrow:ty) ),* ) => {
+        $(
+            impl ArrowValue for $ty {
+                fn data_type() -> DataType {
+                    <$arrow as ArrowPrimitiveType>::DATA_TYPE
+                }
+
+                fn to_array(values: Vec<Self>) -> ArrayRef {
+                    Arc::new(PrimitiveArray::<$arrow>::from(values))
+                }
+
+                fn from_array(array: &dyn Array) -> Result<Vec<Self>, ArrowError> {
+                    let array = downcast::<PrimitiveArray<$arrow>>(array, Self::data_type())?;
+                    Ok(array.values().to_vec())
+                }
+            }
+        )*
+    };
+}
+
+arrow_primitive!(
+    (i8, Int8Type),
+    (i16, Int16Type),
+    (i32, Int32Type),
+    (i64, Int64Type),
+    (u8, UInt8Type),
+    (u16, UInt16Type),
+    (u32, UInt32Type),
+    (u64, UInt64Type),
+    (f32, Float32Type),
+    (f64, Float64Type)
+);
+
+impl ArrowValue for bool {
+    fn data_type() -> DataType {
+        DataType::Boolean
+    }
+
+    fn to_array(values: Vec<Self>) -> ArrayRef {
+        Arc::new(BooleanArray::from(values))
+    }
+
+    fn from_array(array: &dyn Array) -> Result<Vec<Self>, ArrowError> {
+        let array = downcast::<BooleanArray>(array, Self::data_type())?;
+        Ok(array.values().iter().collect())
+    }
+}
+
+impl ArrowValue for String {
+    fn data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn to_array(values: Vec<Self>) -> ArrayRef {
+        Arc::new(StringArray::from(values))
+    }
+
+    fn from_array(array: &dyn Array) -> Result<Vec<Self>, ArrowError> {
+        let array = downcast::<StringArray>(array, Self::data_type())?;
+        Ok(array.iter().flatten().map(str::to_owned).collect())
+    }
+}
+
+/// Downcasts the array to its concrete type, rejecting other data types and nulls.
+fn downcast<A>(array: &dyn Array, expected: DataType) -> Result<&A, ArrowError>
+where
+    A: Array + 'static,
+{
+    let typed = array.as_any().downcast_ref::<A>().ok_or_else(|| {
+        ArrowError::CastError(format!(
+            "expected an array of {expected}, found {}",
+            array.data_type()
+        ))
+    })?;
+    if typed.null_count() > 0 {
+        return Err(ArrowError::InvalidArgumentError(String::from(
+            "the array contains nulls",
+        )));
+    }
+    Ok(typed)
+}
+
+/// Conversions to and from Arrow arrays. This requires the `arrow` feature.
+impl<K, V, S> DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + ArrowValue,
+    V: Default + ArrowValue,
+    S: BuildHasher,
+{
+    /// Returns the keys and the values of the map as two arrays of the same length, in matching
+    /// order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap};
+    ///
+    /// let map: DefaultHashMap<u8, f64> = defaulthashmap!((1, 0.5));
+    /// let (keys, values) = map.to_arrow_arrays();
+    ///
+    /// assert_eq!(1, keys.len());
+    /// assert_eq!(1, values.len());
+    /// ```
+    pub fn to_arrow_arrays(&self) -> (ArrayRef, ArrayRef)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let (keys, values): (Vec<K>, Vec<V>) = self
+            .into_iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .unzip();
+        (K::to_array(keys), V::to_array(values))
+    }
+
+    /// Returns the map as a record batch with a non-nullable key column and value column, named
+    /// `key_name` and `value_name`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap};
+    ///
+    /// let map: DefaultHashMap<String, u64> = defaulthashmap!(("a".to_string(), 1));
+    /// let batch = map.to_record_batch("word", "count");
+    ///
+    /// assert_eq!(1, batch.num_rows());
+    /// assert_eq!("count", batch.schema().field(1).name());
+    /// ```
+    pub fn to_record_batch(&self, key_name: &str, value_name: &str) -> RecordBatch
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let schema = Schema::new(vec![
+            Field::new(key_name, K::data_type(), false),
+            Field::new(value_name, V::data_type(), false),
+        ]);
+        let (keys, values) = self.to_arrow_arrays();
+        RecordBatch::try_new(Arc::new(schema), vec![keys, values])
+            .expect("the columns match the schema")
+    }
+
+    /// Builds a map from an array of keys and an array of values. Later duplicate keys overwrite
+    /// earlier ones.
+    ///
+    /// # Errors
+    /// Fails if the arrays have different lengths, an unexpected data type or contain nulls.
+    pub fn try_from_arrow_arrays(keys: &dyn Array, values: &dyn Array) -> Result<Self, ArrowError>
+    where
+        S: Default,
+    {
+        if keys.len() != values.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "found {} keys and {} values",
+                keys.len(),
+                values.len()
+            )));
+        }
+        let keys = K::from_array(keys)?;
+        let values = V::from_array(values)?;
+        Ok(keys.into_iter().zip(values).collect())
+    }
+
+    /// Builds a map from a record batch whose first column holds the keys and whose second column
+    /// holds the values.
+    ///
+    /// # Errors
+    /// Fails if the batch does not have exactly two columns, or if they have an unexpected data
+    /// type or contain nulls.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap};
+    ///
+    /// let map: DefaultHashMap<i32, bool> = defaulthashmap!((1, true), (2, false));
+    /// let batch = map.to_record_batch("id", "active");
+    ///
+    /// let back = DefaultHashMap::<i32, bool>::try_from_record_batch(&batch).unwrap();
+    ///
+    /// assert_eq!(map, back);
+    /// ```
+    pub fn try_from_record_batch(batch: &RecordBatch) -> Result<Self, ArrowError>
+    where
+        S: Default,
+    {
+        match batch.columns() {
+            [keys, values] => Self::try_from_arrow_arrays(keys, values),
+            columns => Err(ArrowError::InvalidArgumentError(format!(
+                "expected a key and a value column, found {} columns",
+                columns.len()
+            ))),
+        }
+    }
+}
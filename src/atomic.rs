@@ -0,0 +1,106 @@
+#![deny(missing_docs)]
+
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+use crate::DefaultHashMap;
+
+macro_rules! atomic_counters {
+    ( $( ($atomic:ident, $int:ty) ),* ) => {
+        $(
+            /// Lock-free counting for maps of atomic integers. Slots are created with the usual
+            /// mutable methods such as [`DefaultHashMap::get_mut`], after that they can be updated
+            /// through shared references, so the map can be shared between threads without a lock
+            /// as long as the set of keys is known up front.
+            ///
+            /// All operations use [`Ordering::Relaxed`], which is enough for counters that are only
+            /// read after the counting threads have been joined.
+            impl<K, S> DefaultHashMap<K, $atomic, S>
+            where
+                K: Eq + Hash,
+                S: BuildHasher,
+            {
+                #[doc = concat!(
+                    "Adds `delta` to the counter of the key passed in and returns the previous ",
+                    "value, or `None` if the key has no slot yet.\n",
+                    "\n",
+                    "# Example\n",
+                    "```\n",
+                    "use std::sync::atomic::", stringify!($atomic), ";\n",
+                    "\n",
+                    "use defaultdict::DefaultHashMap;\n",
+                    "\n",
+                    "let mut map = DefaultHashMap::<&str, ", stringify!($atomic), ">::new();\n",
+                    "let _ = map.get_mut(&\"hits\");\n",
+                    "\n",
+                    "std::thread::scope(|scope| {\n",
+                    "    for _ in 0..4 {\n",
+                    "        scope.spawn(|| map.fetch_add(&\"hits\", 1));\n",
+                    "    }\n",
+                    "});\n",
+                    "\n",
+                    "assert_eq!(4, map.load(&\"hits\"));\n",
+                    "assert_eq!(None, map.fetch_add(&\"misses\", 1));\n",
+                    "```",
+                )]
+                pub fn fetch_add(&self, key: &K, delta: $int) -> Option<$int> {
+                    self.get_present(key)
+                        .map(|counter| counter.fetch_add(delta, Ordering::Relaxed))
+                }
+
+                #[doc = concat!(
+                    "Returns the current value of the counter of the key passed in, or `0` if the ",
+                    "key has no slot.\n",
+                    "\n",
+                    "# Example\n",
+                    "```\n",
+                    "use std::sync::atomic::", stringify!($atomic), ";\n",
+                    "\n",
+                    "use defaultdict::DefaultHashMap;\n",
+                    "\n",
+                    "let mut map = DefaultHashMap::<&str, ", stringify!($atomic), ">::new();\n",
+                    "map.insert(\"hits\", ", stringify!($atomic), "::new(3));\n",
+                    "\n",
+                    "assert_eq!(3, map.load(&\"hits\"));\n",
+                    "assert_eq!(0, map.load(&\"misses\"));\n",
+                    "```",
+                )]
+                pub fn load(&self, key: &K) -> $int {
+                    self.get_present(key)
+                        .map_or(0, |counter| counter.load(Ordering::Relaxed))
+                }
+
+                #[doc = concat!(
+                    "Reads every counter into a map of plain integers.\n",
+                    "\n",
+                    "# Example\n",
+                    "```\n",
+                    "use std::sync::atomic::", stringify!($atomic), ";\n",
+                    "\n",
+                    "use defaultdict::DefaultHashMap;\n",
+                    "\n",
+                    "let mut map = DefaultHashMap::<&str, ", stringify!($atomic), ">::new();\n",
+                    "let _ = map.get_mut(&\"hits\");\n",
+                    "map.fetch_add(&\"hits\", 2);\n",
+                    "\n",
+                    "let snapshot: DefaultHashMap<&str, ", stringify!($int), "> =\n",
+                    "    map.snapshot();\n",
+                    "\n",
+                    "assert_eq!(&2, snapshot.get(&\"hits\"));\n",
+                    "```",
+                )]
+                pub fn snapshot<T>(&self) -> DefaultHashMap<K, $int, T>
+                where
+                    K: Clone,
+                    T: BuildHasher + Default,
+                {
+                    self.into_iter()
+                        .map(|(key, counter)| (key.clone(), counter.load(Ordering::Relaxed)))
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+atomic_counters!((AtomicU32, u32), (AtomicU64, u64), (AtomicUsize, usize));
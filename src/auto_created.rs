@@ -0,0 +1,150 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::collections::hash_set::{HashSet, Iter};
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Listener, ObservedHashMap};
+
+/// A [`Listener`] that keeps track of the keys whose entries were created implicitly with the
+/// default value, for example by [`ObservedHashMap::get_mut`], as opposed to explicitly inserted.
+///
+/// A key stops being auto-created when a value is inserted for it or when it is removed.
+///
+/// # Example
+/// ```
+/// use defaultdict::TrackedHashMap;
+///
+/// let mut map = TrackedHashMap::tracked();
+/// map.insert("explicit", 1);
+/// let _ = map.get_mut(&"phantom");
+///
+/// assert_eq!(vec![&"phantom"], map.auto_created_keys().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug)]
+pub struct AutoCreatedKeys<K>
+where
+    K: Eq + Hash,
+{
+    _keys: HashSet<K>,
+}
+
+impl<K> AutoCreatedKeys<K>
+where
+    K: Eq + Hash,
+{
+    /// Creates a listener that has not seen any auto-created keys.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _keys: HashSet::new(),
+        }
+    }
+
+    /// Returns `true` if the entry of the key was created with the default value.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self._keys.contains(key)
+    }
+
+    /// Returns `true` if there are no auto-created keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._keys.is_empty()
+    }
+
+    /// An iterator visiting the auto-created keys in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K> {
+        self._keys.iter()
+    }
+
+    /// Returns the number of auto-created keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._keys.len()
+    }
+}
+
+impl<K> Default for AutoCreatedKeys<K>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Listener<K, V> for AutoCreatedKeys<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn on_insert(&mut self, key: &K, _value: &V) {
+        self._keys.remove(key);
+    }
+
+    fn on_overwrite(&mut self, key: &K, _old: &V, _new: &V) {
+        self._keys.remove(key);
+    }
+
+    fn on_remove(&mut self, key: &K, _value: &V) {
+        self._keys.remove(key);
+    }
+
+    fn on_default(&mut self, key: &K, _value: &V) {
+        self._keys.insert(key.clone());
+    }
+}
+
+/// An [`ObservedHashMap`] that tracks which of its keys were auto-created.
+pub type TrackedHashMap<K, V, S = RandomState> = ObservedHashMap<K, V, AutoCreatedKeys<K>, S>;
+
+impl<K, V> ObservedHashMap<K, V, AutoCreatedKeys<K>, RandomState>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+{
+    /// Creates an empty map that tracks its auto-created keys.
+    #[must_use]
+    pub fn tracked() -> Self {
+        Self::new(AutoCreatedKeys::new())
+    }
+}
+
+impl<K, V, S> ObservedHashMap<K, V, AutoCreatedKeys<K>, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+    S: BuildHasher,
+{
+    /// An iterator visiting the keys whose entries were created with the default value, in
+    /// arbitrary order.
+    #[inline]
+    pub fn auto_created_keys(&self) -> Iter<'_, K> {
+        self.listener().iter()
+    }
+
+    /// Removes every auto-created entry from the map and returns how many were removed.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::TrackedHashMap;
+    ///
+    /// let mut map = TrackedHashMap::<&str, u8>::tracked();
+    /// map.insert("a", 1);
+    /// for key in ["a", "b", "c"] {
+    ///     let _ = map.get(&key);
+    ///     let _ = map.get_mut(&key);
+    /// }
+    ///
+    /// assert_eq!(2, map.remove_auto_created());
+    /// assert_eq!(1, map.len());
+    /// ```
+    pub fn remove_auto_created(&mut self) -> usize {
+        let keys: Vec<K> = self.auto_created_keys().cloned().collect();
+        for key in &keys {
+            let _ = self.remove(key);
+        }
+        keys.len()
+    }
+}
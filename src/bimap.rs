@@ -0,0 +1,311 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::Iter;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A one-to-one map that can be looked up from either side, returning a default value on a miss.
+///
+/// Every left value is paired with at most one right value and the other way around. Inserting a
+/// pair removes the pairs that either value was part of before, so both directions always agree.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultBiMap;
+///
+/// let mut map = DefaultBiMap::new();
+/// map.insert(1, "one");
+/// map.insert(2, "two");
+///
+/// assert_eq!(&"one", map.get_by_left(&1));
+/// assert_eq!(&2, map.get_by_right(&"two"));
+/// assert_eq!(&"", map.get_by_left(&3));
+/// assert_eq!(&0, map.get_by_right(&"three"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultBiMap<L, R>
+where
+    L: Eq + Hash,
+    R: Eq + Hash,
+{
+    _left: HashMap<L, R>,
+    _right: HashMap<R, L>,
+    _left_default: L,
+    _right_default: R,
+}
+
+impl<L, R> DefaultBiMap<L, R>
+where
+    L: Eq + Hash + Clone + Default,
+    R: Eq + Hash + Clone + Default,
+{
+    /// Creates an empty [`DefaultBiMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let map = DefaultBiMap::<i8, String>::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _left: HashMap::new(),
+            _right: HashMap::new(),
+            _left_default: L::default(),
+            _right_default: R::default(),
+        }
+    }
+
+    /// Removes all pairs from the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    /// map.clear();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self._left.clear();
+        self._right.clear();
+    }
+
+    /// Returns `true` if the left value passed in is part of a pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert!(map.contains_left(&1));
+    /// assert!(!map.contains_left(&2));
+    /// ```
+    #[inline]
+    pub fn contains_left(&self, left: &L) -> bool {
+        self._left.contains_key(left)
+    }
+
+    /// Returns `true` if the right value passed in is part of a pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert!(map.contains_right(&2));
+    /// assert!(!map.contains_right(&1));
+    /// ```
+    #[inline]
+    pub fn contains_right(&self, right: &R) -> bool {
+        self._right.contains_key(right)
+    }
+
+    /// Returns the right value paired with the left value passed in, or the default right value if
+    /// there is no such pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&2, map.get_by_left(&1));
+    /// assert_eq!(&0, map.get_by_left(&2));
+    /// ```
+    #[must_use]
+    pub fn get_by_left(&self, left: &L) -> &R {
+        self._left.get(left).unwrap_or(&self._right_default)
+    }
+
+    /// Returns the left value paired with the right value passed in, or the default left value if
+    /// there is no such pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&1, map.get_by_right(&2));
+    /// assert_eq!(&0, map.get_by_right(&1));
+    /// ```
+    #[must_use]
+    pub fn get_by_right(&self, right: &R) -> &L {
+        self._right.get(right).unwrap_or(&self._left_default)
+    }
+
+    /// Inserts a pair, removing any pair that contained either value. Returns the right value that
+    /// `left` was paired with and the left value that `right` was paired with.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert_eq!((Some(10), Some(2)), map.insert(1, 20));
+    /// assert_eq!(1, map.len());
+    /// assert_eq!(&0, map.get_by_left(&2));
+    /// ```
+    pub fn insert(&mut self, left: L, right: R) -> (Option<R>, Option<L>) {
+        let old_right = self._left.remove(&left);
+        if let Some(old_right) = &old_right {
+            self._right.remove(old_right);
+        }
+        let old_left = self._right.remove(&right);
+        if let Some(old_left) = &old_left {
+            self._left.remove(old_left);
+        }
+        self._left.insert(left.clone(), right.clone());
+        self._right.insert(right, left);
+        (old_right, old_left)
+    }
+
+    /// Returns `true` if the map does not contain any pairs.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let map = DefaultBiMap::<i8, i8>::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._left.is_empty()
+    }
+
+    /// An iterator visiting all pairs as `(left, right)` in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(vec![(&1, &2)], map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> Iter<'_, L, R> {
+        self._left.iter()
+    }
+
+    /// Returns the number of pairs in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    /// map.insert(3, 4);
+    ///
+    /// assert_eq!(2, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._left.len()
+    }
+
+    /// Removes the pair containing the left value passed in, returning its right value or the
+    /// default right value if there was no such pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(2, map.remove_by_left(&1));
+    /// assert_eq!(0, map.remove_by_left(&1));
+    /// assert!(!map.contains_right(&2));
+    /// ```
+    #[must_use]
+    pub fn remove_by_left(&mut self, left: &L) -> R {
+        match self._left.remove(left) {
+            Some(right) => {
+                self._right.remove(&right);
+                right
+            }
+            None => R::default(),
+        }
+    }
+
+    /// Removes the pair containing the right value passed in, returning its left value or the
+    /// default left value if there was no such pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBiMap;
+    ///
+    /// let mut map = DefaultBiMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(1, map.remove_by_right(&2));
+    /// assert_eq!(0, map.remove_by_right(&2));
+    /// assert!(!map.contains_left(&1));
+    /// ```
+    #[must_use]
+    pub fn remove_by_right(&mut self, right: &R) -> L {
+        match self._right.remove(right) {
+            Some(left) => {
+                self._left.remove(&left);
+                left
+            }
+            None => L::default(),
+        }
+    }
+}
+
+impl<L, R> Default for DefaultBiMap<L, R>
+where
+    L: Eq + Hash + Clone + Default,
+    R: Eq + Hash + Clone + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L, R> FromIterator<(L, R)> for DefaultBiMap<L, R>
+where
+    L: Eq + Hash + Clone + Default,
+    R: Eq + Hash + Clone + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (L, R)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (left, right) in iter {
+            map.insert(left, right);
+        }
+        map
+    }
+}
+
+impl<'a, L, R> IntoIterator for &'a DefaultBiMap<L, R>
+where
+    L: Eq + Hash + Clone + Default,
+    R: Eq + Hash + Clone + Default,
+{
+    type Item = (&'a L, &'a R);
+    type IntoIter = Iter<'a, L, R>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
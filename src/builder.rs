@@ -0,0 +1,202 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::{HashMap, RandomState};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::default_hashmap::DefaultFn;
+use crate::{DefaultHashMap, Policy, ShrinkPolicy};
+
+/// A builder to configure a [`DefaultHashMap`] before it is constructed. This is created by
+/// calling [`DefaultHashMap::builder`].
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultHashMap, Policy};
+///
+/// let map: DefaultHashMap<i8, Vec<i8>> = DefaultHashMap::builder()
+///     .capacity(10)
+///     .default_fn(|| vec![1, 2, 3])
+///     .policy(Policy::InsertOnRead)
+///     .build();
+///
+/// assert_eq!(&vec![1, 2, 3], map.get(&1));
+/// ```
+pub struct DefaultHashMapBuilder<K, V, S = RandomState> {
+    _capacity: usize,
+    _hasher: S,
+    _default_fn: Option<DefaultFn<V>>,
+    _policy: Policy,
+    _shrink: Option<ShrinkPolicy>,
+    _key: PhantomData<K>,
+}
+
+impl<K, V> DefaultHashMapBuilder<K, V, RandomState> {
+    /// Creates a builder with no capacity, a [`RandomState`] hasher, the [`Default`] value of `V`
+    /// and the default [`Policy`].
+    pub(crate) fn new() -> Self {
+        DefaultHashMapBuilder {
+            _capacity: 0,
+            _hasher: RandomState::new(),
+            _default_fn: None,
+            _policy: Policy::default(),
+            _shrink: None,
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K, V, S> DefaultHashMapBuilder<K, V, S> {
+    /// Sets the number of elements the map can hold without reallocating.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map: DefaultHashMap<i8, i8> = DefaultHashMap::builder().capacity(100).build();
+    ///
+    /// assert!(map.capacity() >= 100);
+    /// ```
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self._capacity = capacity;
+        self
+    }
+
+    /// Sets the hash builder the map will use to hash keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut map: DefaultHashMap<i8, i8, RandomState> = DefaultHashMap::builder()
+    ///     .hasher(RandomState::new())
+    ///     .build();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn hasher<T>(self, hash_builder: T) -> DefaultHashMapBuilder<K, V, T> {
+        DefaultHashMapBuilder {
+            _capacity: self._capacity,
+            _hasher: hash_builder,
+            _default_fn: self._default_fn,
+            _policy: self._policy,
+            _shrink: self._shrink,
+            _key: PhantomData,
+        }
+    }
+
+    /// Sets the value that is returned for missing keys and inserted when a missing key is
+    /// accessed mutably. This replaces a function set with
+    /// [`DefaultHashMapBuilder::default_fn`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map: DefaultHashMap<i8, i8> = DefaultHashMap::builder().default_value(5).build();
+    ///
+    /// assert_eq!(&5, map.get(&1));
+    /// assert_eq!(5, map.remove(&1));
+    /// ```
+    #[must_use]
+    pub fn default_value(self, value: V) -> Self
+    where
+        V: Clone + Send + Sync + 'static,
+    {
+        self.default_fn(move || value.clone())
+    }
+
+    /// Sets a function that creates the value for missing keys. It is called once when the map is
+    /// built and every time a missing key is accessed mutably. This replaces a value set with
+    /// [`DefaultHashMapBuilder::default_value`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map: DefaultHashMap<i8, String> = DefaultHashMap::builder()
+    ///     .default_fn(|| String::from("empty"))
+    ///     .build();
+    ///
+    /// map.get_mut(&1).push('!');
+    ///
+    /// assert_eq!("empty!", map.get(&1));
+    /// assert_eq!("empty", map.get(&2));
+    /// ```
+    #[must_use]
+    pub fn default_fn<F>(mut self, func: F) -> Self
+    where
+        F: Fn() -> V + Send + Sync + 'static,
+    {
+        self._default_fn = Some(Arc::new(func));
+        self
+    }
+
+    /// Sets the [`Policy`] of the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, Policy};
+    ///
+    /// let map: DefaultHashMap<i8, i8> = DefaultHashMap::builder()
+    ///     .policy(Policy::InsertOnRead)
+    ///     .build();
+    ///
+    /// assert_eq!(Policy::InsertOnRead, map.policy());
+    /// ```
+    #[must_use]
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self._policy = policy;
+        self
+    }
+
+    /// Sets the [`ShrinkPolicy`] of the map, so it gives memory back after removing many entries.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, ShrinkPolicy};
+    ///
+    /// let map: DefaultHashMap<i8, i8> = DefaultHashMap::builder()
+    ///     .shrink_policy(ShrinkPolicy::new(0.5))
+    ///     .build();
+    ///
+    /// assert_eq!(Some(ShrinkPolicy::new(0.5)), map.shrink_policy());
+    /// ```
+    #[must_use]
+    pub fn shrink_policy(mut self, policy: ShrinkPolicy) -> Self {
+        self._shrink = Some(policy);
+        self
+    }
+}
+
+impl<K, V, S> DefaultHashMapBuilder<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Consumes the builder and creates the configured [`DefaultHashMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map: DefaultHashMap<i8, i8> = DefaultHashMap::builder().build();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn build(self) -> DefaultHashMap<K, V, S> {
+        DefaultHashMap::from_parts(
+            HashMap::with_capacity_and_hasher(self._capacity, self._hasher),
+            self._default_fn,
+            self._policy,
+            self._shrink,
+        )
+    }
+}
@@ -0,0 +1,169 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Listener, ObservedHashMap};
+
+/// How an entry differs from the state it had at the last checkpoint of a
+/// [`CheckpointedHashMap`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Change {
+    /// The key was not present at the checkpoint and is present now.
+    Inserted,
+    /// The key was present at the checkpoint and its value was written since.
+    Modified,
+    /// The key was present at the checkpoint and is not present now.
+    Removed,
+}
+
+/// A [`Listener`] that remembers which keys were touched since the last checkpoint, and whether
+/// each of them was present at that checkpoint. Keys changed in place through
+/// [`ObservedHashMap::get_mut`] are tracked once the guard is dropped.
+#[derive(Clone, Debug)]
+pub struct ChangeTracker<K>
+where
+    K: Eq + Hash,
+{
+    _touched: HashMap<K, bool>,
+}
+
+impl<K> ChangeTracker<K>
+where
+    K: Eq + Hash,
+{
+    /// Creates a tracker that has not seen any changes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _touched: HashMap::new(),
+        }
+    }
+
+    /// Forgets every change, making the current state of the map the new baseline.
+    #[inline]
+    pub fn clear(&mut self) {
+        self._touched.clear();
+    }
+
+    /// Returns `true` if no key was touched since the last checkpoint.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._touched.is_empty()
+    }
+
+    /// Returns the number of keys touched since the last checkpoint. This includes keys that were
+    /// inserted and removed again.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._touched.len()
+    }
+
+    /// Records that the key was touched, keeping whether it was present at the checkpoint if it
+    /// was touched before.
+    fn touch(&mut self, key: &K, existed: bool)
+    where
+        K: Clone,
+    {
+        if !self._touched.contains_key(key) {
+            self._touched.insert(key.clone(), existed);
+        }
+    }
+}
+
+impl<K> Default for ChangeTracker<K>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Listener<K, V> for ChangeTracker<K>
+where
+    K: Eq + Hash + Clone,
+{
+    fn on_insert(&mut self, key: &K, _value: &V) {
+        self.touch(key, false);
+    }
+
+    fn on_overwrite(&mut self, key: &K, _old: &V, _new: &V) {
+        self.touch(key, true);
+    }
+
+    fn on_remove(&mut self, key: &K, _value: &V) {
+        self.touch(key, true);
+    }
+
+    fn on_default(&mut self, key: &K, _value: &V) {
+        self.touch(key, false);
+    }
+}
+
+/// An [`ObservedHashMap`] that tracks which entries changed since its last checkpoint, so only
+/// those have to be synchronized somewhere else.
+///
+/// # Example
+/// ```
+/// use defaultdict::{Change, CheckpointedHashMap};
+///
+/// let mut map = CheckpointedHashMap::change_tracked();
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+/// map.checkpoint();
+///
+/// map.update("a", |value| *value += 1);
+/// let _ = map.remove(&"b");
+/// map.insert("c", 3);
+///
+/// let mut changes: Vec<(&&str, Change)> = map.changed_since_checkpoint().collect();
+/// changes.sort();
+///
+/// assert_eq!(
+///     vec![(&"a", Change::Modified), (&"b", Change::Removed), (&"c", Change::Inserted)],
+///     changes
+/// );
+/// ```
+pub type CheckpointedHashMap<K, V, S = RandomState> = ObservedHashMap<K, V, ChangeTracker<K>, S>;
+
+impl<K, V> ObservedHashMap<K, V, ChangeTracker<K>, RandomState>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+{
+    /// Creates an empty map that tracks its changes since the last checkpoint.
+    #[must_use]
+    pub fn change_tracked() -> Self {
+        Self::new(ChangeTracker::new())
+    }
+}
+
+impl<K, V, S> ObservedHashMap<K, V, ChangeTracker<K>, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+    S: BuildHasher,
+{
+    /// An iterator over the keys that changed since the last checkpoint and how they changed, in
+    /// arbitrary order. A key that was inserted and removed again is not reported. The current
+    /// values can be read with [`ObservedHashMap::get`].
+    pub fn changed_since_checkpoint(&self) -> impl Iterator<Item = (&K, Change)> {
+        self.listener()
+            ._touched
+            .iter()
+            .filter_map(|(key, &existed)| match (existed, self.contains_key(key)) {
+                (true, true) => Some((key, Change::Modified)),
+                (true, false) => Some((key, Change::Removed)),
+                (false, true) => Some((key, Change::Inserted)),
+                (false, false) => None,
+            })
+    }
+
+    /// Marks the current state of the map as the baseline that later changes are compared to.
+    #[inline]
+    pub fn checkpoint(&mut self) {
+        self.listener_mut().clear();
+    }
+}
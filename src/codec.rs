@@ -0,0 +1,87 @@
+#![deny(missing_docs)]
+
+/// Converts keys and values to and from bytes, so maps can be stored outside of memory.
+///
+/// Integers are encoded big-endian, which keeps the byte order of unsigned integers the same as
+/// their numeric order. `usize` and `isize` use the width of the platform they are encoded on.
+///
+/// # Example
+/// ```
+/// use defaultdict::Codec;
+///
+/// let bytes = 258u16.encode();
+///
+/// assert_eq!(vec![1, 2], bytes);
+/// assert_eq!(Some(258u16), u16::decode(&bytes));
+/// assert_eq!(None, u16::decode(&[1]));
+/// ```
+pub trait Codec: Sized {
+    /// Encodes the value as bytes.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decodes a value from bytes that were created by [`Codec::encode`]. Returns `None` if the
+    /// bytes are not a valid encoding.
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! codec_number {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl Codec for $ty {
+                fn encode(&self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+
+                fn decode(bytes: &[u8]) -> Option<Self> {
+                    Some(<$ty>::from_be_bytes(bytes.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+codec_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl Codec for bool {
+    fn encode(&self) -> Vec<u8> {
+        vec![u8::from(*self)]
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl Codec for char {
+    fn encode(&self) -> Vec<u8> {
+        u32::from(*self).encode()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        char::from_u32(u32::decode(bytes)?)
+    }
+}
+
+impl Codec for String {
+    fn encode(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+impl Codec for Vec<u8> {
+    fn encode(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        Some(bytes.to_vec())
+    }
+}
@@ -0,0 +1,195 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+/// An approximate counter that uses a fixed amount of memory no matter how many distinct keys
+/// are counted, using a count-min sketch.
+///
+/// The sketch is a table of `depth` rows of `width` counters. Every key increments one counter
+/// per row, and its estimate is the smallest of those counters. Estimates are never too low, and
+/// with a width of `⌈e / ε⌉` and a depth of `⌈ln(1 / δ)⌉` they are at most `ε * total` too high
+/// with a probability of at least `1 - δ`.
+///
+/// # Example
+/// ```
+/// use defaultdict::CountMinSketch;
+///
+/// let mut sketch = CountMinSketch::new(1024, 4);
+/// for word in "the cat and the hat".split(' ') {
+///     sketch.add(word);
+/// }
+///
+/// assert!(sketch.estimate("the") >= 2);
+/// assert_eq!(5, sketch.total());
+/// ```
+#[derive(Clone, Debug)]
+pub struct CountMinSketch<K, S = RandomState>
+where
+    K: Hash + ?Sized,
+{
+    _counters: Vec<u64>,
+    _width: usize,
+    _depth: usize,
+    _total: u64,
+    _hash_builder: S,
+    _key: PhantomData<fn(&K)>,
+}
+
+impl<K> CountMinSketch<K, RandomState>
+where
+    K: Hash + ?Sized,
+{
+    /// Creates an empty sketch with `depth` rows of `width` counters.
+    ///
+    /// # Panics
+    /// Panics if the width or depth is zero.
+    #[must_use]
+    pub fn new(width: usize, depth: usize) -> Self {
+        Self::with_hasher(width, depth, RandomState::new())
+    }
+
+    /// Creates an empty sketch that overestimates by at most `epsilon * total` with a probability
+    /// of at least `1 - delta`.
+    ///
+    /// # Panics
+    /// Panics if `epsilon` or `delta` is not between zero and one.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::<str>::with_error(0.01, 0.01);
+    ///
+    /// assert_eq!(272, sketch.width());
+    /// assert_eq!(5, sketch.depth());
+    /// ```
+    #[must_use]
+    pub fn with_error(epsilon: f64, delta: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon < 1.0,
+            "epsilon must be between zero and one"
+        );
+        assert!(
+            delta > 0.0 && delta < 1.0,
+            "delta must be between zero and one"
+        );
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil() as usize;
+        Self::new(width, depth.max(1))
+    }
+}
+
+impl<K, S> CountMinSketch<K, S>
+where
+    K: Hash + ?Sized,
+    S: BuildHasher,
+{
+    /// Creates an empty sketch with `depth` rows of `width` counters that hashes keys with the
+    /// given hash builder. Sketches can only be merged if they hash keys the same way.
+    ///
+    /// # Panics
+    /// Panics if the width or depth is zero.
+    #[must_use]
+    pub fn with_hasher(width: usize, depth: usize, hash_builder: S) -> Self {
+        assert!(
+            width > 0 && depth > 0,
+            "the width and depth must be positive"
+        );
+        Self {
+            _counters: vec![0; width * depth],
+            _width: width,
+            _depth: depth,
+            _total: 0,
+            _hash_builder: hash_builder,
+            _key: PhantomData,
+        }
+    }
+
+    /// Counts one occurrence of the key.
+    #[inline]
+    pub fn add(&mut self, key: &K) {
+        self.add_n(key, 1);
+    }
+
+    /// Counts `n` occurrences of the key.
+    pub fn add_n(&mut self, key: &K, n: u64) {
+        self._total = self._total.saturating_add(n);
+        for row in 0..self._depth {
+            let index = self.index(row, key);
+            self._counters[index] = self._counters[index].saturating_add(n);
+        }
+    }
+
+    /// Resets every counter to zero.
+    pub fn clear(&mut self) {
+        self._counters.fill(0);
+        self._total = 0;
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self._depth
+    }
+
+    /// Returns the estimated count of the key. This is never lower than the true count.
+    #[must_use]
+    pub fn estimate(&self, key: &K) -> u64 {
+        (0..self._depth)
+            .map(|row| self._counters[self.index(row, key)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Adds the counts of another sketch to this one, as if its keys had been added here.
+    ///
+    /// # Panics
+    /// Panics if the sketches have a different width or depth. The sketches must also hash keys
+    /// the same way, for example because one is a clone of the other, or the estimates become
+    /// meaningless.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::CountMinSketch;
+    ///
+    /// let mut a = CountMinSketch::new(64, 3);
+    /// let mut b = a.clone();
+    /// a.add_n(&7, 2);
+    /// b.add_n(&7, 3);
+    ///
+    /// a.merge(&b);
+    ///
+    /// assert!(a.estimate(&7) >= 5);
+    /// assert_eq!(5, a.total());
+    /// ```
+    pub fn merge(&mut self, other: &CountMinSketch<K, S>) {
+        assert!(
+            self._width == other._width && self._depth == other._depth,
+            "only sketches with the same dimensions can be merged"
+        );
+        for (counter, other) in self._counters.iter_mut().zip(&other._counters) {
+            *counter = counter.saturating_add(*other);
+        }
+        self._total = self._total.saturating_add(other._total);
+    }
+
+    /// Returns the total number of counted occurrences.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self._total
+    }
+
+    /// Returns the number of counters per row.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self._width
+    }
+
+    /// Returns the position of the counter of the key in the given row.
+    fn index(&self, row: usize, key: &K) -> usize {
+        let hash = self._hash_builder.hash_one((row, key));
+        row * self._width + (hash % self._width as u64) as usize
+    }
+}
@@ -0,0 +1,224 @@
+#![deny(missing_docs)]
+
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::hash::Hash;
+use std::iter;
+
+use crate::DefaultHashMap;
+
+/// A multiset that counts how often every key occurs, like python's `collections.Counter`. It is
+/// a thin layer over a [`DefaultHashMap<K, u64>`], so missing keys have a count of `0`.
+///
+/// Counts never go below zero. A key is removed once its count drops to zero, so
+/// [`Counter::len`] is the number of distinct keys that are currently counted. Like
+/// [`crate::CountMinSketch`] and [`crate::HeavyHitters`], keys are passed by reference and counts
+/// are `u64` values that saturate at [`u64::MAX`].
+///
+/// # Example
+/// ```
+/// use defaultdict::Counter;
+///
+/// let mut words: Counter<&str> = "a b a c a b".split(' ').collect();
+/// words.subtract(&"c");
+///
+/// assert_eq!(3, words.count("a"));
+/// assert_eq!(0, words.count("c"));
+/// assert_eq!(vec![(&"a", 3), (&"b", 2)], words.most_common(2));
+/// assert_eq!(5, words.total());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Counter<K>
+where
+    K: Eq + Hash,
+{
+    _counts: DefaultHashMap<K, u64>,
+    _total: u64,
+}
+
+impl<K> Counter<K>
+where
+    K: Eq + Hash,
+{
+    /// Creates an empty [`Counter`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _counts: DefaultHashMap::new(),
+            _total: 0,
+        }
+    }
+
+    /// Counts one occurrence of the key.
+    #[inline]
+    pub fn add(&mut self, key: &K)
+    where
+        K: Clone,
+    {
+        self.add_n(key, 1);
+    }
+
+    /// Counts `n` occurrences of the key. Adding zero occurrences does not store the key.
+    pub fn add_n(&mut self, key: &K, n: u64)
+    where
+        K: Clone,
+    {
+        if n == 0 {
+            return;
+        }
+        let count = self._counts.get_mut(key);
+        *count = count.saturating_add(n);
+        self._total = self._total.saturating_add(n);
+    }
+
+    /// Removes every key from the counter.
+    pub fn clear(&mut self) {
+        self._counts.clear();
+        self._total = 0;
+    }
+
+    /// Returns how often the key occurs, or `0` if it was never counted.
+    #[must_use]
+    pub fn count<Q>(&self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        *self._counts.get(key)
+    }
+
+    /// An iterator that returns every key as often as it was counted, in arbitrary order. Equal
+    /// keys are returned next to each other.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Counter;
+    ///
+    /// let letters: Counter<char> = "abba".chars().collect();
+    /// let mut elements: Vec<char> = letters.elements().copied().collect();
+    /// elements.sort();
+    ///
+    /// assert_eq!(vec!['a', 'a', 'b', 'b'], elements);
+    /// ```
+    pub fn elements(&self) -> impl Iterator<Item = &K> {
+        (&self._counts)
+            .into_iter()
+            .flat_map(|(key, count)| iter::repeat_n(key, *count as usize))
+    }
+
+    /// Returns `true` if nothing is counted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._counts.is_empty()
+    }
+
+    /// An iterator visiting every counted key and its count in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        (&self._counts)
+            .into_iter()
+            .map(|(key, count)| (key, *count))
+    }
+
+    /// Returns the number of distinct keys that are counted.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._counts.len()
+    }
+
+    /// Returns the `n` keys with the highest counts, from most to least common. Keys with equal
+    /// counts are returned in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Counter;
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.add_n(&'a', 5);
+    /// counter.add_n(&'b', 9);
+    /// counter.add_n(&'c', 1);
+    ///
+    /// assert_eq!(vec![(&'b', 9), (&'a', 5)], counter.most_common(2));
+    /// ```
+    pub fn most_common(&self, n: usize) -> Vec<(&K, u64)> {
+        let mut common: Vec<(&K, u64)> = self.iter().collect();
+        common.sort_by_key(|(_, count)| Reverse(*count));
+        common.truncate(n);
+        common
+    }
+
+    /// Removes one occurrence of the key.
+    #[inline]
+    pub fn subtract(&mut self, key: &K)
+    where
+        K: Clone,
+    {
+        self.subtract_n(key, 1);
+    }
+
+    /// Removes `n` occurrences of the key. The count stops at zero, at which point the key is
+    /// removed.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Counter;
+    ///
+    /// let mut stock = Counter::new();
+    /// stock.add_n(&"apple", 3);
+    /// stock.subtract_n(&"apple", 5);
+    ///
+    /// assert_eq!(0, stock.count("apple"));
+    /// assert!(stock.is_empty());
+    /// ```
+    pub fn subtract_n(&mut self, key: &K, n: u64)
+    where
+        K: Clone,
+    {
+        let count = *self._counts.get(key);
+        let removed = n.min(count);
+        if removed == count {
+            let _ = self._counts.remove(key);
+        } else {
+            *self._counts.get_mut(key) -= removed;
+        }
+        self._total -= removed;
+    }
+
+    /// Returns the sum of all counts.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self._total
+    }
+}
+
+impl<K> Default for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Extend<K> for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            let count = self._counts.entry(key).or_default();
+            *count = count.saturating_add(1);
+            self._total = self._total.saturating_add(1);
+        }
+    }
+}
+
+impl<K> FromIterator<K> for Counter<K>
+where
+    K: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
@@ -0,0 +1,194 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::DefaultHashMap;
+
+/// A copy-on-write [`DefaultHashMap`]. Cloning only increments a reference count, the entries are
+/// copied the first time a clone is mutated while it is still shared.
+///
+/// The read API of the inner map is available through [`Deref`], mutations go through
+/// [`CowHashMap::make_mut`] or the shortcuts on this type.
+///
+/// # Example
+/// ```
+/// use defaultdict::CowHashMap;
+///
+/// let mut map = CowHashMap::<i8, i8>::new();
+/// map.insert(1, 1);
+///
+/// let mut clone = map.clone();
+/// assert!(map.is_shared());
+///
+/// clone.insert(1, 2);
+///
+/// assert_eq!(&1, map.get(&1));
+/// assert_eq!(&2, clone.get(&1));
+/// assert!(!map.is_shared());
+/// ```
+#[derive(Debug)]
+pub struct CowHashMap<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _inner: Arc<DefaultHashMap<K, V, S>>,
+}
+
+impl<K, V> CowHashMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`CowHashMap`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from(DefaultHashMap::new())
+    }
+}
+
+impl<K, V> Default for CowHashMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> CowHashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+    S: BuildHasher + Clone,
+{
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if the key is not present. The map is copied first if it is shared.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::CowHashMap;
+    ///
+    /// let mut map = CowHashMap::<i8, i8>::new();
+    /// *map.get_mut(&1) += 1;
+    ///
+    /// assert_eq!(&1, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        self.make_mut().get_mut(key)
+    }
+
+    /// Inserts a key value pair into the map. The map is copied first if it is shared.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.make_mut().insert(key, value)
+    }
+
+    /// Consumes the wrapper and returns the inner [`DefaultHashMap`], copying it only if it is
+    /// still shared with a clone.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{CowHashMap, DefaultHashMap};
+    ///
+    /// let mut map = CowHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    ///
+    /// let map: DefaultHashMap<i8, i8> = map.into_inner();
+    ///
+    /// assert_eq!(&1, map.get(&1));
+    /// ```
+    pub fn into_inner(self) -> DefaultHashMap<K, V, S> {
+        Arc::try_unwrap(self._inner).unwrap_or_else(|shared| shared.as_ref().clone())
+    }
+
+    /// Returns a mutable reference to the inner [`DefaultHashMap`], copying it first if it is
+    /// shared with a clone.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::CowHashMap;
+    ///
+    /// let mut map = CowHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    ///
+    /// let clone = map.clone();
+    /// map.make_mut().retain(|_, _| false);
+    ///
+    /// assert!(map.is_empty());
+    /// assert_eq!(1, clone.len());
+    /// ```
+    pub fn make_mut(&mut self) -> &mut DefaultHashMap<K, V, S> {
+        Arc::make_mut(&mut self._inner)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise. The map is copied first if it is shared.
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        self.make_mut().remove(key)
+    }
+}
+
+impl<K, V, S> CowHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Returns `true` if the entries are shared with at least one clone.
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self._inner) > 1
+    }
+}
+
+impl<K, V, S> Clone for CowHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _inner: Arc::clone(&self._inner),
+        }
+    }
+}
+
+impl<K, V, S> Deref for CowHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    type Target = DefaultHashMap<K, V, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self._inner
+    }
+}
+
+impl<K, V, S> From<DefaultHashMap<K, V, S>> for CowHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn from(map: DefaultHashMap<K, V, S>) -> Self {
+        Self {
+            _inner: Arc::new(map),
+        }
+    }
+}
+
+impl<K, V, S> PartialEq for CowHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: PartialEq + Default,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &CowHashMap<K, V, S>) -> bool {
+        Arc::ptr_eq(&self._inner, &other._inner) || self._inner == other._inner
+    }
+}
@@ -0,0 +1,237 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+
+use crate::DefaultHashMap;
+
+/// A grow-only counter per key that can be replicated: every replica only increments its own
+/// counts, and replicas converge by exchanging their maps with [`GCounterMap::merge`].
+///
+/// Merging keeps the largest count of every replica per key, so it is commutative, associative
+/// and idempotent. Merging the same state twice or in a different order gives the same result.
+///
+/// # Example
+/// ```
+/// use defaultdict::GCounterMap;
+///
+/// let mut a = GCounterMap::new("a");
+/// let mut b = GCounterMap::new("b");
+/// a.increment("views", 2);
+/// b.increment("views", 3);
+///
+/// a.merge(&b);
+/// b.merge(&a);
+/// a.merge(&b);
+///
+/// assert_eq!(5, a.get(&"views"));
+/// assert_eq!(5, b.get(&"views"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GCounterMap<K, R>
+where
+    K: Eq + Hash,
+    R: Eq + Hash,
+{
+    _replica: R,
+    _counts: DefaultHashMap<K, DefaultHashMap<R, u64>>,
+}
+
+impl<K, R> GCounterMap<K, R>
+where
+    K: Eq + Hash,
+    R: Eq + Hash + Clone,
+{
+    /// Creates an empty counter map for the given replica.
+    #[must_use]
+    pub fn new(replica: R) -> Self {
+        Self {
+            _replica: replica,
+            _counts: DefaultHashMap::new(),
+        }
+    }
+
+    /// Returns `true` if the key has been incremented on any replica.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._counts.contains_key(key)
+    }
+
+    /// Returns the total count of the key over all replicas, or `0` if the key was never
+    /// incremented.
+    #[must_use]
+    pub fn get(&self, key: &K) -> u64 {
+        self._counts.get(key).values().sum()
+    }
+
+    /// Adds `amount` to the count of the key on this replica.
+    pub fn increment(&mut self, key: K, amount: u64) {
+        let count = self._counts.entry(key).or_default();
+        let count = count.entry(self._replica.clone()).or_default();
+        *count = count.saturating_add(amount);
+    }
+
+    /// Returns `true` if no key has been incremented.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._counts.is_empty()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self._counts.keys()
+    }
+
+    /// Returns the number of keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._counts.len()
+    }
+
+    /// Merges the state of another replica into this one, keeping the largest count of every
+    /// replica per key.
+    pub fn merge(&mut self, other: &GCounterMap<K, R>)
+    where
+        K: Clone,
+    {
+        for (key, replicas) in &other._counts {
+            let counts = self._counts.entry(key.clone()).or_default();
+            for (replica, count) in replicas {
+                let current = counts.entry(replica.clone()).or_default();
+                *current = (*current).max(*count);
+            }
+        }
+    }
+
+    /// Returns the replica this map increments.
+    #[inline]
+    pub fn replica(&self) -> &R {
+        &self._replica
+    }
+
+    /// Returns the total count of every key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::GCounterMap;
+    ///
+    /// let mut counter = GCounterMap::new(1);
+    /// counter.increment('x', 4);
+    ///
+    /// let totals = counter.to_map();
+    ///
+    /// assert_eq!(&4, totals.get(&'x'));
+    /// assert_eq!(&0, totals.get(&'y'));
+    /// ```
+    pub fn to_map(&self) -> DefaultHashMap<K, u64>
+    where
+        K: Clone,
+    {
+        self._counts
+            .keys()
+            .map(|key| (key.clone(), self.get(key)))
+            .collect()
+    }
+}
+
+/// A counter per key that can be incremented and decremented and replicated, built from two
+/// [`GCounterMap`]s. The value of a key is its increments minus its decrements over all replicas.
+///
+/// Like [`GCounterMap::merge`], [`PNCounterMap::merge`] is commutative, associative and
+/// idempotent, so replicas converge no matter how often or in which order they exchange state.
+///
+/// # Example
+/// ```
+/// use defaultdict::PNCounterMap;
+///
+/// let mut a = PNCounterMap::new(1);
+/// let mut b = PNCounterMap::new(2);
+/// a.increment("stock", 10);
+/// b.decrement("stock", 3);
+///
+/// a.merge(&b);
+///
+/// assert_eq!(7, a.get(&"stock"));
+/// assert_eq!(-3, b.get(&"stock"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PNCounterMap<K, R>
+where
+    K: Eq + Hash,
+    R: Eq + Hash,
+{
+    _increments: GCounterMap<K, R>,
+    _decrements: GCounterMap<K, R>,
+}
+
+impl<K, R> PNCounterMap<K, R>
+where
+    K: Eq + Hash,
+    R: Eq + Hash + Clone,
+{
+    /// Creates an empty counter map for the given replica.
+    #[must_use]
+    pub fn new(replica: R) -> Self {
+        Self {
+            _increments: GCounterMap::new(replica.clone()),
+            _decrements: GCounterMap::new(replica),
+        }
+    }
+
+    /// Returns `true` if the key has been incremented or decremented on any replica.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._increments.contains_key(key) || self._decrements.contains_key(key)
+    }
+
+    /// Subtracts `amount` from the count of the key on this replica.
+    #[inline]
+    pub fn decrement(&mut self, key: K, amount: u64) {
+        self._decrements.increment(key, amount);
+    }
+
+    /// Returns the count of the key over all replicas, or `0` if the key was never changed.
+    #[must_use]
+    pub fn get(&self, key: &K) -> i64 {
+        let count = i128::from(self._increments.get(key)) - i128::from(self._decrements.get(key));
+        count.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+    }
+
+    /// Adds `amount` to the count of the key on this replica.
+    #[inline]
+    pub fn increment(&mut self, key: K, amount: u64) {
+        self._increments.increment(key, amount);
+    }
+
+    /// Returns `true` if no key has been changed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._increments.is_empty() && self._decrements.is_empty()
+    }
+
+    /// Merges the state of another replica into this one.
+    pub fn merge(&mut self, other: &PNCounterMap<K, R>)
+    where
+        K: Clone,
+    {
+        self._increments.merge(&other._increments);
+        self._decrements.merge(&other._decrements);
+    }
+
+    /// Returns the replica this map changes.
+    #[inline]
+    pub fn replica(&self) -> &R {
+        self._increments.replica()
+    }
+
+    /// Returns the count of every key that has been changed.
+    pub fn to_map(&self) -> DefaultHashMap<K, i64>
+    where
+        K: Clone,
+    {
+        self._increments
+            .keys()
+            .chain(self._decrements.keys())
+            .map(|key| (key.clone(), self.get(key)))
+            .collect()
+    }
+}
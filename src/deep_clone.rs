@@ -0,0 +1,199 @@
+#![deny(missing_docs)]
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::{DefaultBTreeMap, DefaultHashMap};
+
+/// A clone that shares no state with the original, like `copy.deepcopy` in python.
+///
+/// A regular [`Clone`] of an [`Rc`] or an [`Arc`] shares the value it points to, so changes made
+/// through interior mutability show up in both copies. A deep clone allocates a new pointer with
+/// a deep clone of the value instead. Maps keep the configuration of the original, their keys are
+/// cloned normally since keys can not be changed while they are in a map.
+///
+/// Two pointers to the same value become two separate values, a deep clone does not keep track
+/// of shared values the way `copy.deepcopy` does.
+///
+/// # Example
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use defaultdict::{DeepClone, DefaultHashMap};
+///
+/// let mut scores = DefaultHashMap::<&str, DefaultHashMap<&str, Rc<RefCell<u32>>>>::new();
+/// scores.get_mut(&"ada").insert("math", Rc::new(RefCell::new(7)));
+///
+/// let copy = scores.deep_clone();
+/// *scores.get(&"ada").get(&"math").borrow_mut() += 1;
+///
+/// assert_eq!(8, *scores.get(&"ada").get(&"math").borrow());
+/// assert_eq!(7, *copy.get(&"ada").get(&"math").borrow());
+/// ```
+pub trait DeepClone: Clone {
+    /// Returns a copy of the value that shares no state with it.
+    #[must_use]
+    fn deep_clone(&self) -> Self;
+}
+
+macro_rules! deep_clone_by_clone {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl DeepClone for $ty {
+                #[inline]
+                fn deep_clone(&self) -> Self {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+deep_clone_by_clone!(
+    (),
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    String,
+    &'static str
+);
+
+impl<T> DeepClone for Option<T>
+where
+    T: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        self.as_ref().map(DeepClone::deep_clone)
+    }
+}
+
+impl<T> DeepClone for Vec<T>
+where
+    T: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        self.iter().map(DeepClone::deep_clone).collect()
+    }
+}
+
+impl<T> DeepClone for Box<T>
+where
+    T: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        Box::new((**self).deep_clone())
+    }
+}
+
+impl<T> DeepClone for Rc<T>
+where
+    T: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        Rc::new((**self).deep_clone())
+    }
+}
+
+impl<T> DeepClone for Arc<T>
+where
+    T: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        Arc::new((**self).deep_clone())
+    }
+}
+
+impl<T> DeepClone for RefCell<T>
+where
+    T: DeepClone,
+{
+    /// # Panics
+    /// Panics if the value is currently mutably borrowed.
+    fn deep_clone(&self) -> Self {
+        RefCell::new(self.borrow().deep_clone())
+    }
+}
+
+impl<A, B> DeepClone for (A, B)
+where
+    A: DeepClone,
+    B: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        (self.0.deep_clone(), self.1.deep_clone())
+    }
+}
+
+impl<K, V, S> DeepClone for HashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: DeepClone,
+    S: BuildHasher + Clone,
+{
+    fn deep_clone(&self) -> Self {
+        let mut map = HashMap::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        map.extend(
+            self.iter()
+                .map(|(key, value)| (key.clone(), value.deep_clone())),
+        );
+        map
+    }
+}
+
+impl<K, V> DeepClone for BTreeMap<K, V>
+where
+    K: Ord + Clone,
+    V: DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        self.iter()
+            .map(|(key, value)| (key.clone(), value.deep_clone()))
+            .collect()
+    }
+}
+
+impl<K, V, S> DeepClone for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default + DeepClone,
+    S: BuildHasher + Clone,
+{
+    fn deep_clone(&self) -> Self {
+        let mut map = self.empty_like();
+        for (key, value) in self {
+            map.insert(key.clone(), value.deep_clone());
+        }
+        map
+    }
+}
+
+impl<K, V> DeepClone for DefaultBTreeMap<K, V>
+where
+    K: Ord + Clone,
+    V: Default + DeepClone,
+{
+    fn deep_clone(&self) -> Self {
+        let mut map = self.empty_like();
+        for (key, value) in self {
+            map.insert(key.clone(), value.deep_clone());
+        }
+        map
+    }
+}
@@ -1,6 +1,7 @@
 #![deny(missing_docs)]
 
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 use std::collections::{
     btree_map::{
         Entry, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, OccupiedEntry, Range, RangeMut,
@@ -9,19 +10,93 @@ use std::collections::{
     BTreeMap,
 };
 use std::default::Default;
-use std::ops::{Index, RangeBounds};
+use std::fmt;
+use std::iter::{Cloned, Peekable};
+use std::ops::{self, Bound, Index, IndexMut, RangeBounds};
+use std::sync::OnceLock;
+
+use crate::diff::MapDiff;
+use crate::error::DuplicateKeyError;
+
+/// A default value that applies to the missing keys in a range, see
+/// [`DefaultBTreeMap::set_range_default`].
+struct RangeDefault<K, V> {
+    range: ops::Range<K>,
+    value: V,
+    /// Creates the value that is inserted for a missing key from `value`.
+    make: fn(&V) -> V,
+}
+
+impl<K, V> RangeDefault<K, V>
+where
+    K: Clone,
+{
+    /// Copies the range default through `make`, which does not need `V: Clone`.
+    fn duplicate(&self) -> Self {
+        Self {
+            range: self.range.clone(),
+            value: (self.make)(&self.value),
+            make: self.make,
+        }
+    }
+}
+
+impl<K, V> Clone for RangeDefault<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            range: self.range.clone(),
+            value: self.value.clone(),
+            make: self.make,
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for RangeDefault<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RangeDefault")
+            .field("range", &self.range)
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, V> PartialEq for RangeDefault<K, V>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.range == other.range && self.value == other.value
+    }
+}
+
+impl<K, V> Eq for RangeDefault<K, V>
+where
+    K: Eq,
+    V: Eq,
+{
+}
 
 /// This struct mimicks the behaviour of a python defaultdict. This means alongside the traitbounds
 /// that apply on the key and value that are inherited from the [`BTreeMap`], it also requires the
 /// [`Default`] trait be implemented on the value type.
-#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DefaultBTreeMap<K, V>
 where
     K: Eq + Ord,
     V: Default,
 {
     _inner: BTreeMap<K, V>,
-    _default: V,
+    /// Created on first use, so [`DefaultBTreeMap::new`] can be a `const fn`.
+    _default: OnceLock<V>,
+    _range_defaults: Vec<RangeDefault<K, V>>,
 }
 
 impl<K, V> DefaultBTreeMap<K, V>
@@ -31,6 +106,9 @@ where
 {
     /// Creates an empty [`DefaultBTreeMap`].
     ///
+    /// This is a `const fn`, the default value is only created once it is first needed. That
+    /// allows a map to be used as a global without lazy initialization.
+    ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultBTreeMap;
@@ -38,15 +116,70 @@ where
     /// let map = DefaultBTreeMap::<i8, i8>::new();
     /// assert_eq!(&0, map.get(&123));
     /// ```
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    ///
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// static HITS: Mutex<DefaultBTreeMap<&str, u32>> = Mutex::new(DefaultBTreeMap::new());
+    ///
+    /// *HITS.lock().unwrap().get_mut(&"/index") += 1;
+    ///
+    /// assert_eq!(&1, HITS.lock().unwrap().get(&"/index"));
+    /// ```
     #[must_use]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             _inner: BTreeMap::new(),
-            _default: V::default(),
+            _default: OnceLock::new(),
+            _range_defaults: Vec::new(),
         }
     }
 
-    /// Moves all elements from other into self, leaving other empty.
+    /// Returns `true` if the predicate holds for every entry of the map. An empty map returns
+    /// `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert!(map.all(|_, value| *value >= 10));
+    /// assert!(!map.all(|key, _| *key > 1));
+    /// ```
+    pub fn all<F>(&self, mut pred: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self._inner.iter().all(|(key, value)| pred(key, value))
+    }
+
+    /// Returns `true` if the predicate holds for at least one entry of the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert!(map.any(|_, value| *value == 20));
+    /// assert!(!map.any(|key, _| *key > 2));
+    /// ```
+    pub fn any<F>(&self, mut pred: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self._inner.iter().any(|(key, value)| pred(key, value))
+    }
+
+    /// Moves all elements from other into self, leaving other empty. The range defaults of other
+    /// are not moved, so missing keys keep the range defaults of this map.
     ///
     /// # Example
     /// ```
@@ -76,6 +209,33 @@ where
         self._inner.append(&mut other._inner);
     }
 
+    /// Replays a [`MapDiff`] onto the map: added and changed keys are inserted with their new
+    /// value and removed keys are removed.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaultbtreemap, DefaultBTreeMap};
+    ///
+    /// let old: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 1), (2, 2));
+    /// let new: DefaultBTreeMap<i8, i8> = defaultbtreemap!((2, 3), (4, 4));
+    ///
+    /// let mut mirror = old.clone();
+    /// mirror.apply_patch(old.diff(&new));
+    ///
+    /// assert_eq!(new, mirror);
+    /// ```
+    pub fn apply_patch(&mut self, diff: MapDiff<K, V>) {
+        for key in diff._removed {
+            self._inner.remove(&key);
+        }
+        for (key, value) in diff._added {
+            self._inner.insert(key, value);
+        }
+        for (key, _, value) in diff._changed {
+            self._inner.insert(key, value);
+        }
+    }
+
     /// Clears the map, removing all elements.
     ///
     /// # Example
@@ -97,6 +257,29 @@ where
         self._inner.clear()
     }
 
+    /// Resets the value of every key to its default value, keeping the keys. Keys inside a range
+    /// set with [`DefaultBTreeMap::set_range_default`] get the default of that range.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    /// map.clear_to_default();
+    ///
+    /// assert_eq!(2, map.len());
+    /// assert_eq!(&0, map.get(&1));
+    /// ```
+    pub fn clear_to_default(&mut self) {
+        let mut inner = std::mem::take(&mut self._inner);
+        for (key, value) in inner.iter_mut() {
+            *value = self.make_default_for(key);
+        }
+        self._inner = inner;
+    }
+
     /// Returns true if the key passed in exists in the BTreeMap.
     ///
     /// # Example
@@ -113,6 +296,144 @@ where
         self._inner.contains_key(key)
     }
 
+    /// Returns `true` if any key in the map has the given value. This is a linear scan over all
+    /// values.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    ///
+    /// assert!(map.contains_value(&10));
+    /// assert!(!map.contains_value(&0));
+    /// ```
+    pub fn contains_value(&self, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self._inner.values().any(|candidate| candidate == value)
+    }
+
+    /// Returns the default value that applies to the key passed in: the value of the last range
+    /// set with [`DefaultBTreeMap::set_range_default`] that contains the key, or the default of
+    /// the value type if there is none. Whether the key is present in the map is not checked.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i32, i32>::new();
+    /// map.set_range_default(100..200, -1);
+    ///
+    /// assert_eq!(&0, map.default_for(&99));
+    /// assert_eq!(&-1, map.default_for(&100));
+    /// assert_eq!(&0, map.default_for(&200));
+    /// ```
+    #[must_use]
+    pub fn default_for(&self, key: &K) -> &V {
+        self._range_defaults
+            .iter()
+            .rev()
+            .find(|range_default| range_default.range.contains(key))
+            .map_or_else(|| self.base_default(), |range_default| &range_default.value)
+    }
+
+    /// Computes the [`MapDiff`] that turns this map into `other`. The keys in the diff are sorted.
+    ///
+    /// Keys that are missing from one of the maps are compared against the default value of that
+    /// map, so a key that only exists with the default value is not reported.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaultbtreemap, DefaultBTreeMap};
+    ///
+    /// let old: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 1), (2, 2), (3, 3));
+    /// let new: DefaultBTreeMap<i8, i8> = defaultbtreemap!((2, 5), (4, 4), (5, 5));
+    ///
+    /// let diff = old.diff(&new);
+    ///
+    /// assert_eq!(&[(4, 4), (5, 5)], diff.added());
+    /// assert_eq!(&[1, 3], diff.removed());
+    /// assert_eq!(&[(2, 2, 5)], diff.changed());
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &DefaultBTreeMap<K, V>) -> MapDiff<K, V>
+    where
+        K: Clone,
+        V: Clone + PartialEq,
+    {
+        let mut diff = MapDiff::new();
+        for (key, value) in &self._inner {
+            match other._inner.get(key) {
+                Some(new) if new != value => {
+                    diff._changed
+                        .push((key.clone(), value.clone(), new.clone()));
+                }
+                None if value != other.default_for(key) => diff._removed.push(key.clone()),
+                _ => {}
+            }
+        }
+        for (key, value) in &other._inner {
+            if !self._inner.contains_key(key) && value != self.default_for(key) {
+                diff._added.push((key.clone(), value.clone()));
+            }
+        }
+        diff
+    }
+
+    /// An iterator over the entries whose keys are in this map but not in `other`, in ascending key
+    /// order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut a = DefaultBTreeMap::<i8, i8>::new();
+    /// let mut b = DefaultBTreeMap::<i8, i8>::new();
+    /// a.insert(1, 10);
+    /// a.insert(2, 20);
+    /// b.insert(2, 21);
+    ///
+    /// assert_eq!(vec![(&1, &10)], a.difference(&b).collect::<Vec<_>>());
+    /// ```
+    pub fn difference<'a>(
+        &'a self,
+        other: &'a DefaultBTreeMap<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> {
+        self._inner
+            .iter()
+            .filter(|(key, _)| !other._inner.contains_key(*key))
+    }
+
+    /// Makes sure every key produced by the iterator is present, inserting the default value for
+    /// the keys that are missing. Keys that are already present keep their value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<&str, u32>::new();
+    /// map.insert("a", 1);
+    /// map.ensure_keys(["a", "b", "c"]);
+    ///
+    /// assert_eq!(3, map.len());
+    /// assert_eq!(&1, map.get(&"a"));
+    /// assert!(map.contains_key(&"c"));
+    /// ```
+    pub fn ensure_keys<I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        for key in keys {
+            if !self._inner.contains_key(&key) {
+                let value = self.make_default_for(&key);
+                self._inner.insert(key, value);
+            }
+        }
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Example
@@ -131,10 +452,54 @@ where
     /// assert_eq!(&12, map.get(&1));
     /// ```
     #[inline]
-    pub fn entry(&mut self, key: K) -> Entry<K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         self._inner.entry(key)
     }
 
+    /// Inserts the default value for every key in the range that is not present yet, which
+    /// pre-seeds dense maps such as histograms. Keys that are already present keep their value. Use
+    /// [`DefaultBTreeMap::ensure_keys`] for keys that do not form a range.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut histogram = DefaultBTreeMap::<u8, u32>::new();
+    /// histogram.insert(3, 7);
+    /// histogram.fill_range(0..5);
+    ///
+    /// let bins: Vec<(u8, u32)> = histogram.into_iter().collect();
+    ///
+    /// assert_eq!(vec![(0, 0), (1, 0), (2, 0), (3, 7), (4, 0)], bins);
+    /// ```
+    pub fn fill_range(&mut self, range: ops::Range<K>)
+    where
+        ops::Range<K>: Iterator<Item = K>,
+    {
+        self.ensure_keys(range);
+    }
+
+    /// Returns the first entry for which the predicate holds. Entries are visited in ascending key
+    /// order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert_eq!(Some((&2, &20)), map.find(|_, value| *value > 15));
+    /// assert_eq!(None, map.find(|_, value| *value > 25));
+    /// ```
+    pub fn find<F>(&self, mut pred: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self._inner.iter().find(|(key, value)| pred(key, value))
+    }
+
     /// Returns the first entry in the map for in-place manipulation. The key of this entry is the
     /// minimum key in the map.
     ///
@@ -156,7 +521,7 @@ where
     /// assert_eq!(&12, map.get(&1));
     /// ```
     #[inline]
-    pub fn first_entry(&mut self) -> Option<OccupiedEntry<K, V>>
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>>
     where
         K: Ord,
     {
@@ -190,6 +555,60 @@ where
         self._inner.first_key_value()
     }
 
+    /// Returns the first key-value pair in the map, or the key passed in with its default value
+    /// if the map is empty. The key in the map is the minimum key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    ///
+    /// assert_eq!((&0, &0), map.first_key_value_or_default(&0));
+    ///
+    /// map.insert(3, 30);
+    /// map.insert(1, 10);
+    ///
+    /// assert_eq!((&1, &10), map.first_key_value_or_default(&0));
+    /// ```
+    #[must_use]
+    pub fn first_key_value_or_default<'a>(&'a self, key: &'a K) -> (&'a K, &'a V) {
+        self._inner
+            .first_key_value()
+            .unwrap_or_else(|| (key, self.default_for(key)))
+    }
+
+    /// Creates a map from an iterator of key value pairs, combining the values of duplicate keys
+    /// with `merge`. The first value of a key is stored as is, every later value is merged into
+    /// it.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let words = [("a", 1), ("b", 2), ("a", 3)];
+    /// let map = DefaultBTreeMap::<&str, u32>::from_iter_merge(words, |total, n| *total += n);
+    ///
+    /// assert_eq!(&4, map.get(&"a"));
+    /// assert_eq!(&2, map.get(&"b"));
+    /// ```
+    pub fn from_iter_merge<I, F>(iter: I, mut merge: F) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        F: FnMut(&mut V, V),
+    {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            match map._inner.entry(key) {
+                Entry::Occupied(mut entry) => merge(entry.get_mut(), value),
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+        map
+    }
+
     /// Returns a reference to the value of the key passed in.
     /// Because this btreemap mimicks the python defaultdict, it will also return a reference to a
     /// value if the key is not present.
@@ -208,7 +627,111 @@ where
     /// ```
     #[must_use]
     pub fn get(&self, key: &K) -> &V {
-        self._inner.get(key).unwrap_or(&self._default)
+        self._inner
+            .get(key)
+            .unwrap_or_else(|| self.default_for(key))
+    }
+
+    /// Returns the entry with the least key that is greater than or equal to the key passed in.
+    /// If there is no such key, the key passed in is returned together with its default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaultbtreemap, DefaultBTreeMap};
+    ///
+    /// let map: DefaultBTreeMap<i32, &str> = defaultbtreemap!((10, "low"), (20, "high"));
+    ///
+    /// assert_eq!((&10, &"low"), map.get_ceiling(&5));
+    /// assert_eq!((&20, &"high"), map.get_ceiling(&20));
+    /// assert_eq!((&25, &""), map.get_ceiling(&25));
+    /// ```
+    #[must_use]
+    pub fn get_ceiling<'a>(&'a self, key: &'a K) -> (&'a K, &'a V) {
+        self._inner
+            .range(key..)
+            .next()
+            .unwrap_or_else(|| (key, self.default_for(key)))
+    }
+
+    /// Returns mutable references to the values of several distinct keys at once, inserting the
+    /// default value for every key that is missing first. This allows moving values between keys
+    /// in a single call.
+    ///
+    /// The values are collected in one pass over the entries from the smallest to the largest
+    /// of the keys, so this takes O(n) time when the keys are far apart in the map, on top of
+    /// the O(N log n) lookups for the keys themselves.
+    ///
+    /// # Panics
+    /// Panics if any two keys are equal.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut stock = DefaultBTreeMap::<&str, u32>::new();
+    /// stock.insert("warehouse", 10);
+    ///
+    /// let [warehouse, shop] = stock.get_disjoint_mut_or_default([&"warehouse", &"shop"]);
+    /// *warehouse -= 4;
+    /// *shop += 4;
+    ///
+    /// assert_eq!(&6, stock.get(&"warehouse"));
+    /// assert_eq!(&4, stock.get(&"shop"));
+    /// ```
+    #[must_use]
+    pub fn get_disjoint_mut_or_default<const N: usize>(&mut self, keys: [&K; N]) -> [&mut V; N]
+    where
+        K: Clone,
+    {
+        let mut order: [usize; N] = std::array::from_fn(|index| index);
+        order.sort_by(|&a, &b| keys[a].cmp(keys[b]));
+        assert!(
+            order.windows(2).all(|pair| keys[pair[0]] != keys[pair[1]]),
+            "duplicate keys found"
+        );
+
+        for key in keys {
+            if !self._inner.contains_key(key) {
+                let value = self.make_default_for(key);
+                self._inner.insert(key.clone(), value);
+            }
+        }
+
+        let mut values: [Option<&mut V>; N] = std::array::from_fn(|_| None);
+        if let Some(&first) = order.first() {
+            let mut next = 0;
+            for (key, value) in self._inner.range_mut(keys[first]..) {
+                if key == keys[order[next]] {
+                    values[order[next]] = Some(value);
+                    next += 1;
+                    if next == N {
+                        break;
+                    }
+                }
+            }
+        }
+        values.map(|value| value.expect("the missing keys were inserted above"))
+    }
+
+    /// Returns the entry with the greatest key that is less than or equal to the key passed in.
+    /// If there is no such key, the key passed in is returned together with its default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaultbtreemap, DefaultBTreeMap};
+    ///
+    /// let tiers: DefaultBTreeMap<u32, f64> = defaultbtreemap!((0, 1.0), (100, 0.9), (1000, 0.8));
+    ///
+    /// assert_eq!((&0, &1.0), tiers.get_floor(&99));
+    /// assert_eq!((&100, &0.9), tiers.get_floor(&100));
+    /// assert_eq!((&1000, &0.8), tiers.get_floor(&5000));
+    /// ```
+    #[must_use]
+    pub fn get_floor<'a>(&'a self, key: &'a K) -> (&'a K, &'a V) {
+        self._inner
+            .range(..=key)
+            .next_back()
+            .unwrap_or_else(|| (key, self.default_for(key)))
     }
 
     /// Returns the key-value pair corresponding to the supplied key.
@@ -232,13 +755,50 @@ where
     pub fn get_key_value<'a>(&'a self, key: &'a K) -> (&'a K, &'a V) {
         self._inner
             .get_key_value(key)
-            .unwrap_or((key, &self._default))
+            .unwrap_or_else(|| (key, self.default_for(key)))
+    }
+
+    /// Returns the stored key together with a mutable reference to its value. If the key is not
+    /// present it is inserted with the default value first.
+    ///
+    /// This allows reading the key as it is stored in the map while changing the value, without
+    /// looking the key up twice.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    ///
+    /// let (key, value) = map.get_key_value_mut(&1);
+    /// *value += *key;
+    ///
+    /// assert_eq!(&11, map.get(&1));
+    /// assert_eq!((&2, &mut 0), map.get_key_value_mut(&2));
+    /// ```
+    #[must_use]
+    pub fn get_key_value_mut(&mut self, key: &K) -> (&K, &mut V)
+    where
+        K: Clone,
+    {
+        if !self._inner.contains_key(key) {
+            let value = self.make_default_for(key);
+            self._inner.insert(key.clone(), value);
+        }
+        self._inner
+            .range_mut(key..=key)
+            .next()
+            .expect("the key was inserted above")
     }
 
     /// Returns a mutable reference to the value corresponding to the key.
     /// If the key is not present in the hashmap it will return the default value and insert it in
     /// the map.
     ///
+    /// A present key is looked up without cloning it, the key is only cloned when the default
+    /// value has to be inserted.
+    ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultBTreeMap;
@@ -254,11 +814,42 @@ where
     where
         K: Eq + Clone + Ord,
     {
-        let exists = self._inner.keys().any(|k| key == k);
-        if !exists {
-            self.insert(key.clone(), V::default());
+        self.get_mut_cow(Cow::Borrowed(key))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if the key is missing. Unlike [`DefaultBTreeMap::get_mut`] the key can be any borrowed
+    /// form of the key type, which is only turned into an owned key when it has to be inserted.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<String, u32>::new();
+    ///
+    /// *map.get_mut_cow(Cow::Borrowed("word")) += 1;
+    /// *map.get_mut_cow(Cow::<str>::Owned(String::from("word"))) += 1;
+    ///
+    /// assert_eq!(&2, map.get(&String::from("word")));
+    /// ```
+    #[must_use]
+    pub fn get_mut_cow<Q>(&mut self, key: Cow<'_, Q>) -> &mut V
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        // A single `get_mut` would keep the map borrowed when the key is missing.
+        if self._inner.contains_key(key.as_ref()) {
+            return self
+                ._inner
+                .get_mut(key.as_ref())
+                .expect("the key was just found");
         }
-        self._inner.get_mut(key).unwrap()
+        let key = key.into_owned();
+        let value = self.make_default_for(&key);
+        self._inner.entry(key).or_insert(value)
     }
 
     /// Inserts a key value pair into the map.
@@ -279,6 +870,77 @@ where
         let _ = &self._inner.insert(key, value);
     }
 
+    /// Inserts a key value pair into the map, converting the key and value into the types of the
+    /// map first. This behaves like [`DefaultBTreeMap::insert`] otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<String, u64>::new();
+    /// map.insert_into("key", 5u8);
+    ///
+    /// assert_eq!(&5, map.get(&String::from("key")));
+    /// ```
+    #[inline]
+    pub fn insert_into<Q, T>(&mut self, key: Q, value: T)
+    where
+        Q: Into<K>,
+        T: Into<V>,
+    {
+        self.insert(key.into(), value.into());
+    }
+
+    /// Inserts a key value pair into the map and returns the previous value, or the default value
+    /// for the key if it was not present. The default respects the ranges set with
+    /// [`DefaultBTreeMap::set_range_default`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.set_range_default(0..10, 5);
+    ///
+    /// assert_eq!(5, map.insert_or_previous(1, 7));
+    /// assert_eq!(7, map.insert_or_previous(1, 8));
+    /// assert_eq!(0, map.insert_or_previous(20, 1));
+    /// ```
+    pub fn insert_or_previous(&mut self, key: K, value: V) -> V {
+        match self._inner.get_mut(&key) {
+            Some(previous) => std::mem::replace(previous, value),
+            None => {
+                let default = self.make_default_for(&key);
+                self._inner.insert(key, value);
+                default
+            }
+        }
+    }
+
+    /// An iterator over the keys that are in both maps, with the value from this map and the value
+    /// from `other`, in ascending key order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut a = DefaultBTreeMap::<i8, i8>::new();
+    /// let mut b = DefaultBTreeMap::<i8, i8>::new();
+    /// a.insert(1, 10);
+    /// a.insert(2, 20);
+    /// b.insert(2, 21);
+    ///
+    /// assert_eq!(vec![(&2, &20, &21)], a.intersection(&b).collect::<Vec<_>>());
+    /// ```
+    pub fn intersection<'a>(
+        &'a self,
+        other: &'a DefaultBTreeMap<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V, &'a V)> {
+        self._inner
+            .iter()
+            .filter_map(|(key, value)| Some((key, value, other._inner.get(key)?)))
+    }
+
     /// Creates a consuming iterator visiting all the keys in sorted order. The map cannot be used
     /// after calling this. The iterator element type is `K`.
     ///
@@ -338,6 +1000,48 @@ where
         self._inner.is_empty()
     }
 
+    /// Returns an iterator that yields every key produced by `keys` together with its value, or
+    /// with its default value if it is not present. Nothing is inserted into the map.
+    ///
+    /// The entries are walked alongside the keys, so keys in ascending order, such as a range,
+    /// visit every entry in between once. A key that is smaller than the entries already walked
+    /// restarts the walk, which costs a lookup.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaultbtreemap, DefaultBTreeMap};
+    ///
+    /// let sales: DefaultBTreeMap<u32, u32> = defaultbtreemap!((1, 5), (4, 2));
+    ///
+    /// let days: Vec<(u32, &u32)> = sales.iter_range_defaulted(0..6).collect();
+    ///
+    /// assert_eq!(vec![(0, &0), (1, &5), (2, &0), (3, &0), (4, &2), (5, &0)], days);
+    /// assert_eq!(2, sales.len());
+    /// ```
+    pub fn iter_range_defaulted<I>(&self, keys: I) -> impl Iterator<Item = (K, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        // Every key of the map that is greater than `walked` is still ahead in `entries`.
+        let mut walked: Option<&K> = None;
+        let mut entries: Option<Peekable<Range<'_, K, V>>> = None;
+        keys.into_iter().map(move |key| {
+            if entries.is_none() || walked.is_some_and(|walked| &key <= walked) {
+                walked = self._inner.range(..&key).next_back().map(|(key, _)| key);
+                entries = Some(self._inner.range(&key..).peekable());
+            }
+            let ahead = entries.as_mut().expect("the walk was started above");
+            while let Some((next, _)) = ahead.next_if(|(next, _)| *next < &key) {
+                walked = Some(next);
+            }
+            let value = match ahead.peek() {
+                Some((next, value)) if *next == &key => *value,
+                _ => self.default_for(&key),
+            };
+            (key, value)
+        })
+    }
+
     /// Returns an iterator visiting all keys in arbitrary order. The iterator element type is
     /// &'a K.
     ///
@@ -362,6 +1066,81 @@ where
         self._inner.keys()
     }
 
+    /// Returns an iterator over clones of the keys in ascending order, leaving the map untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<String, i8>::new();
+    /// map.insert("a".to_string(), 1);
+    ///
+    /// let keys: Vec<String> = map.keys_cloned().collect();
+    ///
+    /// assert_eq!(vec!["a".to_string()], keys);
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn keys_cloned(&self) -> Cloned<Keys<'_, K, V>>
+    where
+        K: Clone,
+    {
+        self._inner.keys().cloned()
+    }
+
+    /// Returns an iterator over the keys in a sub-range of the map, in ascending order. The range
+    /// is given the same way as for [`DefaultBTreeMap::range`].
+    ///
+    /// # Panics
+    /// Panics if range `start > end`. Panics if range `start == end` and both bounds are
+    /// `Excluded`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let map: DefaultBTreeMap<i8, i8> = (0..10).map(|i| (i, i * 2)).collect();
+    ///
+    /// let keys: Vec<&i8> = map.keys_range(3..6).collect();
+    ///
+    /// assert_eq!(vec![&3, &4, &5], keys);
+    /// ```
+    pub fn keys_range<T, R>(&self, range: R) -> impl DoubleEndedIterator<Item = &K>
+    where
+        T: Ord + ?Sized,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        self._inner.range(range).map(|(key, _)| key)
+    }
+
+    /// An iterator over the keys that have the given value, in ascending order. This is a linear
+    /// scan over all entries.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    /// map.insert(3, 10);
+    ///
+    /// let mut keys: Vec<&i8> = map.keys_with_value(&10).collect();
+    /// keys.sort();
+    ///
+    /// assert_eq!(vec![&1, &3], keys);
+    /// ```
+    pub fn keys_with_value<'a>(&'a self, value: &'a V) -> impl Iterator<Item = &'a K>
+    where
+        V: PartialEq,
+    {
+        self._inner
+            .iter()
+            .filter(move |(_, candidate)| *candidate == value)
+            .map(|(key, _)| key)
+    }
+
     /// Returns the last entry in the map for in-place manipulation. The key of this entry is the
     /// maximum key in the map.
     ///
@@ -383,7 +1162,7 @@ where
     /// assert_eq!(&14, map.get(&3));
     /// ```
     #[inline]
-    pub fn last_entry(&mut self) -> Option<OccupiedEntry<K, V>>
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>>
     where
         K: Ord,
     {
@@ -414,6 +1193,30 @@ where
         self._inner.last_key_value()
     }
 
+    /// Returns the last key-value pair in the map, or the key passed in with its default value if
+    /// the map is empty. The key in the map is the maximum key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut best = DefaultBTreeMap::<u32, &str>::new();
+    ///
+    /// assert_eq!((&0, &""), best.last_key_value_or_default(&0));
+    ///
+    /// for (score, name) in [(12, "ada"), (30, "bob"), (7, "cy")] {
+    ///     best.insert(score, name);
+    /// }
+    ///
+    /// assert_eq!((&30, &"bob"), best.last_key_value_or_default(&0));
+    /// ```
+    #[must_use]
+    pub fn last_key_value_or_default<'a>(&'a self, key: &'a K) -> (&'a K, &'a V) {
+        self._inner
+            .last_key_value()
+            .unwrap_or_else(|| (key, self.default_for(key)))
+    }
+
     /// Returns the length of the keys in the map.
     ///
     /// # Example
@@ -485,6 +1288,63 @@ where
         self._inner.pop_last()
     }
 
+    /// Removes and returns the entry with the largest value, or `None` if the map is empty. This
+    /// scans all entries. When several entries share the largest value the one with the largest key
+    /// is picked.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut tally = DefaultBTreeMap::<&str, u32>::new();
+    /// *tally.get_mut(&"a") += 1;
+    /// *tally.get_mut(&"b") += 3;
+    ///
+    /// assert_eq!(Some(("b", 3)), tally.pop_max_by_value());
+    /// assert_eq!(Some(("a", 1)), tally.pop_max_by_value());
+    /// assert_eq!(None, tally.pop_max_by_value());
+    /// ```
+    pub fn pop_max_by_value(&mut self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Ord,
+    {
+        let key = self
+            ._inner
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|(key, _)| key.clone())?;
+        self._inner.remove_entry(&key)
+    }
+
+    /// Removes and returns the entry with the smallest value, or `None` if the map is empty. This
+    /// scans all entries. When several entries share the smallest value the one with the smallest
+    /// key is picked.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut tally = DefaultBTreeMap::<&str, u32>::new();
+    /// *tally.get_mut(&"a") += 1;
+    /// *tally.get_mut(&"b") += 3;
+    ///
+    /// assert_eq!(Some(("a", 1)), tally.pop_min_by_value());
+    /// assert_eq!(1, tally.len());
+    /// ```
+    pub fn pop_min_by_value(&mut self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Ord,
+    {
+        let key = self
+            ._inner
+            .iter()
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(key, _)| key.clone())?;
+        self._inner.remove_entry(&key)
+    }
+
     /// Constructs a double-ended iterator over a sub-range of elements in the map. The simplest way
     /// is to use the range syntax `min..max`, thus `range(min..max)` will yield elements from min
     /// (inclusive) to max (exclusive). The range may also be entered as `(Bound<T>, Bound<T>)`, so
@@ -520,7 +1380,7 @@ where
     /// assert_eq!(vec, golden);
     /// ```
     #[inline]
-    pub fn range<T, R>(&self, range: R) -> Range<K, V>
+    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V>
     where
         T: Ord + ?Sized,
         K: Borrow<T> + Ord,
@@ -555,14 +1415,40 @@ where
     ///     println!("key: {}\tvalue: {}", key, value);
     /// }
     /// ```
-    #[inline]
-    pub fn range_mut<T, R>(&mut self, range: R) -> RangeMut<K, V>
+    #[inline]
+    pub fn range_mut<T, R>(&mut self, range: R) -> RangeMut<'_, K, V>
+    where
+        T: Ord + ?Sized,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        self._inner.range_mut(range)
+    }
+
+    /// Returns an iterator over the entries whose key starts with the prefix, in ascending order
+    /// of the keys. Only the matching part of the map is visited.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<String, i8>::new();
+    /// map.insert(String::from("user:1:name"), 1);
+    /// map.insert(String::from("user:1:age"), 2);
+    /// map.insert(String::from("user:10:name"), 3);
+    /// map.insert(String::from("user:2:name"), 4);
+    ///
+    /// let keys: Vec<&String> = map.range_prefix("user:1:").map(|(key, _)| key).collect();
+    ///
+    /// assert_eq!(vec!["user:1:age", "user:1:name"], keys);
+    /// ```
+    pub fn range_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a K, &'a V)>
     where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord,
-        R: RangeBounds<T>,
+        K: Borrow<str>,
     {
-        self._inner.range_mut(range)
+        self._inner
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(key, _)| (*key).borrow().starts_with(prefix))
     }
 
     /// Removes a key from the map, returning the value at the key if the key was previously in the
@@ -580,7 +1466,9 @@ where
     /// ```
     #[must_use]
     pub fn remove(&mut self, key: &K) -> V {
-        self._inner.remove(key).unwrap_or_default()
+        self._inner
+            .remove(key)
+            .unwrap_or_else(|| self.make_default_for(key))
     }
 
     /// Removes a key from the map, returning the stored key and value if the key was previously in
@@ -612,7 +1500,31 @@ where
     {
         self._inner
             .remove_entry(key)
-            .unwrap_or((key.clone(), self._default.to_owned()))
+            .unwrap_or_else(|| (key.clone(), self.default_for(key).clone()))
+    }
+
+    /// Removes every key yielded by the iterator, returning the removed values in the same order.
+    /// Keys that are not present yield the default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert_eq!(vec![20, 0, 10], map.remove_many([2, 3, 1]));
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn remove_many<I>(&mut self, keys: I) -> Vec<V>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<K>,
+    {
+        keys.into_iter()
+            .map(|key| self.remove(key.borrow()))
+            .collect()
     }
 
     /// Retains only the elements specified by the predicate.
@@ -646,14 +1558,287 @@ where
         self._inner.retain(func);
     }
 
-    /// TODO
-    #[inline]
+    /// Retains only the values for which the predicate returns `true`, regardless of their key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, Vec<i8>>::new();
+    /// map.get_mut(&1).push(1);
+    /// let _ = map.get_mut(&2);
+    ///
+    /// map.retain_values(|value| !value.is_empty());
+    ///
+    /// assert_eq!(1, map.len());
+    /// assert!(map.contains_key(&1));
+    /// ```
+    pub fn retain_values<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut V) -> bool,
+    {
+        self._inner.retain(|_, value| func(value));
+    }
+
+    /// Sets the default value for the missing keys in a range. The range default is returned by
+    /// the reads of a missing key and inserted by [`DefaultBTreeMap::get_mut`], just like the
+    /// regular default. When ranges overlap, the one that was set last wins.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut scores = DefaultBTreeMap::<u32, i32>::new();
+    /// scores.set_range_default(100..u32::MAX, -1);
+    /// scores.insert(150, 10);
+    ///
+    /// assert_eq!(&0, scores.get(&50));
+    /// assert_eq!(&-1, scores.get(&120));
+    /// assert_eq!(&10, scores.get(&150));
+    ///
+    /// *scores.get_mut(&130) += 5;
+    ///
+    /// assert_eq!(&4, scores.get(&130));
+    /// ```
+    pub fn set_range_default(&mut self, range: ops::Range<K>, value: V)
+    where
+        V: Clone,
+    {
+        self._range_defaults.push(RangeDefault {
+            range,
+            value,
+            make: V::clone,
+        });
+    }
+
+    /// Splits the map in two at the key. The returned map holds every key greater than or equal
+    /// to the key, and this map keeps the rest. Both maps keep the range defaults set with
+    /// [`DefaultBTreeMap::set_range_default`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<u8, i8>::new();
+    /// map.set_range_default(10..20, -1);
+    /// map.insert(1, 1);
+    /// map.insert(15, 15);
+    ///
+    /// let upper = map.split_off(&10);
+    ///
+    /// assert_eq!(1, map.len());
+    /// assert_eq!(&15, upper.get(&15));
+    /// assert_eq!(&-1, upper.get(&16));
+    /// ```
+    #[must_use]
     pub fn split_off<Q>(&mut self, key: &Q) -> DefaultBTreeMap<K, V>
     where
         Q: Ord + ?Sized,
         K: Borrow<Q> + Ord + Clone,
     {
-        self._inner.split_off(key).into()
+        DefaultBTreeMap {
+            _inner: self._inner.split_off(key),
+            _default: OnceLock::new(),
+            _range_defaults: self
+                ._range_defaults
+                .iter()
+                .map(RangeDefault::duplicate)
+                .collect(),
+        }
+    }
+
+    /// Swaps the values of two keys in place. Keys that are not present are inserted with the
+    /// default value first.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.swap_values(&1, &2);
+    ///
+    /// assert_eq!(&0, map.get(&1));
+    /// assert_eq!(&10, map.get(&2));
+    /// ```
+    pub fn swap_values(&mut self, a: &K, b: &K)
+    where
+        K: Clone + Ord,
+    {
+        if a == b {
+            let _ = self.get_mut(a);
+            return;
+        }
+        let first = std::mem::take(self.get_mut(a));
+        let second = std::mem::replace(self.get_mut(b), first);
+        *self.get_mut(a) = second;
+    }
+
+    /// An iterator over the entries whose keys are in exactly one of the two maps. The entries of
+    /// this map come first, followed by the entries of `other`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut a = DefaultBTreeMap::<i8, i8>::new();
+    /// let mut b = DefaultBTreeMap::<i8, i8>::new();
+    /// a.insert(1, 10);
+    /// a.insert(2, 20);
+    /// b.insert(2, 21);
+    /// b.insert(3, 30);
+    ///
+    /// assert_eq!(
+    ///     vec![(&1, &10), (&3, &30)],
+    ///     a.symmetric_difference(&b).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a DefaultBTreeMap<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> {
+        self.difference(other).chain(other.difference(self))
+    }
+
+    /// Returns the entries of the map as a vector sorted with the given comparator. The vector is
+    /// allocated with the exact size up front.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut tally = DefaultBTreeMap::<&str, u32>::new();
+    /// *tally.get_mut(&"a") += 1;
+    /// *tally.get_mut(&"b") += 3;
+    /// *tally.get_mut(&"c") += 2;
+    ///
+    /// let by_count = tally.to_sorted_vec_by(|a, b| b.1.cmp(&a.1));
+    ///
+    /// assert_eq!(vec![("b", 3), ("c", 2), ("a", 1)], by_count);
+    /// ```
+    pub fn to_sorted_vec_by<F>(&self, compare: F) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+        F: FnMut(&(K, V), &(K, V)) -> Ordering,
+    {
+        let mut sorted = Vec::with_capacity(self._inner.len());
+        sorted.extend(
+            self._inner
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        sorted.sort_by(compare);
+        sorted
+    }
+
+    /// Returns the entries of the map as a vector sorted by value in ascending order. Entries with
+    /// equal values keep the order of their keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, i8>::new();
+    /// map.insert(1, 30);
+    /// map.insert(2, 10);
+    /// map.insert(3, 20);
+    ///
+    /// assert_eq!(vec![(2, 10), (3, 20), (1, 30)], map.to_sorted_vec_by_value());
+    /// ```
+    pub fn to_sorted_vec_by_value(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone + Ord,
+    {
+        self.to_sorted_vec_by(|a, b| a.1.cmp(&b.1))
+    }
+
+    /// Creates a map from an iterator of key value pairs, failing with the first key that occurs
+    /// more than once instead of keeping the last value like [`FromIterator`] does.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let map = DefaultBTreeMap::<&str, u8>::try_from_iter([("a", 1), ("b", 2)]).unwrap();
+    /// assert_eq!(&2, map.get(&"b"));
+    ///
+    /// let error = DefaultBTreeMap::<&str, u8>::try_from_iter([("a", 1), ("a", 2)]).unwrap_err();
+    /// assert_eq!("a", error.into_key());
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, DuplicateKeyError<K>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            if map.contains_key(&key) {
+                return Err(DuplicateKeyError::new(key));
+            }
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+
+    /// Applies `func` to the value of every key from the iterator, inserting the default value
+    /// for the key first if it is missing. A key that appears more than once is updated more than
+    /// once.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut seen = DefaultBTreeMap::<char, Vec<usize>>::new();
+    ///
+    /// seen.update_many("abca".chars(), |_, positions| positions.push(positions.len()));
+    ///
+    /// assert_eq!(&vec![0, 1], seen.get(&'a'));
+    /// assert_eq!(&vec![0], seen.get(&'c'));
+    /// ```
+    pub fn update_many<I, F>(&mut self, keys: I, mut func: F)
+    where
+        I: IntoIterator<Item = K>,
+        F: FnMut(&K, &mut V),
+    {
+        for key in keys {
+            match self._inner.get_mut(&key) {
+                Some(value) => func(&key, value),
+                None => {
+                    let mut value = self.make_default_for(&key);
+                    func(&key, &mut value);
+                    self._inner.insert(key, value);
+                }
+            }
+        }
+    }
+
+    /// Counts how often every distinct value occurs in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<&str, u8>::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 1);
+    ///
+    /// let counts = map.value_counts();
+    ///
+    /// assert_eq!(&2, counts.get(&&1));
+    /// assert_eq!(&1, counts.get(&&2));
+    /// assert_eq!(&0, counts.get(&&3));
+    /// ```
+    pub fn value_counts(&self) -> DefaultBTreeMap<&V, usize>
+    where
+        V: Ord,
+    {
+        let mut counts = DefaultBTreeMap::new();
+        for value in self._inner.values() {
+            *counts.get_mut(&value) += 1;
+        }
+        counts
     }
 
     /// Returns an iterator visiting all values in arbitrary order. The iterator element type is
@@ -680,6 +1865,28 @@ where
         self._inner.values()
     }
 
+    /// Returns an iterator over clones of the values in ascending order, leaving the map untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let mut map = DefaultBTreeMap::<i8, String>::new();
+    /// map.insert(1, "a".to_string());
+    ///
+    /// let values: Vec<String> = map.values_cloned().collect();
+    ///
+    /// assert_eq!(vec!["a".to_string()], values);
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn values_cloned(&self) -> Cloned<Values<'_, K, V>>
+    where
+        V: Clone,
+    {
+        self._inner.values().cloned()
+    }
+
     /// Gets a mutable iterator over the values of the map, in order by key.
     ///
     /// # Example
@@ -701,9 +1908,120 @@ where
     /// }
     /// ```
     #[inline]
-    pub fn values_mut(&mut self) -> ValuesMut<K, V> {
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         self._inner.values_mut()
     }
+
+    /// Returns an iterator over the values whose keys are in a sub-range of the map, in ascending
+    /// order of the keys. The range is given the same way as for [`DefaultBTreeMap::range`].
+    ///
+    /// # Panics
+    /// Panics if range `start > end`. Panics if range `start == end` and both bounds are
+    /// `Excluded`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    ///
+    /// let map: DefaultBTreeMap<i8, i8> = (0..10).map(|i| (i, i * 2)).collect();
+    ///
+    /// let total: i8 = map.values_range(..=2).sum();
+    ///
+    /// assert_eq!(6, total);
+    /// ```
+    pub fn values_range<T, R>(&self, range: R) -> impl DoubleEndedIterator<Item = &V>
+    where
+        T: Ord + ?Sized,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        self._inner.range(range).map(|(_, value)| value)
+    }
+
+    /// Creates an empty map with the same default and range defaults as this map.
+    pub(crate) fn empty_like(&self) -> Self
+    where
+        K: Clone,
+        V: Clone,
+    {
+        Self {
+            _inner: BTreeMap::new(),
+            _default: self._default.clone(),
+            _range_defaults: self._range_defaults.clone(),
+        }
+    }
+
+    /// Creates the default value for a missing key, see [`DefaultBTreeMap::default_for`].
+    fn make_default_for(&self, key: &K) -> V {
+        self._range_defaults
+            .iter()
+            .rev()
+            .find(|range_default| range_default.range.contains(key))
+            .map_or_else(V::default, |range_default| {
+                (range_default.make)(&range_default.value)
+            })
+    }
+
+    /// Returns the default value of the keys outside of any range default, creating it on first
+    /// use.
+    pub(crate) fn base_default(&self) -> &V {
+        self._default.get_or_init(V::default)
+    }
+}
+
+impl<K, V> fmt::Debug for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultBTreeMap")
+            .field("_inner", &self._inner)
+            .field("_default", self.base_default())
+            .field("_range_defaults", &self._range_defaults)
+            .finish()
+    }
+}
+
+/// Maps are equal when they hold the same entries and defaults, regardless of whether their
+/// default value has been created yet.
+impl<K, V> PartialEq for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord,
+    V: Default + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self._inner == other._inner
+            && self.base_default() == other.base_default()
+            && self._range_defaults == other._range_defaults
+    }
+}
+
+impl<K, V> Eq for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord,
+    V: Default + Eq,
+{
+}
+
+impl<K, V> Clone for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord + Clone,
+    V: Default + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _inner: self._inner.clone(),
+            _default: self._default.clone(),
+            _range_defaults: self._range_defaults.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self._inner.clone_from(&source._inner);
+        self._default.clone_from(&source._default);
+        self._range_defaults.clone_from(&source._range_defaults);
+    }
 }
 
 impl<K, V> Default for DefaultBTreeMap<K, V>
@@ -718,7 +2036,7 @@ where
 
 impl<K, V> IntoIterator for DefaultBTreeMap<K, V>
 where
-    K: Eq + Ord + Clone,
+    K: Eq + Ord,
     V: Default,
 {
     type Item = (K, V);
@@ -750,7 +2068,17 @@ where
     type Output = V;
 
     fn index(&self, key: &K) -> &V {
-        self._inner.get(key).unwrap_or(&self._default)
+        self.get(key)
+    }
+}
+
+impl<K, V> IndexMut<&K> for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord + Clone,
+    V: Default,
+{
+    fn index_mut(&mut self, key: &K) -> &mut V {
+        self.get_mut(key)
     }
 }
 
@@ -775,7 +2103,8 @@ where
     fn from(btree: BTreeMap<K, V>) -> Self {
         Self {
             _inner: btree,
-            _default: V::default(),
+            _default: OnceLock::new(),
+            _range_defaults: Vec::new(),
         }
     }
 }
@@ -798,7 +2127,7 @@ where
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         let mut map = DefaultBTreeMap::default();
         for (k, v) in iter {
-            let _ = map.insert(k, v);
+            map.insert(k, v);
         }
         map
     }
@@ -821,6 +2150,24 @@ where
 ///     (2, 2),
 /// );
 /// ```
+///
+/// Entries can also be written as `key => value`, and keys without a value can be mixed in, which
+/// get the default value. Keys and values can be any expression.
+///
+/// ```
+/// use defaultdict::DefaultBTreeMap;
+///
+/// let map: DefaultBTreeMap<String, i8> = defaultdict::defaultbtreemap!(
+///     "a".to_string() => 1,
+///     format!("b{}", 2),
+///     String::from("c") => 3,
+/// );
+///
+/// assert_eq!(&1, map.get(&"a".to_string()));
+/// assert_eq!(&0, map.get(&"b2".to_string()));
+/// assert!(map.contains_key(&"b2".to_string()));
+/// assert_eq!(3, map.len());
+/// ```
 macro_rules! defaultbtreemap {
 
     // match 1
@@ -843,13 +2190,42 @@ macro_rules! defaultbtreemap {
 
     // match 3
     ( $( $key:expr ),* $(,)? ) => {
+        {
+            let mut map = DefaultBTreeMap::new();
+            map.ensure_keys([$($key),*]);
+            map
+        }
+    };
+
+    // match 4
+    ( $( $key:expr => $val:expr ),+ $(,)? ) => {
         {
             let mut map = DefaultBTreeMap::new();
             $(
-                let _ = map.get_mut(&$key);
+                let _ = map.insert($key, $val);
             )*
             map
         }
     };
 
+    // entries of match 5, these have to come before it
+    (@entry $map:ident; $key:expr => $val:expr) => {
+        let _ = $map.insert($key, $val);
+    };
+
+    (@entry $map:ident; $key:expr) => {
+        $map.ensure_keys([$key]);
+    };
+
+    // match 5
+    ( $( $key:expr $( => $val:expr )? ),+ $(,)? ) => {
+        {
+            let mut map = DefaultBTreeMap::new();
+            $(
+                $crate::defaultbtreemap!(@entry map; $key $( => $val )?);
+            )+
+            map
+        }
+    };
+
 }
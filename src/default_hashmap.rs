@@ -1,30 +1,49 @@
 #![deny(missing_docs)]
 
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 use std::collections::hash_map::{
     Drain, Entry, HashMap, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, RandomState,
     Values, ValuesMut,
 };
 use std::default::Default;
+use std::fmt;
 use std::hash::{BuildHasher, Hash};
-use std::ops::Index;
+use std::iter::Cloned;
+use std::ops::{Index, IndexMut, Range};
+use std::sync::Arc;
+
+use crate::builder::DefaultHashMapBuilder;
+use crate::diff::MapDiff;
+use crate::error::DuplicateKeyError;
+use crate::guard::ValueGuard;
+use crate::policy::{Policy, ShrinkPolicy};
+use crate::transaction::Transaction;
+use crate::view::DefaultView;
+
+/// A shared factory that produces the default value of a map.
+pub(crate) type DefaultFn<V> = Arc<dyn Fn() -> V + Send + Sync>;
+
 /// This struct mimicks the behaviour of a python defaultdict. This means alongside the traitbounds
 /// that apply on the key and value that are inherited from the [`HashMap`], it also requires the
-/// [`Default`] trait be implemented on the value type.
-#[derive(Clone, Debug)]
+/// [`Default`] trait be implemented on the value type, unless the map is created with
+/// [`DefaultHashMap::with_default_value`].
 pub struct DefaultHashMap<K, V, S = RandomState>
 where
     K: Eq + Hash,
-    V: Default,
 {
     _inner: HashMap<K, V, S>,
     _default: V,
+    _default_fn: Option<DefaultFn<V>>,
+    /// Creates a default value from `_default` when there is no `_default_fn`.
+    _new_default: fn(&V) -> V,
+    _policy: Policy,
+    _shrink: Option<ShrinkPolicy>,
 }
 
 impl<K, V> DefaultHashMap<K, V, RandomState>
 where
     K: Eq + Hash,
-    V: Default,
 {
     /// Creates an empty [`DefaultHashMap`].
     ///
@@ -41,10 +60,99 @@ where
     /// assert_eq!(empty, collected);
     /// ```
     #[must_use]
-    pub fn new() -> Self {
+    pub fn new() -> Self
+    where
+        V: Default,
+    {
         Self {
             _inner: HashMap::new(),
             _default: V::default(),
+            _default_fn: None,
+            _new_default: |_| V::default(),
+            _policy: Policy::default(),
+            _shrink: None,
+        }
+    }
+
+    /// Creates a [`DefaultHashMapBuilder`] to configure the capacity, hasher, default value and
+    /// [`Policy`] of a map before constructing it.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, Policy};
+    ///
+    /// let mut map: DefaultHashMap<&str, i32> = DefaultHashMap::builder()
+    ///     .capacity(16)
+    ///     .default_value(-1)
+    ///     .policy(Policy::InsertOnRead)
+    ///     .build();
+    ///
+    /// assert_eq!(&-1, map.get(&"missing"));
+    /// assert!(map.capacity() >= 16);
+    ///
+    /// *map.get_mut(&"present") += 2;
+    /// assert_eq!(&1, map.get(&"present"));
+    /// ```
+    #[must_use]
+    pub fn builder() -> DefaultHashMapBuilder<K, V, RandomState>
+    where
+        V: Default,
+    {
+        DefaultHashMapBuilder::new()
+    }
+
+    /// Creates an empty [`DefaultHashMap`] with room for at least `capacity` entries before it
+    /// reallocates. A capacity of `0` does not allocate.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map = DefaultHashMap::<u32, u32>::with_capacity(100);
+    ///
+    /// assert!(map.capacity() >= 100);
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        V: Default,
+    {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+
+    /// Creates an empty [`DefaultHashMap`] whose missing keys read as a clone of `value`. The
+    /// value type does not have to implement [`Default`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Status {
+    ///     Pending,
+    ///     Done,
+    /// }
+    ///
+    /// let mut jobs = DefaultHashMap::with_default_value(Status::Pending);
+    /// *jobs.get_mut(&"build") = Status::Done;
+    ///
+    /// assert_eq!(&Status::Done, jobs.get(&"build"));
+    /// assert_eq!(&Status::Pending, jobs.get(&"deploy"));
+    /// assert_eq!(Status::Pending, jobs.remove(&"deploy"));
+    /// ```
+    #[must_use]
+    pub fn with_default_value(value: V) -> Self
+    where
+        V: Clone,
+    {
+        Self {
+            _inner: HashMap::new(),
+            _default: value,
+            _default_fn: None,
+            _new_default: V::clone,
+            _policy: Policy::default(),
+            _shrink: None,
         }
     }
 }
@@ -52,9 +160,76 @@ where
 impl<K, V, S> DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: Default,
     S: BuildHasher,
 {
+    /// Returns `true` if the predicate holds for every entry of the map. An empty map returns
+    /// `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert!(map.all(|_, value| *value >= 10));
+    /// assert!(!map.all(|key, _| *key > 1));
+    /// ```
+    pub fn all<F>(&self, mut pred: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self._inner.iter().all(|(key, value)| pred(key, value))
+    }
+
+    /// Returns `true` if the predicate holds for at least one entry of the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert!(map.any(|_, value| *value == 20));
+    /// assert!(!map.any(|key, _| *key > 2));
+    /// ```
+    pub fn any<F>(&self, mut pred: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self._inner.iter().any(|(key, value)| pred(key, value))
+    }
+
+    /// Replays a [`MapDiff`] onto the map: added and changed keys are inserted with their new
+    /// value and removed keys are removed.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap};
+    ///
+    /// let old: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+    /// let new: DefaultHashMap<i8, i8> = defaulthashmap!((2, 3), (4, 4));
+    ///
+    /// let mut mirror = old.clone();
+    /// mirror.apply_patch(old.diff(&new));
+    ///
+    /// assert_eq!(new, mirror);
+    /// ```
+    pub fn apply_patch(&mut self, diff: MapDiff<K, V>) {
+        for key in diff._removed {
+            self._inner.remove(&key);
+        }
+        for (key, value) in diff._added {
+            self._inner.insert(key, value);
+        }
+        for (key, _, value) in diff._changed {
+            self._inner.insert(key, value);
+        }
+    }
+
     /// Returns the number of elements the map can hold without reallocating.
     ///
     /// This number is a lower bound; the `HashMap<K, V>` might be able to hold more, but is
@@ -74,7 +249,9 @@ where
         self._inner.capacity()
     }
 
-    /// Clears the map, removing all key-value pairs. Keeps the allocated memory for reuse.
+    /// Clears the map, removing all key-value pairs. Keeps the allocated memory for reuse, unless
+    /// the map was built with a [`ShrinkPolicy`], which may release it down to the minimum
+    /// capacity of the policy.
     ///
     /// # Example
     /// ```
@@ -92,7 +269,32 @@ where
     /// ```
     #[inline]
     pub fn clear(&mut self) {
-        self._inner.clear()
+        self._inner.clear();
+        self.apply_shrink_policy();
+    }
+
+    /// Resets the value of every key to the default value, keeping the keys and the allocated
+    /// memory. A default function set on the map is called once for every key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    /// map.clear_to_default();
+    ///
+    /// assert_eq!(2, map.len());
+    /// assert_eq!(&0, map.get(&1));
+    /// ```
+    pub fn clear_to_default(&mut self) {
+        for value in self._inner.values_mut() {
+            *value = match &self._default_fn {
+                Some(func) => func(),
+                None => (self._new_default)(&self._default),
+            };
+        }
     }
 
     /// Returns `true` if the key passed in exists in the HashMap.
@@ -114,6 +316,93 @@ where
         self._inner.contains_key(key)
     }
 
+    /// Returns `true` if any key in the map has the given value. This is a linear scan over all
+    /// values.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    ///
+    /// assert!(map.contains_value(&10));
+    /// assert!(!map.contains_value(&0));
+    /// ```
+    pub fn contains_value(&self, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self._inner.values().any(|candidate| candidate == value)
+    }
+
+    /// Computes the [`MapDiff`] that turns this map into `other`.
+    ///
+    /// Keys that are missing from one of the maps are compared against the default value of that
+    /// map, so a key that only exists with the default value is not reported.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap};
+    ///
+    /// let old: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2), (3, 0));
+    /// let new: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 5));
+    ///
+    /// let diff = old.diff(&new);
+    ///
+    /// assert!(diff.added().is_empty());
+    /// assert!(diff.removed().is_empty());
+    /// assert_eq!(&[(2, 2, 5)], diff.changed());
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &DefaultHashMap<K, V, S>) -> MapDiff<K, V>
+    where
+        K: Clone,
+        V: Clone + PartialEq,
+    {
+        let mut diff = MapDiff::new();
+        for (key, value) in &self._inner {
+            match other._inner.get(key) {
+                Some(new) if new != value => {
+                    diff._changed
+                        .push((key.clone(), value.clone(), new.clone()));
+                }
+                None if value != &other._default => diff._removed.push(key.clone()),
+                _ => {}
+            }
+        }
+        for (key, value) in &other._inner {
+            if !self._inner.contains_key(key) && value != &self._default {
+                diff._added.push((key.clone(), value.clone()));
+            }
+        }
+        diff
+    }
+
+    /// An iterator over the entries whose keys are in this map but not in `other`, in arbitrary
+    /// order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut a = DefaultHashMap::<i8, i8>::new();
+    /// let mut b = DefaultHashMap::<i8, i8>::new();
+    /// a.insert(1, 10);
+    /// a.insert(2, 20);
+    /// b.insert(2, 21);
+    ///
+    /// assert_eq!(vec![(&1, &10)], a.difference(&b).collect::<Vec<_>>());
+    /// ```
+    pub fn difference<'a>(
+        &'a self,
+        other: &'a DefaultHashMap<K, V, S>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> {
+        self._inner
+            .iter()
+            .filter(|(key, _)| !other._inner.contains_key(*key))
+    }
+
     /// Clears the map, returning all key-value pairs as an iterator. Keeps the allocated memory for
     /// reuse.
     ///
@@ -139,10 +428,40 @@ where
     /// assert_eq!(empty, collected);
     /// ```
     #[inline]
-    pub fn drain(&mut self) -> Drain<K, V> {
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
         self._inner.drain()
     }
 
+    /// Makes sure every key produced by the iterator is present, inserting the default value for
+    /// the keys that are missing. Keys that are already present keep their value. Room for the keys
+    /// is reserved up front based on the size hint of the iterator.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<&str, u32>::new();
+    /// map.insert("a", 1);
+    /// map.ensure_keys(["a", "b", "c"]);
+    ///
+    /// assert_eq!(3, map.len());
+    /// assert_eq!(&1, map.get(&"a"));
+    /// assert!(map.contains_key(&"c"));
+    /// ```
+    pub fn ensure_keys<I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let keys = keys.into_iter();
+        self._inner.reserve(keys.size_hint().0);
+        for key in keys {
+            if !self._inner.contains_key(&key) {
+                let value = self.make_default();
+                self._inner.insert(key, value);
+            }
+        }
+    }
+
     /// Gets the given key’s corresponding entry in the map for in-place manipulation.
     ///
     /// # Example
@@ -162,10 +481,114 @@ where
     /// assert_eq!(&30, map.get(&10));
     /// ```
     #[inline]
-    pub fn entry(&mut self, key: K) -> Entry<K, V> {
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         self._inner.entry(key)
     }
 
+    /// Inserts all key value pairs from the iterator after reserving room for exactly `len` more
+    /// entries, for callers that know the count up front when the size hint of the iterator is
+    /// not exact. Existing keys are overwritten. The iterator may yield more or fewer than `len`
+    /// pairs, the reservation is only a hint.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<u32, u32>::new();
+    /// map.extend_exact(100, (0..1000).filter(|i| i % 10 == 0).map(|i| (i, i)));
+    ///
+    /// assert_eq!(100, map.len());
+    /// assert!(map.capacity() >= 100);
+    /// ```
+    pub fn extend_exact<I>(&mut self, len: usize, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self._inner.reserve(len);
+        for (key, value) in iter {
+            self._inner.insert(key, value);
+        }
+    }
+
+    /// Inserts the default value for every key in the range that is not present yet, which
+    /// pre-seeds dense maps such as histograms. Keys that are already present keep their value. Use
+    /// [`DefaultHashMap::ensure_keys`] for keys that do not form a range.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<u8, u32>::new();
+    /// map.insert(3, 7);
+    /// map.fill_range(0..5);
+    ///
+    /// assert_eq!(5, map.len());
+    /// assert_eq!(&7, map.get(&3));
+    /// assert!(map.contains_key(&4));
+    /// ```
+    pub fn fill_range(&mut self, range: Range<K>)
+    where
+        Range<K>: Iterator<Item = K>,
+    {
+        self.ensure_keys(range);
+    }
+
+    /// Returns the first entry for which the predicate holds. Entries are visited in arbitrary
+    /// order, so with multiple matches any of them may be returned.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert_eq!(Some((&2, &20)), map.find(|_, value| *value > 15));
+    /// assert_eq!(None, map.find(|_, value| *value > 25));
+    /// ```
+    pub fn find<F>(&self, mut pred: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self._inner.iter().find(|(key, value)| pred(key, value))
+    }
+
+    /// Creates a map from an iterator of key value pairs, combining the values of duplicate keys
+    /// with `merge`. The first value of a key is stored as is, every later value is merged into
+    /// it.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let words = [("a", 1), ("b", 2), ("a", 3)];
+    /// let map = DefaultHashMap::<&str, u32>::from_iter_merge(words, |total, n| *total += n);
+    ///
+    /// assert_eq!(&4, map.get(&"a"));
+    /// assert_eq!(&2, map.get(&"b"));
+    /// ```
+    pub fn from_iter_merge<I, F>(iter: I, mut merge: F) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        V: Default,
+        F: FnMut(&mut V, V),
+        S: Default,
+    {
+        let iter = iter.into_iter();
+        let mut map = Self::with_hasher(S::default());
+        map._inner.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            match map._inner.entry(key) {
+                Entry::Occupied(mut entry) => merge(entry.get_mut(), value),
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+        map
+    }
+
     /// Returns a reference to the value of the key passed in.
     /// Because this hashmap mimicks the python defaultdict, it will also return a reference to a
     /// value if the key is not present.
@@ -191,6 +614,43 @@ where
         self._inner.get(key).unwrap_or(&self._default)
     }
 
+    /// Returns mutable references to the values of several distinct keys at once, inserting the
+    /// default value for every key that is missing first. This allows moving values between keys
+    /// in a single call.
+    ///
+    /// # Panics
+    /// Panics if any two keys are equal.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut stock = DefaultHashMap::<&str, u32>::new();
+    /// stock.insert("warehouse", 10);
+    ///
+    /// let [warehouse, shop] = stock.get_disjoint_mut_or_default([&"warehouse", &"shop"]);
+    /// *warehouse -= 4;
+    /// *shop += 4;
+    ///
+    /// assert_eq!(&6, stock.get(&"warehouse"));
+    /// assert_eq!(&4, stock.get(&"shop"));
+    /// ```
+    #[must_use]
+    pub fn get_disjoint_mut_or_default<const N: usize>(&mut self, keys: [&K; N]) -> [&mut V; N]
+    where
+        K: Clone,
+    {
+        for key in keys {
+            if !self._inner.contains_key(key) {
+                let value = self.make_default();
+                self._inner.insert(key.clone(), value);
+            }
+        }
+        self._inner
+            .get_disjoint_mut(keys)
+            .map(|value| value.expect("the missing keys were inserted above"))
+    }
+
     /// Returns the key-value pair corresponding to the supplied key.
     /// The supplied key may be any borrowed form of the map’s key type, but [`Hash`] and [`Eq`] on
     /// the borrowed form must match those for the key type.Returns a reference to the value of the
@@ -208,7 +668,7 @@ where
     /// assert_eq!((&10, &20), key_value);
     /// ```
     #[must_use]
-    pub fn get_key_value<'a>(&'a self, key: &'a K) -> (&K, &V)
+    pub fn get_key_value<'a>(&'a self, key: &'a K) -> (&'a K, &'a V)
     where
         K: Eq + Hash,
     {
@@ -221,6 +681,9 @@ where
     /// If the key is not present in the hashmap it will return the default value and insert it in
     /// the map.
     ///
+    /// A present key is looked up without cloning it, the key is only cloned when the default
+    /// value has to be inserted.
+    ///
     /// The key may be any borrowed form of the map’s key type, but [`Hash`] and [`Eq`] on the
     /// borrowed form must match those for the key type.
     ///
@@ -240,11 +703,66 @@ where
     where
         K: Hash + Eq + Clone,
     {
-        let exists = self._inner.keys().any(|k| key == k);
-        if !exists {
-            self.insert(key.clone(), V::default());
+        self.get_mut_cow(Cow::Borrowed(key))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if the key is missing. Unlike [`DefaultHashMap::get_mut`] the key can be any borrowed
+    /// form of the key type, which is only turned into an owned key when it has to be inserted.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<String, u32>::new();
+    ///
+    /// *map.get_mut_cow(Cow::Borrowed("word")) += 1;
+    /// *map.get_mut_cow(Cow::<str>::Owned(String::from("word"))) += 1;
+    ///
+    /// assert_eq!(&2, map.get("word"));
+    /// ```
+    #[must_use]
+    pub fn get_mut_cow<Q>(&mut self, key: Cow<'_, Q>) -> &mut V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        // A single `get_mut` would keep the map borrowed when the key is missing.
+        if self._inner.contains_key(key.as_ref()) {
+            return self
+                ._inner
+                .get_mut(key.as_ref())
+                .expect("the key was just found");
         }
-        self._inner.get_mut(key).unwrap()
+        let value = self.make_default();
+        self._inner.entry(key.into_owned()).or_insert(value)
+    }
+
+    /// Returns a [`ValueGuard`] for the value of the key, which is the default value if the key
+    /// is missing. The value is written back to the map when the guard is dropped, unless a check
+    /// added to the guard rejects it.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut queues = DefaultHashMap::<&str, Vec<u8>>::new();
+    /// queues.insert("jobs", vec![1]);
+    ///
+    /// {
+    ///     let mut jobs = queues.guard("jobs").keep_if(|jobs| !jobs.is_empty());
+    ///     jobs.pop();
+    /// }
+    ///
+    /// assert!(!queues.contains_key(&"jobs"));
+    /// ```
+    pub fn guard(&mut self, key: K) -> ValueGuard<'_, K, V, S>
+    where
+        V: Default,
+    {
+        ValueGuard::new(self, key)
     }
 
     /// Inserts a key value pair into the map. If the map did not have this key present, `None` is
@@ -269,28 +787,146 @@ where
         self._inner.insert(key, value)
     }
 
-    /// Creates a consuming iterator visiting all the keys in arbitrary order. The map cannot be
-    /// used after calling this. The iterator element type is `K`.
+    /// Inserts a key value pair into the map, converting the key and value into the types of the
+    /// map first. This behaves like [`DefaultHashMap::insert`] otherwise.
     ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultHashMap;
     ///
-    /// let mut map = DefaultHashMap::<i8, i8>::new();
-    /// map.insert(10, 20);
-    /// map.insert(30, 40);
-    ///
-    /// let mut collected: Vec<i8> = map.into_keys().collect();
-    /// collected.sort();
+    /// let mut map = DefaultHashMap::<String, u64>::new();
+    /// map.insert_into("key", 5u8);
     ///
-    /// assert_eq!(vec![10, 30], collected);
+    /// assert_eq!(&5, map.get(&String::from("key")));
     /// ```
     #[inline]
-    pub fn into_keys(self) -> IntoKeys<K, V> {
-        self._inner.into_keys()
+    pub fn insert_into<Q, T>(&mut self, key: Q, value: T) -> Option<V>
+    where
+        Q: Into<K>,
+        T: Into<V>,
+    {
+        self._inner.insert(key.into(), value.into())
     }
 
-    /// Creates a consuming iterator visiting all the values in arbitrary order. The map cannot be
+    /// Inserts all key value pairs from the iterator, reserving room for them up front based on
+    /// the size hint of the iterator. Existing keys are overwritten.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert_many((0..10).map(|i| (i, i * 2)));
+    ///
+    /// assert_eq!(10, map.len());
+    /// assert_eq!(&18, map.get(&9));
+    /// ```
+    pub fn insert_many<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = iter.into_iter();
+        self._inner.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self._inner.insert(key, value);
+        }
+    }
+
+    /// Inserts all key value pairs from the iterator like [`DefaultHashMap::insert_many`], and
+    /// returns the previous value of every pair in iteration order, `None` for keys that were not
+    /// present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    ///
+    /// let previous = map.insert_many_returning([(1, 11), (2, 20)]);
+    ///
+    /// assert_eq!(vec![Some(10), None], previous);
+    /// ```
+    pub fn insert_many_returning<I>(&mut self, iter: I) -> Vec<Option<V>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = iter.into_iter();
+        let hint = iter.size_hint().0;
+        self._inner.reserve(hint);
+        let mut previous = Vec::with_capacity(hint);
+        for (key, value) in iter {
+            previous.push(self._inner.insert(key, value));
+        }
+        previous
+    }
+
+    /// Inserts a key value pair into the map and returns the previous value, or the default value
+    /// if the key was not present. This is the counterpart of [`DefaultHashMap::insert`] that
+    /// never returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<&str, u32>::new();
+    ///
+    /// assert_eq!(0, map.insert_or_previous("a", 1));
+    /// assert_eq!(1, map.insert_or_previous("a", 2));
+    /// assert_eq!(&2, map.get(&"a"));
+    /// ```
+    pub fn insert_or_previous(&mut self, key: K, value: V) -> V {
+        self._inner
+            .insert(key, value)
+            .unwrap_or_else(|| self.make_default())
+    }
+
+    /// An iterator over the keys that are in both maps, with the value from this map and the value
+    /// from `other`, in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut a = DefaultHashMap::<i8, i8>::new();
+    /// let mut b = DefaultHashMap::<i8, i8>::new();
+    /// a.insert(1, 10);
+    /// a.insert(2, 20);
+    /// b.insert(2, 21);
+    ///
+    /// assert_eq!(vec![(&2, &20, &21)], a.intersection(&b).collect::<Vec<_>>());
+    /// ```
+    pub fn intersection<'a>(
+        &'a self,
+        other: &'a DefaultHashMap<K, V, S>,
+    ) -> impl Iterator<Item = (&'a K, &'a V, &'a V)> {
+        self._inner
+            .iter()
+            .filter_map(|(key, value)| Some((key, value, other._inner.get(key)?)))
+    }
+
+    /// Creates a consuming iterator visiting all the keys in arbitrary order. The map cannot be
+    /// used after calling this. The iterator element type is `K`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(10, 20);
+    /// map.insert(30, 40);
+    ///
+    /// let mut collected: Vec<i8> = map.into_keys().collect();
+    /// collected.sort();
+    ///
+    /// assert_eq!(vec![10, 30], collected);
+    /// ```
+    #[inline]
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        self._inner.into_keys()
+    }
+
+    /// Creates a consuming iterator visiting all the values in arbitrary order. The map cannot be
     /// used after calling this. The iterator element type is `V`.
     ///
     /// # Example
@@ -301,164 +937,662 @@ where
     /// map.insert(10, 20);
     /// map.insert(30, 40);
     ///
-    /// let mut collected: Vec<i8> = map.into_values().collect();
-    /// collected.sort();
+    /// let mut collected: Vec<i8> = map.into_values().collect();
+    /// collected.sort();
+    ///
+    /// assert_eq!(vec![20, 40], collected);
+    /// ```
+    #[inline]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        self._inner.into_values()
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// Returns an iterator visiting all keys in arbitrary order. The iterator element type is
+    /// `&'a K`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(10, 20);
+    /// map.insert(11, 21);
+    /// map.insert(12, 22);
+    /// map.insert(13, 23);
+    ///
+    /// let mut collected: Vec<&i8> = map.keys().collect();
+    /// collected.sort();
+    ///
+    /// assert_eq!(vec![&10, &11, &12, &13], collected);
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self._inner.keys()
+    }
+
+    /// Returns an iterator over clones of the keys in arbitrary order, leaving the map untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<String, i8>::new();
+    /// map.insert("a".to_string(), 1);
+    ///
+    /// let keys: Vec<String> = map.keys_cloned().collect();
+    ///
+    /// assert_eq!(vec!["a".to_string()], keys);
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn keys_cloned(&self) -> Cloned<Keys<'_, K, V>>
+    where
+        K: Clone,
+    {
+        self._inner.keys().cloned()
+    }
+
+    /// An iterator over the keys that have the given value, in arbitrary order. This is a linear
+    /// scan over all entries.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    /// map.insert(3, 10);
+    ///
+    /// let mut keys: Vec<&i8> = map.keys_with_value(&10).collect();
+    /// keys.sort();
+    ///
+    /// assert_eq!(vec![&1, &3], keys);
+    /// ```
+    pub fn keys_with_value<'a>(&'a self, value: &'a V) -> impl Iterator<Item = &'a K>
+    where
+        V: PartialEq,
+    {
+        self._inner
+            .iter()
+            .filter(move |(_, candidate)| *candidate == value)
+            .map(|(key, _)| key)
+    }
+
+    /// Returns the length of the keys in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(10, 20);
+    /// map.insert(11, 21);
+    /// map.insert(12, 22);
+    /// map.insert(13, 23);
+    ///
+    /// assert_eq!(4, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Returns a reference to the value of the key passed in, following the [`Policy`] of the map.
+    ///
+    /// With [`Policy::InsertOnRead`] a missing key is inserted with the default value, just like
+    /// reading a missing key from a python defaultdict. With [`Policy::ReturnDefault`] this
+    /// behaves like [`DefaultHashMap::get`] and the map is left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, Policy};
+    ///
+    /// let mut map: DefaultHashMap<i8, i8> = DefaultHashMap::builder()
+    ///     .policy(Policy::InsertOnRead)
+    ///     .build();
+    ///
+    /// assert_eq!(&0, map.lookup(&10));
+    /// assert!(map.contains_key(&10));
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// assert_eq!(&0, map.lookup(&10));
+    /// assert!(!map.contains_key(&10));
+    /// ```
+    pub fn lookup(&mut self, key: &K) -> &V
+    where
+        K: Clone,
+    {
+        match self._policy {
+            Policy::InsertOnRead => self.get_mut(key),
+            Policy::ReturnDefault => self.get(key),
+        }
+    }
+
+    /// Returns the [`Policy`] this map was configured with.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, Policy};
+    ///
+    /// let map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// assert_eq!(Policy::ReturnDefault, map.policy());
+    /// ```
+    #[inline]
+    pub fn policy(&self) -> Policy {
+        self._policy
+    }
+
+    /// Removes and returns the entry with the largest value, or `None` if the map is empty. This
+    /// scans all entries. When several entries share the largest value one of them is picked
+    /// arbitrarily.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut tally = DefaultHashMap::<&str, u32>::new();
+    /// *tally.get_mut(&"a") += 1;
+    /// *tally.get_mut(&"b") += 3;
+    ///
+    /// assert_eq!(Some(("b", 3)), tally.pop_max_by_value());
+    /// assert_eq!(Some(("a", 1)), tally.pop_max_by_value());
+    /// assert_eq!(None, tally.pop_max_by_value());
+    /// ```
+    pub fn pop_max_by_value(&mut self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Ord,
+    {
+        let key = self
+            ._inner
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|(key, _)| key.clone())?;
+        self._inner.remove_entry(&key)
+    }
+
+    /// Removes and returns the entry with the smallest value, or `None` if the map is empty. This
+    /// scans all entries. When several entries share the smallest value one of them is picked
+    /// arbitrarily.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut tally = DefaultHashMap::<&str, u32>::new();
+    /// *tally.get_mut(&"a") += 1;
+    /// *tally.get_mut(&"b") += 3;
+    ///
+    /// assert_eq!(Some(("a", 1)), tally.pop_min_by_value());
+    /// assert_eq!(1, tally.len());
+    /// ```
+    pub fn pop_min_by_value(&mut self) -> Option<(K, V)>
+    where
+        K: Clone,
+        V: Ord,
+    {
+        let key = self
+            ._inner
+            .iter()
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(key, _)| key.clone())?;
+        self._inner.remove_entry(&key)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map. If the key is not present in the map it will return the default value.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but [`Hash`] and [`Eq`] on the
+    /// borrowed form must match those for the key type.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(10, 20);
+    ///
+    /// println!("{}", map.remove(&10));
+    ///
+    /// println!("{}", map.remove(&90));
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn remove<Q>(&mut self, key: &Q) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner
+            .remove(key)
+            .unwrap_or_else(|| self.make_default())
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key was previously in
+    /// the map. If the key is not present in the map, a default value will be returned.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but [`Hash`] and [`Eq`] on the
+    /// borrowed form must match those for the key type.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// for i in 0..10 {
+    ///     map.insert(i, 20);
+    /// }
+    ///
+    /// let entry = map.remove_entry(&0);
+    ///
+    /// let default_entry = map.remove_entry(&0);
+    ///
+    /// assert_eq!((1, 20), map.remove_entry(&1));
+    /// assert_eq!((1, 0), map.remove_entry(&1));
+    /// ```
+    #[must_use]
+    pub fn remove_entry(&mut self, key: &K) -> (K, V)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self._inner
+            .remove_entry(key)
+            .unwrap_or((key.clone(), self._default.to_owned()))
+    }
+
+    /// Removes every key yielded by the iterator, returning the removed values in the same order.
+    /// Keys that are not present yield the default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// assert_eq!(vec![20, 0, 10], map.remove_many([2, 3, 1]));
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn remove_many<I>(&mut self, keys: I) -> Vec<V>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<K>,
+    {
+        let values = keys
+            .into_iter()
+            .map(|key| self.remove(key.borrow()))
+            .collect();
+        self.apply_shrink_policy();
+        values
+    }
+
+    /// Retains only the elements specified by the predicate.
+    /// In other words, remove all pairs (k, v) for which f(&k, &mut v) returns false. The elements
+    /// are visited in unsorted (and unspecified) order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, defaulthashmap};
+    ///
+    /// let mut map: DefaultHashMap<i8, i8> = defaulthashmap!();
+    ///
+    /// for i in 0..10 {
+    ///     map.insert(i, i);
+    /// }
+    ///
+    /// map.retain(|key, value| {
+    ///     key <= &2
+    /// });
+    ///
+    /// let mut collected: Vec<(i8, i8)> = map.into_iter().collect();
+    /// collected.sort();
+    /// let golden: Vec<(i8, i8)> = vec![(0, 0), (1, 1), (2, 2)];
+    ///
+    /// assert_eq!(golden, collected);
+    /// ```
+    pub fn retain<F>(&mut self, func: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self._inner.retain(func);
+        self.apply_shrink_policy();
+    }
+
+    /// Retains only the values for which the predicate returns `true`, regardless of their key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, Vec<i8>>::new();
+    /// map.get_mut(&1).push(1);
+    /// let _ = map.get_mut(&2);
+    ///
+    /// map.retain_values(|value| !value.is_empty());
+    ///
+    /// assert_eq!(1, map.len());
+    /// assert!(map.contains_key(&1));
+    /// ```
+    pub fn retain_values<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut V) -> bool,
+    {
+        self._inner.retain(|_, value| func(value));
+        self.apply_shrink_policy();
+    }
+
+    /// Splits the map into `shards` maps using the map's own hasher, so the entries can be
+    /// processed in parallel. Every shard keeps the default value, [`Policy`] and hasher of this
+    /// map.
+    ///
+    /// # Panics
+    /// Panics if `shards` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map: DefaultHashMap<i32, i32> = (0..100).map(|i| (i, i)).collect();
+    /// let shards = map.shard(4);
+    ///
+    /// assert_eq!(4, shards.len());
+    /// assert_eq!(100, shards.iter().map(|shard| shard.len()).sum::<usize>());
+    /// ```
+    pub fn shard(self, shards: usize) -> Vec<Self>
+    where
+        S: Clone,
+    {
+        let hasher = self._inner.hasher().clone();
+        self.shard_by(shards, |key| hasher.hash_one(key) as usize)
+    }
+
+    /// Splits the map into `shards` maps, putting every entry in the shard at the index returned
+    /// by `func` modulo `shards`. Every shard keeps the default value, [`Policy`] and hasher of
+    /// this map.
+    ///
+    /// # Panics
+    /// Panics if `shards` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map: DefaultHashMap<i32, i32> = (0..10).map(|i| (i, i)).collect();
+    /// let shards = map.shard_by(2, |key| *key as usize);
+    ///
+    /// assert!(shards[0].keys().all(|key| key % 2 == 0));
+    /// assert!(shards[1].keys().all(|key| key % 2 == 1));
+    ///
+    /// let merged: DefaultHashMap<i32, i32> = shards.into_iter().flatten().collect();
+    ///
+    /// assert_eq!(10, merged.len());
+    /// ```
+    pub fn shard_by<F>(mut self, shards: usize, mut func: F) -> Vec<Self>
+    where
+        S: Clone,
+        F: FnMut(&K) -> usize,
+    {
+        assert!(shards > 0, "the number of shards must not be zero");
+        let mut result: Vec<Self> = (0..shards)
+            .map(|_| self.empty_with(HashMap::with_hasher(self._inner.hasher().clone())))
+            .collect();
+        for (key, value) in self._inner.drain() {
+            let index = func(&key) % shards;
+            result[index]._inner.insert(key, value);
+        }
+        result
+    }
+
+    /// Returns the [`ShrinkPolicy`] this map was configured with, if any.
+    #[inline]
+    pub fn shrink_policy(&self) -> Option<ShrinkPolicy> {
+        self._shrink
+    }
+
+    /// Swaps the values of two keys in place. Keys that are not present are inserted with the
+    /// default value first.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 10);
+    /// map.swap_values(&1, &2);
     ///
-    /// assert_eq!(vec![20, 40], collected);
+    /// assert_eq!(&0, map.get(&1));
+    /// assert_eq!(&10, map.get(&2));
     /// ```
-    #[inline]
-    pub fn into_values(self) -> IntoValues<K, V> {
-        self._inner.into_values()
+    pub fn swap_values(&mut self, a: &K, b: &K)
+    where
+        K: Clone,
+    {
+        if a == b {
+            let _ = self.get_mut(a);
+            return;
+        }
+        let [a, b] = self.get_disjoint_mut_or_default([a, b]);
+        std::mem::swap(a, b);
     }
 
-    /// Returns `true` if the map does not contain any keys.
+    /// An iterator over the entries whose keys are in exactly one of the two maps. The entries of
+    /// this map come first, followed by the entries of `other`.
     ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultHashMap;
     ///
-    /// let map = DefaultHashMap::<i8, i8>::new();
-    ///
-    /// assert!(map.is_empty());
+    /// let mut a = DefaultHashMap::<i8, i8>::new();
+    /// let mut b = DefaultHashMap::<i8, i8>::new();
+    /// a.insert(1, 10);
+    /// a.insert(2, 20);
+    /// b.insert(2, 21);
+    /// b.insert(3, 30);
+    ///
+    /// assert_eq!(
+    ///     vec![(&1, &10), (&3, &30)],
+    ///     a.symmetric_difference(&b).collect::<Vec<_>>()
+    /// );
     /// ```
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self._inner.is_empty()
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a DefaultHashMap<K, V, S>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> {
+        self.difference(other).chain(other.difference(self))
     }
 
-    /// Returns an iterator visiting all keys in arbitrary order. The iterator element type is
-    /// `&'a K`.
+    /// Returns the entries of the map as a vector sorted with the given comparator. The vector is
+    /// allocated with the exact size up front.
     ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultHashMap;
     ///
-    /// let mut map = DefaultHashMap::<i8, i8>::new();
-    /// map.insert(10, 20);
-    /// map.insert(11, 21);
-    /// map.insert(12, 22);
-    /// map.insert(13, 23);
+    /// let mut tally = DefaultHashMap::<&str, u32>::new();
+    /// *tally.get_mut(&"a") += 1;
+    /// *tally.get_mut(&"b") += 3;
+    /// *tally.get_mut(&"c") += 2;
     ///
-    /// let mut collected: Vec<&i8> = map.keys().collect();
-    /// collected.sort();
+    /// let by_count = tally.to_sorted_vec_by(|a, b| b.1.cmp(&a.1));
     ///
-    /// assert_eq!(vec![&10, &11, &12, &13], collected);
+    /// assert_eq!(vec![("b", 3), ("c", 2), ("a", 1)], by_count);
     /// ```
-    #[inline]
-    pub fn keys(&self) -> Keys<'_, K, V> {
-        self._inner.keys()
+    pub fn to_sorted_vec_by<F>(&self, compare: F) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+        F: FnMut(&(K, V), &(K, V)) -> Ordering,
+    {
+        let mut sorted = Vec::with_capacity(self._inner.len());
+        sorted.extend(
+            self._inner
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        sorted.sort_by(compare);
+        sorted
     }
 
-    /// Returns the length of the keys in the map.
+    /// Returns the entries of the map as a vector sorted by value in ascending order. Entries with
+    /// equal values are returned in arbitrary order.
     ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultHashMap;
     ///
     /// let mut map = DefaultHashMap::<i8, i8>::new();
-    /// map.insert(10, 20);
-    /// map.insert(11, 21);
-    /// map.insert(12, 22);
-    /// map.insert(13, 23);
+    /// map.insert(1, 30);
+    /// map.insert(2, 10);
+    /// map.insert(3, 20);
     ///
-    /// assert_eq!(4, map.len());
+    /// assert_eq!(vec![(2, 10), (3, 20), (1, 30)], map.to_sorted_vec_by_value());
     /// ```
-    #[inline]
-    pub fn len(&self) -> usize {
-        self._inner.len()
+    pub fn to_sorted_vec_by_value(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone + Ord,
+    {
+        self.to_sorted_vec_by(|a, b| a.1.cmp(&b.1))
     }
 
-    /// Removes a key from the map, returning the value at the key if the key was previously in the
-    /// map. If the key is not present in the map it will return the default value.
-    ///
-    /// The key may be any borrowed form of the map’s key type, but [`Hash`] and [`Eq`] on the
-    /// borrowed form must match those for the key type.
+    /// Starts a [`Transaction`] on the map. Changes made through the transaction are kept when it
+    /// is committed and undone when it is rolled back or dropped.
     ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultHashMap;
     ///
     /// let mut map = DefaultHashMap::<i8, i8>::new();
-    /// map.insert(10, 20);
-    ///
-    /// println!("{}", map.remove(&10));
     ///
-    /// println!("{}", map.remove(&90));
+    /// let mut tx = map.transaction();
+    /// tx.insert(1, 1);
+    /// tx.insert(2, 2);
+    /// tx.rollback();
     ///
     /// assert!(map.is_empty());
     /// ```
-    #[must_use]
-    pub fn remove<Q>(&mut self, key: &Q) -> V
+    pub fn transaction(&mut self) -> Transaction<'_, K, V, S>
     where
-        K: Borrow<Q>,
-        Q: Hash + Eq + ?Sized,
+        K: Clone,
+        V: Default + Clone,
     {
-        self._inner.remove(key).unwrap_or_default()
+        Transaction::new(self)
     }
 
-    /// Removes a key from the map, returning the stored key and value if the key was previously in
-    /// the map. If the key is not present in the map, a default value will be returned.
-    ///
-    /// The key may be any borrowed form of the map’s key type, but [`Hash`] and [`Eq`] on the
-    /// borrowed form must match those for the key type.
+    /// Creates a map from an iterator of key value pairs, failing with the first key that occurs
+    /// more than once instead of keeping the last value like [`FromIterator`] does.
     ///
     /// # Example
     /// ```
     /// use defaultdict::DefaultHashMap;
     ///
-    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// let map = DefaultHashMap::<&str, u8>::try_from_iter([("a", 1), ("b", 2)]).unwrap();
+    /// assert_eq!(&2, map.get(&"b"));
     ///
-    /// for i in 0..10 {
-    ///     map.insert(i, 20);
-    /// }
+    /// let error = DefaultHashMap::<&str, u8>::try_from_iter([("a", 1), ("a", 2)]).unwrap_err();
+    /// assert_eq!("a", error.into_key());
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, DuplicateKeyError<K>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        V: Default,
+        S: Default,
+    {
+        let iter = iter.into_iter();
+        let mut map = Self::with_hasher(S::default());
+        map._inner.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            if map.contains_key(&key) {
+                return Err(DuplicateKeyError::new(key));
+            }
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+
+    /// Applies `func` to the value of every key from the iterator, inserting the default value
+    /// first for keys that are missing. Room for the keys is reserved once up front based on the
+    /// size hint of the iterator. A key that appears more than once is updated more than once.
     ///
-    /// let entry = map.remove_entry(&0);
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
     ///
-    /// let default_entry = map.remove_entry(&0);
+    /// let mut totals = DefaultHashMap::<&str, u32>::new();
+    /// totals.insert("a", 10);
     ///
-    /// assert_eq!((1, 20), map.remove_entry(&1));
-    /// assert_eq!((1, 0), map.remove_entry(&1));
+    /// totals.update_many(["a", "b", "a"], |_, total| *total += 1);
+    ///
+    /// assert_eq!(&12, totals.get(&"a"));
+    /// assert_eq!(&1, totals.get(&"b"));
     /// ```
-    #[must_use]
-    pub fn remove_entry(&mut self, key: &K) -> (K, V)
+    pub fn update_many<I, F>(&mut self, keys: I, mut func: F)
     where
-        K: Clone,
-        V: Clone,
+        I: IntoIterator<Item = K>,
+        F: FnMut(&K, &mut V),
     {
-        self._inner
-            .remove_entry(key)
-            .unwrap_or((key.clone(), self._default.to_owned()))
+        let keys = keys.into_iter();
+        self._inner.reserve(keys.size_hint().0);
+        for key in keys {
+            match self._inner.get_mut(&key) {
+                Some(value) => func(&key, value),
+                None => {
+                    let mut value = self.make_default();
+                    func(&key, &mut value);
+                    self._inner.insert(key, value);
+                }
+            }
+        }
     }
 
-    /// Retains only the elements specified by the predicate.
-    /// In other words, remove all pairs (k, v) for which f(&k, &mut v) returns false. The elements
-    /// are visited in unsorted (and unspecified) order.
+    /// Counts how often every distinct value occurs in the map.
     ///
     /// # Example
     /// ```
-    /// use defaultdict::{DefaultHashMap, defaulthashmap};
-    ///
-    /// let mut map: DefaultHashMap<i8, i8> = defaulthashmap!();
-    ///
-    /// for i in 0..10 {
-    ///     map.insert(i, i);
-    /// }
+    /// use defaultdict::DefaultHashMap;
     ///
-    /// map.retain(|key, value| {
-    ///     key <= &2
-    /// });
+    /// let mut map = DefaultHashMap::<&str, u8>::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 1);
     ///
-    /// let mut collected: Vec<(i8, i8)> = map.into_iter().collect();
-    /// collected.sort();
-    /// let golden: Vec<(i8, i8)> = vec![(0, 0), (1, 1), (2, 2)];
+    /// let counts = map.value_counts();
     ///
-    /// assert_eq!(golden, collected);
+    /// assert_eq!(&2, counts.get(&&1));
+    /// assert_eq!(&1, counts.get(&&2));
+    /// assert_eq!(&0, counts.get(&&3));
     /// ```
-    pub fn retain<F>(&mut self, func: F)
+    pub fn value_counts(&self) -> DefaultHashMap<&V, usize>
     where
-        F: FnMut(&K, &mut V) -> bool,
+        V: Eq + Hash,
     {
-        self._inner.retain(func);
+        let mut counts = DefaultHashMap::new();
+        for value in self._inner.values() {
+            *counts.get_mut(&value) += 1;
+        }
+        counts
     }
 
     /// Returns an iterator visiting all values in arbitrary order. The iterator element type is
@@ -485,6 +1619,28 @@ where
         self._inner.values()
     }
 
+    /// Returns an iterator over clones of the values in arbitrary order, leaving the map untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, String>::new();
+    /// map.insert(1, "a".to_string());
+    ///
+    /// let values: Vec<String> = map.values_cloned().collect();
+    ///
+    /// assert_eq!(vec!["a".to_string()], values);
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn values_cloned(&self) -> Cloned<Values<'_, K, V>>
+    where
+        V: Clone,
+    {
+        self._inner.values().cloned()
+    }
+
     /// Gets a mutable iterator over the values of the map, in order by key.
     ///
     /// # Example
@@ -508,10 +1664,65 @@ where
     /// assert_eq!(golden, collected);
     /// ```
     #[inline]
-    pub fn values_mut(&mut self) -> ValuesMut<K, V> {
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
         self._inner.values_mut()
     }
 
+    /// Returns a read-only view of the map that uses another default value for missing keys,
+    /// without cloning the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 2);
+    ///
+    /// let view = map.view_with_default(&-1);
+    ///
+    /// assert_eq!(&2, view.get(&1));
+    /// assert_eq!(&-1, view.get(&2));
+    /// assert_eq!(&0, map.get(&2));
+    /// ```
+    #[inline]
+    pub fn view_with_default<'a>(&'a self, default: &'a V) -> DefaultView<'a, K, V, S>
+    where
+        V: Default,
+    {
+        DefaultView::new(self, default)
+    }
+
+    /// Creates an empty [`DefaultHashMap`] with room for at least `capacity` entries, which will
+    /// use the given hash builder to hash keys. The same warning as for
+    /// [`DefaultHashMap::with_hasher`] applies to the hash builder.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<&str, u32>::with_capacity_and_hasher(10, RandomState::new());
+    /// *map.get_mut(&"a") += 1;
+    ///
+    /// assert!(map.capacity() >= 10);
+    /// assert_eq!(&1, map.get(&"a"));
+    /// ```
+    #[must_use]
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self
+    where
+        V: Default,
+    {
+        DefaultHashMap {
+            _inner: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+            _default: V::default(),
+            _default_fn: None,
+            _new_default: |_| V::default(),
+            _policy: Policy::default(),
+            _shrink: None,
+        }
+    }
+
     /// Creates an empty [`DefaultHashMap`] which will use the given hash builder to hash
     /// keys.
     ///
@@ -534,28 +1745,162 @@ where
     /// map.insert(1, 2);
     /// ```
     #[inline]
-    pub fn with_hasher(hash_builder: S) -> Self {
+    pub fn with_hasher(hash_builder: S) -> Self
+    where
+        V: Default,
+    {
         DefaultHashMap {
             _inner: HashMap::with_hasher(hash_builder),
             _default: V::default(),
+            _default_fn: None,
+            _new_default: |_| V::default(),
+            _policy: Policy::default(),
+            _shrink: None,
+        }
+    }
+
+    /// Assembles a map from the parts collected by a [`DefaultHashMapBuilder`].
+    pub(crate) fn from_parts(
+        inner: HashMap<K, V, S>,
+        default_fn: Option<DefaultFn<V>>,
+        policy: Policy,
+        shrink: Option<ShrinkPolicy>,
+    ) -> Self
+    where
+        V: Default,
+    {
+        let default = match &default_fn {
+            Some(func) => func(),
+            None => V::default(),
+        };
+        DefaultHashMap {
+            _inner: inner,
+            _default: default,
+            _default_fn: default_fn,
+            _new_default: |_| V::default(),
+            _policy: policy,
+            _shrink: shrink,
+        }
+    }
+
+    /// Creates an empty map with the same hasher, default and policies as this map, with room for
+    /// as many entries as this map holds.
+    pub(crate) fn empty_like(&self) -> Self
+    where
+        S: Clone,
+    {
+        self.empty_with(HashMap::with_capacity_and_hasher(
+            self._inner.len(),
+            self._inner.hasher().clone(),
+        ))
+    }
+
+    /// Shrinks the capacity if the [`ShrinkPolicy`] of the map asks for it.
+    fn apply_shrink_policy(&mut self) {
+        let target = self
+            ._shrink
+            .and_then(|policy| policy.shrink_to(self._inner.len(), self._inner.capacity()));
+        if let Some(target) = target {
+            self._inner.shrink_to(target);
+        }
+    }
+
+    /// Returns the value that missing keys read as.
+    #[cfg(feature = "ordered-float")]
+    pub(crate) fn base_default(&self) -> &V {
+        &self._default
+    }
+
+    /// Returns the value of the key if it is present, without falling back to the default.
+    pub(crate) fn get_present<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner.get(key)
+    }
+
+    /// Wraps the empty map with the same default and policies as this map.
+    fn empty_with(&self, inner: HashMap<K, V, S>) -> Self {
+        DefaultHashMap {
+            _inner: inner,
+            _default: self.make_default(),
+            _default_fn: self._default_fn.clone(),
+            _new_default: self._new_default,
+            _policy: self._policy,
+            _shrink: self._shrink,
+        }
+    }
+
+    /// Creates a new default value, using the configured default function if there is one.
+    pub(crate) fn make_default(&self) -> V {
+        match &self._default_fn {
+            Some(func) => func(),
+            None => (self._new_default)(&self._default),
+        }
+    }
+}
+
+impl<K, V, S> Clone for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _inner: self._inner.clone(),
+            _default: self._default.clone(),
+            _default_fn: self._default_fn.clone(),
+            _new_default: self._new_default,
+            _policy: self._policy,
+            _shrink: self._shrink,
         }
     }
+
+    /// Overwrites this map with a clone of `source`. When both maps have the same number of
+    /// buckets, for example because this map was cloned from `source` before, the existing table
+    /// is reused and the entries are copied in place without hashing the keys again.
+    fn clone_from(&mut self, source: &Self) {
+        self._inner.clone_from(&source._inner);
+        self._default.clone_from(&source._default);
+        self._default_fn.clone_from(&source._default_fn);
+        self._new_default = source._new_default;
+        self._policy = source._policy;
+        self._shrink = source._shrink;
+    }
 }
 
-impl<K, V> Default for DefaultHashMap<K, V>
+impl<K, V, S> Default for DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
     V: Default,
+    S: BuildHasher + Default,
 {
     fn default() -> Self {
-        Self::new()
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> fmt::Debug for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultHashMap")
+            .field("_inner", &self._inner)
+            .field("_default", &self._default)
+            .field("_policy", &self._policy)
+            .field("_shrink", &self._shrink)
+            .finish_non_exhaustive()
     }
 }
 
 impl<K, V, S> PartialEq for DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: PartialEq + Default,
+    V: PartialEq,
     S: BuildHasher,
 {
     fn eq(&self, other: &DefaultHashMap<K, V, S>) -> bool {
@@ -566,15 +1911,14 @@ where
 impl<K, V, S> Eq for DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: Eq + Default,
+    V: Eq,
     S: BuildHasher,
 {
 }
 
 impl<K, V, S> IntoIterator for DefaultHashMap<K, V, S>
 where
-    K: Eq + Hash + Clone,
-    V: Default,
+    K: Eq + Hash,
     S: BuildHasher,
 {
     type Item = (K, V);
@@ -588,7 +1932,6 @@ where
 impl<'a, K, V, S> IntoIterator for &'a DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: Default,
     S: BuildHasher,
 {
     type Item = (&'a K, &'a V);
@@ -602,7 +1945,6 @@ where
 impl<K, V, S> Index<&K> for DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: Default,
     S: BuildHasher,
 {
     type Output = V;
@@ -612,10 +1954,19 @@ where
     }
 }
 
+impl<K, V, S> IndexMut<&K> for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    fn index_mut(&mut self, key: &K) -> &mut V {
+        self.get_mut(key)
+    }
+}
+
 impl<'a, K, V, S> IntoIterator for &'a mut DefaultHashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: Default,
     S: BuildHasher,
 {
     type Item = (&'a K, &'a mut V);
@@ -636,6 +1987,10 @@ where
         Self {
             _inner: hashmap,
             _default: V::default(),
+            _default_fn: None,
+            _new_default: |_| V::default(),
+            _policy: Policy::default(),
+            _shrink: None,
         }
     }
 }
@@ -643,7 +1998,6 @@ where
 impl<K, V, S> From<DefaultHashMap<K, V, S>> for HashMap<K, V, S>
 where
     K: Eq + Hash,
-    V: Default,
     S: BuildHasher,
 {
     fn from(hashmap: DefaultHashMap<K, V, S>) -> Self {
@@ -659,13 +2013,34 @@ where
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         let mut map = DefaultHashMap::with_hasher(Default::default());
-        for (k, v) in iter {
-            let _ = map.insert(k, v);
-        }
+        map.extend(iter);
         map
     }
 }
 
+impl<K, V, S> Extend<(K, V)> for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Inserts all key value pairs from the iterator. Room is reserved up front from the lower
+    /// bound of the size hint, or half of it if the map is not empty since some keys are likely
+    /// to be present already. Use [`DefaultHashMap::extend_exact`] when the count is known.
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        let hint = iter.size_hint().0;
+        let reserve = if self._inner.is_empty() {
+            hint
+        } else {
+            hint.div_ceil(2)
+        };
+        self._inner.reserve(reserve);
+        for (key, value) in iter {
+            self._inner.insert(key, value);
+        }
+    }
+}
+
 #[macro_export]
 /// A quick way to instantiate a HashMap.
 ///
@@ -683,6 +2058,24 @@ where
 ///     (2, 2),
 /// );
 /// ```
+///
+/// Entries can also be written as `key => value`, and keys without a value can be mixed in, which
+/// get the default value. Keys and values can be any expression.
+///
+/// ```
+/// use defaultdict::DefaultHashMap;
+///
+/// let map: DefaultHashMap<String, i8> = defaultdict::defaulthashmap!(
+///     "a".to_string() => 1,
+///     format!("b{}", 2),
+///     String::from("c") => 3,
+/// );
+///
+/// assert_eq!(&1, map.get(&"a".to_string()));
+/// assert_eq!(&0, map.get(&"b2".to_string()));
+/// assert!(map.contains_key(&"b2".to_string()));
+/// assert_eq!(3, map.len());
+/// ```
 macro_rules! defaulthashmap {
 
     // match 1
@@ -705,13 +2098,42 @@ macro_rules! defaulthashmap {
 
     // match 3
     ( $( $key:expr ),* $(,)? ) => {
+        {
+            let mut map = DefaultHashMap::new();
+            map.ensure_keys([$($key),*]);
+            map
+        }
+    };
+
+    // match 4
+    ( $( $key:expr => $val:expr ),+ $(,)? ) => {
         {
             let mut map = DefaultHashMap::new();
             $(
-                let _ = map.get_mut(&$key);
+                let _ = map.insert($key, $val);
             )*
             map
         }
     };
 
+    // entries of match 5, these have to come before it
+    (@entry $map:ident; $key:expr => $val:expr) => {
+        let _ = $map.insert($key, $val);
+    };
+
+    (@entry $map:ident; $key:expr) => {
+        $map.ensure_keys([$key]);
+    };
+
+    // match 5
+    ( $( $key:expr $( => $val:expr )? ),+ $(,)? ) => {
+        {
+            let mut map = DefaultHashMap::new();
+            $(
+                $crate::defaulthashmap!(@entry map; $key $( => $val )?);
+            )+
+            map
+        }
+    };
+
 }
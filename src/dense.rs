@@ -0,0 +1,337 @@
+#![deny(missing_docs)]
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// A dense map always has room for keys below this, however few entries it holds.
+const MIN_DENSE_LEN: usize = 64;
+
+/// A dense map may hold this many slots per entry before it switches to hashing.
+const SLOTS_PER_ENTRY: usize = 4;
+
+/// An unsigned integer key that can be used as an index into the dense storage of a
+/// [`DenseDefaultMap`].
+pub trait DenseKey: Copy + Eq + Hash {
+    /// Returns the key as an index, or `None` if it does not fit in a `usize`.
+    fn to_index(self) -> Option<usize>;
+
+    /// Returns the key at the index. Only called with indices returned by
+    /// [`DenseKey::to_index`].
+    fn from_index(index: usize) -> Self;
+}
+
+macro_rules! impl_dense_key {
+    ($($ty:ty),*) => {
+        $(
+            impl DenseKey for $ty {
+                #[inline]
+                fn to_index(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Self {
+                    index as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_dense_key!(u8, u16, u32, u64, usize);
+
+/// Where the entries of a [`DenseDefaultMap`] live.
+#[derive(Clone)]
+enum Storage<K, V> {
+    /// The value of key `k` is stored at index `k`.
+    Dense { slots: Vec<Option<V>>, len: usize },
+    /// A key far beyond the other keys has been stored at some point.
+    Sparse(HashMap<K, V>),
+}
+
+/// A default map for small unsigned integer keys, such as ids, that stores its values in a
+/// [`Vec`] indexed by the key while the keys are compact, and moves them to a [`HashMap`] once
+/// they become sparse.
+///
+/// Dense lookups are a bounds check and an index, without hashing, and iterate in key order. The
+/// map switches to hashing when a key would need more than four slots per entry, with room for
+/// the first 64 keys regardless. Once the map has switched it stays a [`HashMap`], even if
+/// entries are removed again.
+///
+/// # Example
+/// ```
+/// use defaultdict::DenseDefaultMap;
+///
+/// let mut frequencies = DenseDefaultMap::<u16, u32>::new();
+/// for id in [3, 1, 3, 2, 3] {
+///     *frequencies.get_mut(id) += 1;
+/// }
+///
+/// assert!(frequencies.is_dense());
+/// assert_eq!(&3, frequencies.get(3));
+///
+/// frequencies.insert(60_000, 1);
+///
+/// assert!(!frequencies.is_dense());
+/// assert_eq!(&3, frequencies.get(3));
+/// assert_eq!(&0, frequencies.get(4));
+/// ```
+#[derive(Clone)]
+pub struct DenseDefaultMap<K, V>
+where
+    K: DenseKey,
+    V: Default,
+{
+    _storage: Storage<K, V>,
+    _default: V,
+}
+
+impl<K, V> DenseDefaultMap<K, V>
+where
+    K: DenseKey,
+    V: Default,
+{
+    /// Creates an empty [`DenseDefaultMap`]. This does not allocate.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _storage: Storage::Dense {
+                slots: Vec::new(),
+                len: 0,
+            },
+            _default: V::default(),
+        }
+    }
+
+    /// Removes all entries from the map. The map keeps its allocation and its storage mode.
+    pub fn clear(&mut self) {
+        match &mut self._storage {
+            Storage::Dense { slots, len } => {
+                slots.clear();
+                *len = 0;
+            }
+            Storage::Sparse(map) => map.clear(),
+        }
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DenseDefaultMap;
+    ///
+    /// let mut map = DenseDefaultMap::<u8, u8>::new();
+    /// map.insert(1, 1);
+    ///
+    /// assert!(map.contains_key(1));
+    /// assert!(!map.contains_key(2));
+    /// ```
+    pub fn contains_key(&self, key: K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    #[must_use]
+    pub fn get(&self, key: K) -> &V {
+        self.find(key).unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key. If the key is not
+    /// present the default value is inserted first, which can make the map switch to hashing.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DenseDefaultMap;
+    ///
+    /// let mut map = DenseDefaultMap::<u32, Vec<&str>>::new();
+    /// map.get_mut(7).push("seven");
+    ///
+    /// assert_eq!(&vec!["seven"], map.get(7));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: K) -> &mut V {
+        if !self.contains_key(key) {
+            self.insert(key, V::default());
+        }
+        let value = match &mut self._storage {
+            Storage::Dense { slots, .. } => key
+                .to_index()
+                .and_then(|index| slots.get_mut(index))
+                .and_then(Option::as_mut),
+            Storage::Sparse(map) => map.get_mut(&key),
+        };
+        value.expect("the key was inserted above")
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if the key was
+    /// present. Inserting a key that would leave the dense storage mostly empty moves all entries
+    /// to a [`HashMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DenseDefaultMap;
+    ///
+    /// let mut map = DenseDefaultMap::<u8, u8>::new();
+    ///
+    /// assert_eq!(None, map.insert(1, 2));
+    /// assert_eq!(Some(2), map.insert(1, 3));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match &mut self._storage {
+            Storage::Dense { slots, len } => match key.to_index() {
+                Some(index) if index < slots.len() => {
+                    let old = slots[index].replace(value);
+                    if old.is_none() {
+                        *len += 1;
+                    }
+                    old
+                }
+                Some(index) if index < MIN_DENSE_LEN.max((*len + 1) * SLOTS_PER_ENTRY) => {
+                    slots.resize_with(index + 1, || None);
+                    slots[index] = Some(value);
+                    *len += 1;
+                    None
+                }
+                _ => {
+                    let mut map: HashMap<K, V> = HashMap::with_capacity(*len + 1);
+                    map.extend(slots.drain(..).enumerate().filter_map(|(index, slot)| {
+                        slot.map(|value| (K::from_index(index), value))
+                    }));
+                    map.insert(key, value);
+                    self._storage = Storage::Sparse(map);
+                    None
+                }
+            },
+            Storage::Sparse(map) => map.insert(key, value),
+        }
+    }
+
+    /// Returns `true` if the values are still stored in a [`Vec`] indexed by the key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DenseDefaultMap;
+    ///
+    /// let mut map = DenseDefaultMap::<u32, u8>::new();
+    /// map.insert(10, 1);
+    ///
+    /// assert!(map.is_dense());
+    ///
+    /// map.insert(1_000_000, 1);
+    ///
+    /// assert!(!map.is_dense());
+    /// ```
+    #[inline]
+    pub fn is_dense(&self) -> bool {
+        matches!(self._storage, Storage::Dense { .. })
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An iterator visiting all key-value pairs. The keys are returned by value, in ascending
+    /// order while the map is dense and in arbitrary order once it hashes.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DenseDefaultMap;
+    ///
+    /// let map: DenseDefaultMap<u8, char> = [(2, 'b'), (0, 'a')].into_iter().collect();
+    ///
+    /// assert_eq!(vec![(0, &'a'), (2, &'b')], map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        let (dense, sparse) = match &self._storage {
+            Storage::Dense { slots, .. } => (&slots[..], None),
+            Storage::Sparse(map) => (&[][..], Some(map.iter())),
+        };
+        dense
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((K::from_index(index), slot.as_ref()?)))
+            .chain(
+                sparse
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, value)| (*key, value)),
+            )
+    }
+
+    /// Returns the number of keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self._storage {
+            Storage::Dense { len, .. } => *len,
+            Storage::Sparse(map) => map.len(),
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise.
+    #[must_use]
+    pub fn remove(&mut self, key: K) -> V {
+        let value = match &mut self._storage {
+            Storage::Dense { slots, len } => {
+                let value = key
+                    .to_index()
+                    .and_then(|index| slots.get_mut(index))
+                    .and_then(Option::take);
+                if value.is_some() {
+                    *len -= 1;
+                }
+                value
+            }
+            Storage::Sparse(map) => map.remove(&key),
+        };
+        value.unwrap_or_default()
+    }
+
+    fn find(&self, key: K) -> Option<&V> {
+        match &self._storage {
+            Storage::Dense { slots, .. } => key
+                .to_index()
+                .and_then(|index| slots.get(index))
+                .and_then(Option::as_ref),
+            Storage::Sparse(map) => map.get(&key),
+        }
+    }
+}
+
+impl<K, V> Default for DenseDefaultMap<K, V>
+where
+    K: DenseKey,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for DenseDefaultMap<K, V>
+where
+    K: DenseKey + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for DenseDefaultMap<K, V>
+where
+    K: DenseKey,
+    V: Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
@@ -0,0 +1,76 @@
+#![deny(missing_docs)]
+
+/// The differences between two maps, as returned by [`DefaultHashMap::diff`] and
+/// [`DefaultBTreeMap::diff`]. A diff can be replayed onto another map with `apply_patch`.
+///
+/// Diffs are default-aware: a key that is only present in one of the maps but holds the default
+/// value reads the same in both maps, so it is not reported.
+///
+/// [`DefaultHashMap::diff`]: crate::DefaultHashMap::diff
+/// [`DefaultBTreeMap::diff`]: crate::DefaultBTreeMap::diff
+///
+/// # Example
+/// ```
+/// use defaultdict::{defaulthashmap, DefaultHashMap};
+///
+/// let old: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+/// let new: DefaultHashMap<i8, i8> = defaulthashmap!((2, 3), (4, 4));
+///
+/// let diff = old.diff(&new);
+///
+/// assert_eq!(&[(4, 4)], diff.added());
+/// assert_eq!(&[1], diff.removed());
+/// assert_eq!(&[(2, 2, 3)], diff.changed());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MapDiff<K, V> {
+    pub(crate) _added: Vec<(K, V)>,
+    pub(crate) _removed: Vec<K>,
+    pub(crate) _changed: Vec<(K, V, V)>,
+}
+
+impl<K, V> MapDiff<K, V> {
+    /// Creates an empty diff.
+    pub(crate) fn new() -> Self {
+        Self {
+            _added: Vec::new(),
+            _removed: Vec::new(),
+            _changed: Vec::new(),
+        }
+    }
+
+    /// Returns the keys, with their values, that are only present in the newer map.
+    #[inline]
+    pub fn added(&self) -> &[(K, V)] {
+        &self._added
+    }
+
+    /// Returns the keys whose value differs between the maps, together with the old and the new
+    /// value.
+    #[inline]
+    pub fn changed(&self) -> &[(K, V, V)] {
+        &self._changed
+    }
+
+    /// Returns `true` if the maps read the same for every key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaultbtreemap, DefaultBTreeMap};
+    ///
+    /// let map: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 1));
+    /// let other: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 1), (2, 0));
+    ///
+    /// assert!(map.diff(&other).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._added.is_empty() && self._removed.is_empty() && self._changed.is_empty()
+    }
+
+    /// Returns the keys that are only present in the older map.
+    #[inline]
+    pub fn removed(&self) -> &[K] {
+        &self._removed
+    }
+}
@@ -0,0 +1,118 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::DefaultHashMap;
+
+macro_rules! distributions {
+    ( $( $int:ty ),* ) => {
+        $(
+            /// Probability helpers for maps of counts, treating the counts as the frequencies of
+            /// a discrete distribution over the keys.
+            impl<K, S> DefaultHashMap<K, $int, S>
+            where
+                K: Eq + Hash,
+                S: BuildHasher,
+            {
+                #[doc = concat!(
+                    "Returns the Shannon entropy of the distribution in bits. Keys with a count ",
+                    "of zero do not contribute, and an empty map has an entropy of `0.0`.\n",
+                    "\n",
+                    "# Example\n",
+                    "```\n",
+                    "use defaultdict::DefaultHashMap;\n",
+                    "\n",
+                    "let mut coin = DefaultHashMap::<&str, ", stringify!($int), ">::new();\n",
+                    "coin.insert(\"heads\", 5);\n",
+                    "coin.insert(\"tails\", 5);\n",
+                    "\n",
+                    "assert_eq!(1.0, coin.entropy());\n",
+                    "```",
+                )]
+                pub fn entropy(&self) -> f64 {
+                    let total = self.values().map(|count| *count as f64).sum::<f64>();
+                    if total == 0.0 {
+                        return 0.0;
+                    }
+                    self.values()
+                        .filter(|count| **count > 0)
+                        .map(|count| {
+                            let p = *count as f64 / total;
+                            -p * p.log2()
+                        })
+                        .sum()
+                }
+
+                #[doc = concat!(
+                    "Converts the counts into probabilities that sum to one. If all counts are ",
+                    "zero every probability is `0.0`.\n",
+                    "\n",
+                    "# Example\n",
+                    "```\n",
+                    "use defaultdict::DefaultHashMap;\n",
+                    "\n",
+                    "let mut counts = DefaultHashMap::<char, ", stringify!($int), ">::new();\n",
+                    "counts.insert('a', 3);\n",
+                    "counts.insert('b', 1);\n",
+                    "\n",
+                    "let probabilities = counts.normalize();\n",
+                    "\n",
+                    "assert_eq!(&0.75, probabilities.get(&'a'));\n",
+                    "assert_eq!(&0.25, probabilities.get(&'b'));\n",
+                    "assert_eq!(&0.0, probabilities.get(&'c'));\n",
+                    "```",
+                )]
+                pub fn normalize(&self) -> DefaultHashMap<K, f64, RandomState>
+                where
+                    K: Clone,
+                {
+                    let total = self.values().map(|count| *count as f64).sum::<f64>();
+                    self.into_iter()
+                        .map(|(key, count)| {
+                            let p = if total == 0.0 { 0.0 } else { *count as f64 / total };
+                            (key.clone(), p)
+                        })
+                        .collect()
+                }
+
+                #[doc = concat!(
+                    "Draws a key at random, with a probability proportional to its count. ",
+                    "Returns `None` if all counts are zero. This requires the `rand` feature.\n",
+                    "\n",
+                    "# Example\n",
+                    "```\n",
+                    "use defaultdict::DefaultHashMap;\n",
+                    "\n",
+                    "let mut counts = DefaultHashMap::<&str, ", stringify!($int), ">::new();\n",
+                    "counts.insert(\"always\", 1);\n",
+                    "counts.insert(\"never\", 0);\n",
+                    "\n",
+                    "assert_eq!(Some(&\"always\"), counts.sample(&mut rand::rng()));\n",
+                    "```",
+                )]
+                #[cfg(feature = "rand")]
+                pub fn sample<R>(&self, rng: &mut R) -> Option<&K>
+                where
+                    R: rand::Rng + ?Sized,
+                {
+                    let total = self.values().map(|count| *count as u128).sum::<u128>();
+                    if total == 0 {
+                        return None;
+                    }
+                    let mut target = rng.random_range(0..total);
+                    for (key, count) in self {
+                        let count = *count as u128;
+                        if target < count {
+                            return Some(key);
+                        }
+                        target -= count;
+                    }
+                    unreachable!("the target is smaller than the total of the counts")
+                }
+            }
+        )*
+    };
+}
+
+distributions!(u8, u16, u32, u64, usize);
@@ -0,0 +1,186 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{DefaultHashMap, Error};
+
+/// The keys a [`DomainHashMap`] accepts.
+enum Domain<K> {
+    Keys(HashSet<K>),
+    Predicate(Box<dyn Fn(&K) -> bool + Send + Sync>),
+}
+
+/// A [`DefaultHashMap`] that only stores keys from a fixed domain, given as a set of allowed keys
+/// or as a predicate.
+///
+/// Reading a key outside of the domain returns the default value like any missing key, but
+/// inserting it or accessing it mutably fails with [`Error::Validation`]. This keeps a mistyped key
+/// in a fixed-schema aggregation from silently creating a new bucket.
+///
+/// # Example
+/// ```
+/// use defaultdict::{DomainHashMap, Error};
+///
+/// let mut totals = DomainHashMap::<&str, u32>::with_keys(["north", "south"]);
+///
+/// *totals.get_mut(&"north")? += 5;
+///
+/// assert_eq!(&5, totals.get(&"north"));
+/// assert_eq!(&0, totals.get(&"nort"));
+/// assert!(matches!(totals.get_mut(&"nort"), Err(Error::Validation(_))));
+/// # Ok::<(), Error>(())
+/// ```
+pub struct DomainHashMap<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _inner: DefaultHashMap<K, V, S>,
+    _domain: Domain<K>,
+}
+
+impl<K, V> DomainHashMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty map that only accepts the given keys.
+    #[must_use]
+    pub fn with_keys<I>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+    {
+        Self {
+            _inner: DefaultHashMap::new(),
+            _domain: Domain::Keys(keys.into_iter().collect()),
+        }
+    }
+
+    /// Creates an empty map that only accepts the keys for which the predicate returns `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DomainHashMap;
+    ///
+    /// let mut hours = DomainHashMap::<u8, u32>::with_predicate(|hour| *hour < 24);
+    ///
+    /// assert!(hours.insert(23, 1).is_ok());
+    /// assert!(hours.insert(24, 1).is_err());
+    /// ```
+    #[must_use]
+    pub fn with_predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&K) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            _inner: DefaultHashMap::new(),
+            _domain: Domain::Predicate(Box::new(predicate)),
+        }
+    }
+}
+
+impl<K, V, S> DomainHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Returns a reference to the wrapped [`DefaultHashMap`] for read access.
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<K, V, S> {
+        &self._inner
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._inner.contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present or outside of the domain.
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._inner.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if the key is missing.
+    ///
+    /// # Errors
+    /// Returns [`Error::Validation`] if the key is outside of the domain.
+    pub fn get_mut(&mut self, key: &K) -> Result<&mut V, Error>
+    where
+        K: Clone,
+    {
+        self.check(key)?;
+        Ok(self._inner.get_mut(key))
+    }
+
+    /// Inserts a key value pair into the map and returns the previous value of the key.
+    ///
+    /// # Errors
+    /// Returns [`Error::Validation`] if the key is outside of the domain.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, Error> {
+        self.check(&key)?;
+        Ok(self._inner.insert(key, value))
+    }
+
+    /// Consumes the wrapper and returns the inner [`DefaultHashMap`].
+    #[inline]
+    pub fn into_inner(self) -> DefaultHashMap<K, V, S> {
+        self._inner
+    }
+
+    /// Returns `true` if the key is inside the domain of the map.
+    pub fn is_allowed(&self, key: &K) -> bool {
+        match &self._domain {
+            Domain::Keys(keys) => keys.contains(key),
+            Domain::Predicate(predicate) => predicate(key),
+        }
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// Returns the length of the keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map. If the key is not present the default value is returned.
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        self._inner.remove(key)
+    }
+
+    fn check(&self, key: &K) -> Result<(), Error> {
+        if self.is_allowed(key) {
+            Ok(())
+        } else {
+            Err(Error::Validation(String::from(
+                "the key is outside of the domain of the map",
+            )))
+        }
+    }
+}
+
+impl<K, V, S> fmt::Debug for DomainHashMap<K, V, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DomainHashMap")
+            .field("inner", &self._inner)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,452 @@
+#![deny(missing_docs)]
+
+use std::fmt;
+use std::ops::Index;
+
+/// A key with a small, fixed set of values that can be used as an index into an array, which is
+/// what [`EnumDefaultMap`] needs to store its values without hashing.
+///
+/// This is usually implemented for a fieldless enum with the [`enum_key!`](crate::enum_key)
+/// macro.
+pub trait EnumKey: Copy {
+    /// The number of distinct keys.
+    const COUNT: usize;
+
+    /// An array that holds one `T` for every key, usually `[T; Self::COUNT]`.
+    type Array<T>: AsRef<[T]> + AsMut<[T]>;
+
+    /// Returns the position of the key, which is lower than [`EnumKey::COUNT`].
+    fn index(self) -> usize;
+
+    /// Returns the key at the given position, or `None` if the position is out of range.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Creates an array by calling `func` with every position.
+    fn array<T, F>(func: F) -> Self::Array<T>
+    where
+        F: FnMut(usize) -> T;
+}
+
+/// A default map for enum keys that stores its values in a fixed-size array indexed by the
+/// position of the key, so lookups never hash and the map never allocates.
+///
+/// # Example
+/// ```
+/// use defaultdict::{enum_key, EnumDefaultMap};
+///
+/// enum_key! {
+///     #[derive(Debug, PartialEq)]
+///     enum Color {
+///         Red,
+///         Green,
+///         Blue,
+///     }
+/// }
+///
+/// let mut map = EnumDefaultMap::<Color, u32>::new();
+/// *map.get_mut(&Color::Red) += 2;
+///
+/// assert_eq!(&2, map.get(&Color::Red));
+/// assert_eq!(&0, map.get(&Color::Blue));
+/// assert_eq!(vec![(Color::Red, &2)], map.iter().collect::<Vec<_>>());
+/// ```
+pub struct EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default,
+{
+    _slots: K::Array<Option<V>>,
+    _default: V,
+}
+
+impl<K, V> EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default,
+{
+    /// Creates an empty [`EnumDefaultMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let map = EnumDefaultMap::<Side, i8>::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _slots: K::array(|_| None),
+            _default: V::default(),
+        }
+    }
+
+    /// Removes every value from the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    /// map.insert(Side::Left, 1);
+    /// map.clear();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        for slot in self._slots.as_mut() {
+            *slot = None;
+        }
+    }
+
+    /// Returns `true` if a value has been set for the key passed in.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    /// map.insert(Side::Left, 1);
+    ///
+    /// assert!(map.contains_key(&Side::Left));
+    /// assert!(!map.contains_key(&Side::Right));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._slots.as_ref()[key.index()].is_some()
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if it has not
+    /// been set.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    /// map.insert(Side::Left, 1);
+    ///
+    /// assert_eq!(&1, map.get(&Side::Left));
+    /// assert_eq!(&0, map.get(&Side::Right));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._slots.as_ref()[key.index()]
+            .as_ref()
+            .unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value of the key passed in, setting it to the default
+    /// value first if it has not been set.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::<Side, i8>::new();
+    /// *map.get_mut(&Side::Right) += 3;
+    ///
+    /// assert_eq!(&3, map.get(&Side::Right));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        self._slots.as_mut()[key.index()].get_or_insert_with(V::default)
+    }
+
+    /// Sets the value of a key, returning the previous value if it had been set.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    ///
+    /// assert_eq!(None, map.insert(Side::Left, 1));
+    /// assert_eq!(Some(1), map.insert(Side::Left, 2));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self._slots.as_mut()[key.index()].replace(value)
+    }
+
+    /// Returns `true` if no value has been set.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let map = EnumDefaultMap::<Side, i8>::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._slots.as_ref().iter().all(Option::is_none)
+    }
+
+    /// An iterator visiting the keys that have been set and their values, in the order of the
+    /// keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     #[derive(Debug, PartialEq)]
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    /// map.insert(Side::Right, 2);
+    /// map.insert(Side::Left, 1);
+    ///
+    /// assert_eq!(
+    ///     vec![(Side::Left, &1), (Side::Right, &2)],
+    ///     map.iter().collect::<Vec<_>>(),
+    /// );
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        self._slots
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((K::from_index(index)?, slot.as_ref()?)))
+    }
+
+    /// Returns the number of keys that have been set.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    /// map.insert(Side::Left, 1);
+    ///
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._slots.as_ref().iter().flatten().count()
+    }
+
+    /// Unsets a key, returning its value if it had been set or the default value otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{enum_key, EnumDefaultMap};
+    ///
+    /// enum_key! {
+    ///     enum Side {
+    ///         Left,
+    ///         Right,
+    ///     }
+    /// }
+    ///
+    /// let mut map = EnumDefaultMap::new();
+    /// map.insert(Side::Left, 1);
+    ///
+    /// assert_eq!(1, map.remove(&Side::Left));
+    /// assert_eq!(0, map.remove(&Side::Left));
+    /// ```
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        self._slots.as_mut()[key.index()].take().unwrap_or_default()
+    }
+}
+
+impl<K, V> Clone for EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    K::Array<Option<V>>: Clone,
+    V: Default + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _slots: self._slots.clone(),
+            _default: self._default.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for EnumDefaultMap<K, V>
+where
+    K: EnumKey + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> PartialEq for EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self._slots.as_ref() == other._slots.as_ref() && self._default == other._default
+    }
+}
+
+impl<K, V> Eq for EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default + Eq,
+{
+}
+
+impl<K, V> FromIterator<(K, V)> for EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K, V> Index<&K> for EnumDefaultMap<K, V>
+where
+    K: EnumKey,
+    V: Default,
+{
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        self.get(key)
+    }
+}
+
+#[macro_export]
+/// Declares a fieldless enum and implements [`EnumKey`](crate::EnumKey) for it, so it can be
+/// used as the key of an [`EnumDefaultMap`](crate::EnumDefaultMap).
+///
+/// `Clone` and `Copy` are derived by the macro, other attributes are passed through. Variants
+/// can not have explicit discriminants.
+///
+/// # Example
+/// ```
+/// use defaultdict::{enum_key, EnumKey};
+///
+/// enum_key! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Weekday {
+///         Monday,
+///         Tuesday,
+///         Wednesday,
+///     }
+/// }
+///
+/// assert_eq!(3, Weekday::COUNT);
+/// assert_eq!(1, Weekday::Tuesday.index());
+/// assert_eq!(Some(Weekday::Wednesday), Weekday::from_index(2));
+/// assert_eq!(None, Weekday::from_index(3));
+/// ```
+macro_rules! enum_key {
+    (
+        $( #[$meta:meta] )*
+        $vis:vis enum $name:ident {
+            $( $( #[$variant_meta:meta] )* $variant:ident ),+ $(,)?
+        }
+    ) => {
+        $( #[$meta] )*
+        #[derive(Clone, Copy)]
+        $vis enum $name {
+            $( $( #[$variant_meta] )* $variant ),+
+        }
+
+        impl $crate::EnumKey for $name {
+            const COUNT: usize = [$( $name::$variant ),+].len();
+
+            type Array<T> = [T; <$name as $crate::EnumKey>::COUNT];
+
+            fn index(self) -> usize {
+                self as usize
+            }
+
+            fn from_index(index: usize) -> Option<Self> {
+                [$( $name::$variant ),+].get(index).copied()
+            }
+
+            fn array<T, F>(func: F) -> Self::Array<T>
+            where
+                F: FnMut(usize) -> T,
+            {
+                ::std::array::from_fn(func)
+            }
+        }
+    };
+}
@@ -0,0 +1,63 @@
+#![deny(missing_docs)]
+
+use std::env;
+use std::hash::BuildHasher;
+
+use crate::DefaultHashMap;
+
+/// Building maps from the environment.
+impl<S> DefaultHashMap<String, String, S>
+where
+    S: BuildHasher + Default,
+{
+    /// Collects the environment variables whose names start with `prefix`, keyed by the rest of
+    /// their name. Variables whose name or value is not valid unicode are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// std::env::set_var("MYAPP_PORT", "8080");
+    ///
+    /// let config: DefaultHashMap<String, String> = DefaultHashMap::from_env_prefix("MYAPP_");
+    ///
+    /// assert_eq!("8080", config.get("PORT"));
+    /// assert_eq!("", config.get("HOST"));
+    /// ```
+    pub fn from_env_prefix(prefix: &str) -> Self {
+        Self::from_env_prefix_with(prefix, str::to_owned)
+    }
+
+    /// Collects the environment variables whose names start with `prefix`, keyed by the rest of
+    /// their name passed through `normalize`. Variables whose name or value is not valid unicode
+    /// are skipped. If two names normalize to the same key, the value read last is kept.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// std::env::set_var("OTHERAPP_LOG_LEVEL", "debug");
+    ///
+    /// let config: DefaultHashMap<String, String> =
+    ///     DefaultHashMap::from_env_prefix_with("OTHERAPP_", |name| {
+    ///         name.to_lowercase().replace('_', ".")
+    ///     });
+    ///
+    /// assert_eq!("debug", config.get("log.level"));
+    /// ```
+    pub fn from_env_prefix_with<F>(prefix: &str, mut normalize: F) -> Self
+    where
+        F: FnMut(&str) -> String,
+    {
+        let mut map = Self::with_hasher(S::default());
+        for (name, value) in env::vars_os() {
+            let (Some(name), Ok(value)) = (name.to_str(), value.into_string()) else {
+                continue;
+            };
+            if let Some(key) = name.strip_prefix(prefix) {
+                map.insert(normalize(key), value);
+            }
+        }
+        map
+    }
+}
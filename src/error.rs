@@ -0,0 +1,103 @@
+#![deny(missing_docs)]
+
+use std::fmt;
+
+/// The error type shared by the fallible operations of this crate. Errors that carry more detail,
+/// such as [`DuplicateKeyError`], convert into it, so `?` can be used in functions that return
+/// this type.
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultHashMap, Error};
+///
+/// fn load() -> Result<DefaultHashMap<&'static str, u8>, Error> {
+///     Ok(DefaultHashMap::try_from_iter([("a", 1), ("a", 2)])?)
+/// }
+///
+/// assert_eq!(Err(Error::DuplicateKey(String::from("\"a\""))), load());
+/// assert_eq!("duplicate key \"a\"", load().unwrap_err().to_string());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input contained the same key more than once, holds the key formatted with [`Debug`].
+    ///
+    /// [`Debug`]: fmt::Debug
+    DuplicateKey(String),
+    /// The key is already present and the operation does not overwrite it.
+    Occupied,
+    /// A function that loads a value for a key failed, for example the loader of a
+    /// [`crate::MemoMap`].
+    Loader(String),
+    /// A key or value was rejected by a validation rule.
+    Validation(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DuplicateKey(key) => write!(f, "duplicate key {key}"),
+            Error::Occupied => f.write_str("the key is already present"),
+            Error::Loader(message) => write!(f, "loading the value failed: {message}"),
+            Error::Validation(message) => write!(f, "validation failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The error returned when the input of a map contains the same key more than once.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultHashMap;
+///
+/// let error = DefaultHashMap::<&str, u8>::try_from_iter([("a", 1), ("b", 2), ("a", 3)])
+///     .unwrap_err();
+///
+/// assert_eq!(&"a", error.key());
+/// assert_eq!("duplicate key \"a\"", error.to_string());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DuplicateKeyError<K> {
+    _key: K,
+}
+
+impl<K> DuplicateKeyError<K> {
+    /// Creates the error for the given key.
+    pub(crate) fn new(key: K) -> Self {
+        Self { _key: key }
+    }
+
+    /// Consumes the error and returns the duplicate key.
+    #[inline]
+    pub fn into_key(self) -> K {
+        self._key
+    }
+
+    /// Returns the duplicate key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self._key
+    }
+}
+
+impl<K> fmt::Display for DuplicateKeyError<K>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate key {:?}", self._key)
+    }
+}
+
+impl<K> std::error::Error for DuplicateKeyError<K> where K: fmt::Debug {}
+
+impl<K> From<DuplicateKeyError<K>> for Error
+where
+    K: fmt::Debug,
+{
+    fn from(error: DuplicateKeyError<K>) -> Self {
+        Error::DuplicateKey(format!("{:?}", error._key))
+    }
+}
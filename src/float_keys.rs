@@ -0,0 +1,242 @@
+#![deny(missing_docs)]
+
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Bound, RangeBounds};
+
+use ordered_float::{NotNan, OrderedFloat};
+
+use crate::{DefaultBTreeMap, DefaultHashMap};
+
+/// A key that wraps a float so it can be ordered and hashed, which lets maps with these keys be
+/// used with plain floats through methods such as [`DefaultBTreeMap::get_float`] and
+/// [`DefaultHashMap::insert_float`]. This requires the `ordered-float` feature.
+///
+/// Implemented for [`OrderedFloat`], which accepts every float and sorts NaN above all other
+/// values, and for [`NotNan`], which rejects NaN.
+pub trait FloatKey: Copy + Ord + Hash {
+    /// The float that is wrapped.
+    type Float: Copy;
+
+    /// Wraps the float, or returns `None` if the key type does not accept it.
+    fn from_float(value: Self::Float) -> Option<Self>;
+
+    /// Returns the wrapped float.
+    fn to_float(self) -> Self::Float;
+}
+
+macro_rules! impl_float_key {
+    ($($float:ty),*) => {
+        $(
+            impl FloatKey for OrderedFloat<$float> {
+                type Float = $float;
+
+                #[inline]
+                fn from_float(value: $float) -> Option<Self> {
+                    Some(OrderedFloat(value))
+                }
+
+                #[inline]
+                fn to_float(self) -> $float {
+                    self.0
+                }
+            }
+
+            impl FloatKey for NotNan<$float> {
+                type Float = $float;
+
+                #[inline]
+                fn from_float(value: $float) -> Option<Self> {
+                    NotNan::new(value).ok()
+                }
+
+                #[inline]
+                fn to_float(self) -> $float {
+                    self.into_inner()
+                }
+            }
+        )*
+    };
+}
+
+impl_float_key!(f32, f64);
+
+/// Wraps a float that is stored in the map.
+fn wrap<K: FloatKey>(value: K::Float) -> K {
+    K::from_float(value).expect("NaN can not be used as a key of this map")
+}
+
+/// Lookups and updates with plain floats for maps keyed by a [`FloatKey`].
+impl<K, V> DefaultBTreeMap<K, V>
+where
+    K: FloatKey,
+    V: Default,
+{
+    /// Returns `true` if the map holds the float. A NaN is never held by a map with [`NotNan`]
+    /// keys.
+    pub fn contains_float(&self, key: K::Float) -> bool {
+        K::from_float(key).is_some_and(|key| self.contains_key(&key))
+    }
+
+    /// Returns a reference to the value of the float, or the default value if it is missing.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    /// use ordered_float::OrderedFloat;
+    ///
+    /// let mut map = DefaultBTreeMap::<OrderedFloat<f64>, u32>::new();
+    /// map.insert_float(0.5, 3);
+    ///
+    /// assert_eq!(&3, map.get_float(0.5));
+    /// assert_eq!(&0, map.get_float(f64::NAN));
+    /// ```
+    #[must_use]
+    pub fn get_float(&self, key: K::Float) -> &V {
+        match K::from_float(key) {
+            Some(key) => self.get(&key),
+            None => self.base_default(),
+        }
+    }
+
+    /// Returns a mutable reference to the value of the float, inserting the default value if it
+    /// is missing.
+    ///
+    /// # Panics
+    /// Panics if the float is NaN and the keys are [`NotNan`].
+    #[must_use]
+    pub fn get_mut_float(&mut self, key: K::Float) -> &mut V {
+        self.get_mut(&wrap(key))
+    }
+
+    /// Inserts a value at the float.
+    ///
+    /// # Panics
+    /// Panics if the float is NaN and the keys are [`NotNan`].
+    pub fn insert_float(&mut self, key: K::Float, value: V) {
+        self.insert(wrap(key), value);
+    }
+
+    /// An iterator over the entries of the map with the keys as plain floats, in ascending order.
+    pub fn iter_float(&self) -> impl DoubleEndedIterator<Item = (K::Float, &V)> {
+        self.into_iter().map(|(key, value)| (key.to_float(), value))
+    }
+
+    /// An iterator over the entries whose keys fall in a range of plain floats, in ascending
+    /// order.
+    ///
+    /// # Panics
+    /// Panics if a bound of the range is NaN and the keys are [`NotNan`]. Panics if range
+    /// `start > end`, or if `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultBTreeMap;
+    /// use ordered_float::NotNan;
+    ///
+    /// let mut histogram = DefaultBTreeMap::<NotNan<f64>, u32>::new();
+    /// for sample in [0.1, 0.4, 0.45, 0.9] {
+    ///     *histogram.get_mut_float(sample) += 1;
+    /// }
+    ///
+    /// let middle: Vec<f64> = histogram.range_float(0.25..0.75).map(|(key, _)| key).collect();
+    ///
+    /// assert_eq!(vec![0.4, 0.45], middle);
+    /// ```
+    pub fn range_float<R>(&self, range: R) -> impl DoubleEndedIterator<Item = (K::Float, &V)>
+    where
+        R: RangeBounds<K::Float>,
+    {
+        let bounds: (Bound<K>, Bound<K>) = (
+            range.start_bound().map(|start| wrap(*start)),
+            range.end_bound().map(|end| wrap(*end)),
+        );
+        self.range(bounds)
+            .map(|(key, value)| (key.to_float(), value))
+    }
+
+    /// Removes the float from the map and returns its value, or the default value if it was
+    /// missing.
+    #[must_use]
+    pub fn remove_float(&mut self, key: K::Float) -> V {
+        K::from_float(key).map_or_else(V::default, |key| self.remove(&key))
+    }
+}
+
+/// Lookups and updates with plain floats for maps keyed by a [`FloatKey`].
+impl<K, V, S> DefaultHashMap<K, V, S>
+where
+    K: FloatKey,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Returns `true` if the map holds the float. A NaN is never held by a map with [`NotNan`]
+    /// keys.
+    pub fn contains_float(&self, key: K::Float) -> bool {
+        K::from_float(key).is_some_and(|key| self.contains_key(&key))
+    }
+
+    /// Returns a reference to the value of the float, or the default value if it is missing.
+    /// Positive and negative zero are the same key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    /// use ordered_float::OrderedFloat;
+    ///
+    /// let mut map = DefaultHashMap::<OrderedFloat<f32>, &str>::new();
+    /// map.insert_float(0.0, "zero");
+    ///
+    /// assert_eq!(&"zero", map.get_float(-0.0));
+    /// assert_eq!(&"", map.get_float(1.0));
+    /// ```
+    #[must_use]
+    pub fn get_float(&self, key: K::Float) -> &V {
+        match K::from_float(key) {
+            Some(key) => self.get(&key),
+            None => self.base_default(),
+        }
+    }
+
+    /// Returns a mutable reference to the value of the float, inserting the default value if it
+    /// is missing.
+    ///
+    /// # Panics
+    /// Panics if the float is NaN and the keys are [`NotNan`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    /// use ordered_float::NotNan;
+    ///
+    /// let mut prices = DefaultHashMap::<NotNan<f64>, u32>::new();
+    /// for price in [9.99, 4.5, 9.99] {
+    ///     *prices.get_mut_float(price) += 1;
+    /// }
+    ///
+    /// assert_eq!(&2, prices.get_float(9.99));
+    /// ```
+    #[must_use]
+    pub fn get_mut_float(&mut self, key: K::Float) -> &mut V {
+        self.get_mut(&wrap(key))
+    }
+
+    /// Inserts a value at the float, returning the previous value if the float was present.
+    ///
+    /// # Panics
+    /// Panics if the float is NaN and the keys are [`NotNan`].
+    pub fn insert_float(&mut self, key: K::Float, value: V) -> Option<V> {
+        self.insert(wrap(key), value)
+    }
+
+    /// An iterator over the entries of the map with the keys as plain floats, in arbitrary order.
+    pub fn iter_float(&self) -> impl Iterator<Item = (K::Float, &V)> {
+        self.into_iter().map(|(key, value)| (key.to_float(), value))
+    }
+
+    /// Removes the float from the map and returns its value, or the default value if it was
+    /// missing.
+    #[must_use]
+    pub fn remove_float(&mut self, key: K::Float) -> V {
+        K::from_float(key).map_or_else(V::default, |key| self.remove(&key))
+    }
+}
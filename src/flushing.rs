@@ -0,0 +1,145 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::DefaultHashMap;
+
+/// Aggregates a stream into a [`DefaultHashMap`] and hands the map to a sink in batches.
+///
+/// The map is drained into the sink whenever it grows beyond a number of entries or when an
+/// interval has passed since the last flush, after which aggregation starts from an empty map.
+/// The time trigger is checked on every update and by [`FlushingMap::tick`], which can be called
+/// periodically when updates are rare. Whatever is left is flushed when the map is dropped.
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultHashMap, FlushingMap};
+///
+/// let mut batches = Vec::new();
+/// {
+///     let mut counts = FlushingMap::new(|batch: DefaultHashMap<&str, u32>| batches.push(batch))
+///         .max_entries(2);
+///     for word in ["a", "b", "a", "c", "d"] {
+///         counts.update(word, |count| *count += 1);
+///     }
+/// }
+///
+/// assert_eq!(2, batches.len());
+/// assert_eq!(&2, batches[0].get(&"a"));
+/// assert_eq!(&1, batches[0].get(&"c"));
+/// assert_eq!(&1, batches[1].get(&"d"));
+/// ```
+pub struct FlushingMap<K, V, F>
+where
+    K: Eq + Hash,
+    V: Default,
+    F: FnMut(DefaultHashMap<K, V>),
+{
+    _map: DefaultHashMap<K, V>,
+    _sink: F,
+    _max_entries: Option<usize>,
+    _interval: Option<Duration>,
+    _last_flush: Instant,
+}
+
+impl<K, V, F> FlushingMap<K, V, F>
+where
+    K: Eq + Hash,
+    V: Default,
+    F: FnMut(DefaultHashMap<K, V>),
+{
+    /// Creates an empty map that flushes into the sink. Without triggers it only flushes when
+    /// [`FlushingMap::flush`] is called or when it is dropped.
+    #[must_use]
+    pub fn new(sink: F) -> Self {
+        Self {
+            _map: DefaultHashMap::new(),
+            _sink: sink,
+            _max_entries: None,
+            _interval: None,
+            _last_flush: Instant::now(),
+        }
+    }
+
+    /// Flushes whenever the map holds more than `max_entries` keys after an update.
+    #[must_use]
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self._max_entries = Some(max_entries);
+        self
+    }
+
+    /// Flushes whenever `interval` has passed since the last flush.
+    #[must_use]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self._interval = Some(interval);
+        self
+    }
+
+    /// Returns a reference to the map aggregated since the last flush.
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<K, V> {
+        &self._map
+    }
+
+    /// Hands the aggregated map to the sink and starts from an empty map. Nothing is handed over
+    /// if the map is empty.
+    pub fn flush(&mut self) {
+        self._last_flush = Instant::now();
+        if !self._map.is_empty() {
+            (self._sink)(mem::take(&mut self._map));
+        }
+    }
+
+    /// Returns `true` if nothing has been aggregated since the last flush.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._map.is_empty()
+    }
+
+    /// Returns the number of keys aggregated since the last flush.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._map.len()
+    }
+
+    /// Flushes if the interval has passed since the last flush.
+    #[inline]
+    pub fn tick(&mut self) {
+        self.tick_at(Instant::now());
+    }
+
+    /// Flushes if the interval has passed between the last flush and `now`.
+    pub fn tick_at(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self._last_flush);
+        if self._interval.is_some_and(|interval| elapsed >= interval) {
+            self.flush();
+        }
+    }
+
+    /// Changes the value of the key with `func`, starting from the default value for a new key,
+    /// and flushes if a trigger fires.
+    pub fn update<G>(&mut self, key: K, func: G)
+    where
+        G: FnOnce(&mut V),
+    {
+        func(self._map.entry(key).or_default());
+        if self._max_entries.is_some_and(|max| self._map.len() > max) {
+            self.flush();
+        } else {
+            self.tick();
+        }
+    }
+}
+
+impl<K, V, F> Drop for FlushingMap<K, V, F>
+where
+    K: Eq + Hash,
+    V: Default,
+    F: FnMut(DefaultHashMap<K, V>),
+{
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
@@ -0,0 +1,76 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::Hash;
+
+use crate::DefaultHashMap;
+
+/// Two-level group-bys into nested maps.
+impl<K1, K2, A> DefaultHashMap<K1, DefaultHashMap<K2, A>, RandomState>
+where
+    K1: Eq + Hash,
+    K2: Eq + Hash,
+    A: Default,
+{
+    /// Groups `(outer, inner, value)` triples by both keys, folding every value into the
+    /// aggregate of its group with `fold`. Aggregates start at their default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let sales = [
+    ///     ("nl", "apples", 3),
+    ///     ("nl", "pears", 1),
+    ///     ("de", "apples", 2),
+    ///     ("nl", "apples", 4),
+    /// ];
+    ///
+    /// let totals: DefaultHashMap<&str, DefaultHashMap<&str, u32>> =
+    ///     DefaultHashMap::from_nested_fold(sales, |total, amount| *total += amount);
+    ///
+    /// assert_eq!(&7, totals.get(&"nl").get(&"apples"));
+    /// assert_eq!(&2, totals.get(&"de").get(&"apples"));
+    /// assert_eq!(&0, totals.get(&"de").get(&"pears"));
+    /// ```
+    pub fn from_nested_fold<I, V, F>(iter: I, mut fold: F) -> Self
+    where
+        I: IntoIterator<Item = (K1, K2, V)>,
+        F: FnMut(&mut A, V),
+    {
+        let mut map = Self::new();
+        for (outer, inner, value) in iter {
+            let group = map.entry(outer).or_default();
+            fold(group.entry(inner).or_default(), value);
+        }
+        map
+    }
+}
+
+impl<K1, K2, V> DefaultHashMap<K1, DefaultHashMap<K2, Vec<V>>, RandomState>
+where
+    K1: Eq + Hash,
+    K2: Eq + Hash,
+{
+    /// Groups `(outer, inner, value)` triples by both keys, collecting the values of every group
+    /// in the order they were produced.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let requests = [("GET", 200, "/"), ("GET", 404, "/missing"), ("GET", 200, "/about")];
+    ///
+    /// let grouped = DefaultHashMap::from_nested_groups(requests);
+    ///
+    /// assert_eq!(&vec!["/", "/about"], grouped.get(&"GET").get(&200));
+    /// assert_eq!(&vec!["/missing"], grouped.get(&"GET").get(&404));
+    /// assert!(grouped.get(&"POST").get(&200).is_empty());
+    /// ```
+    pub fn from_nested_groups<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K1, K2, V)>,
+    {
+        Self::from_nested_fold(iter, Vec::push)
+    }
+}
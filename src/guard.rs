@@ -0,0 +1,150 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Deref, DerefMut};
+
+use crate::DefaultHashMap;
+
+/// A check that decides whether the value is written back when the guard is dropped.
+type Keep<'a, V> = Box<dyn FnMut(&V) -> bool + 'a>;
+
+/// A guard around the value of a single key of a [`DefaultHashMap`]. This is created by calling
+/// [`DefaultHashMap::guard`].
+///
+/// The guard dereferences to the value, which is the default value if the key was missing. When
+/// the guard is dropped the value is written back to the map, unless one of the checks added
+/// with [`ValueGuard::keep_if`] or [`ValueGuard::remove_if_default`] rejects it, in which case
+/// the key is removed from the map.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultHashMap;
+///
+/// let mut stock = DefaultHashMap::<&str, u32>::new();
+/// stock.insert("apples", 1);
+///
+/// {
+///     let mut apples = stock.guard("apples").remove_if_default();
+///     *apples -= 1;
+/// }
+///
+/// assert!(!stock.contains_key(&"apples"));
+/// ```
+pub struct ValueGuard<'a, K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    _map: &'a mut DefaultHashMap<K, V, S>,
+    _entry: Option<(K, V)>,
+    _keep: Vec<Keep<'a, V>>,
+}
+
+impl<'a, K, V, S> ValueGuard<'a, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Takes the value of the key out of the map for the lifetime of the guard.
+    pub(crate) fn new(map: &'a mut DefaultHashMap<K, V, S>, key: K) -> Self {
+        let value = map.remove(&key);
+        Self {
+            _map: map,
+            _entry: Some((key, value)),
+            _keep: Vec::new(),
+        }
+    }
+
+    /// Returns the key this guard is for.
+    pub fn key(&self) -> &K {
+        &self.entry().0
+    }
+
+    /// Only writes the value back if the predicate holds for it when the guard is dropped.
+    /// Otherwise the key is removed from the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<&str, Vec<u8>>::new();
+    ///
+    /// {
+    ///     let mut bytes = map.guard("bytes").keep_if(|bytes| bytes.len() <= 2);
+    ///     bytes.extend([1, 2, 3]);
+    /// }
+    ///
+    /// assert!(!map.contains_key(&"bytes"));
+    /// ```
+    #[must_use]
+    pub fn keep_if<F>(mut self, pred: F) -> Self
+    where
+        F: FnMut(&V) -> bool + 'a,
+    {
+        self._keep.push(Box::new(pred));
+        self
+    }
+
+    /// Removes the key instead of writing the value back if the value equals the default value
+    /// when the guard is dropped.
+    #[must_use]
+    pub fn remove_if_default(self) -> Self
+    where
+        V: Clone + PartialEq + 'a,
+    {
+        let default = self._map.get(self.key()).clone();
+        self.keep_if(move |value| *value != default)
+    }
+
+    fn entry(&self) -> &(K, V) {
+        self._entry
+            .as_ref()
+            .expect("the entry is only taken on drop")
+    }
+}
+
+impl<K, V, S> Deref for ValueGuard<'_, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.entry().1
+    }
+}
+
+impl<K, V, S> DerefMut for ValueGuard<'_, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self
+            ._entry
+            .as_mut()
+            .expect("the entry is only taken on drop")
+            .1
+    }
+}
+
+impl<K, V, S> Drop for ValueGuard<'_, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    fn drop(&mut self) {
+        if let Some((key, value)) = self._entry.take() {
+            if self._keep.iter_mut().all(|keep| keep(&value)) {
+                self._map.insert(key, value);
+            }
+        }
+    }
+}
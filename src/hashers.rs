@@ -0,0 +1,113 @@
+#![deny(missing_docs)]
+
+#[cfg(any(feature = "ahash", feature = "fxhash"))]
+use std::hash::Hash;
+
+#[cfg(feature = "fxhash")]
+use fxhash::FxBuildHasher;
+#[cfg(feature = "nohash")]
+use nohash_hasher::BuildNoHashHasher;
+
+#[cfg(any(feature = "ahash", feature = "fxhash", feature = "nohash"))]
+use crate::DefaultHashMap;
+
+/// A [`DefaultHashMap`] that hashes its keys with [ahash](https://crates.io/crates/ahash). This
+/// requires the `ahash` feature.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultAHashMap;
+///
+/// let mut map: DefaultAHashMap<&str, u32> = DefaultAHashMap::with_ahash();
+/// *map.get_mut(&"a") += 1;
+///
+/// assert_eq!(&1, map.get(&"a"));
+/// ```
+#[cfg(feature = "ahash")]
+pub type DefaultAHashMap<K, V> = DefaultHashMap<K, V, ahash::RandomState>;
+
+/// A [`DefaultHashMap`] that hashes its keys with
+/// [FxHash](https://crates.io/crates/fxhash), the fast non-cryptographic hash used by the Rust
+/// compiler. This requires the `fxhash` feature.
+///
+/// FxHash is not randomized, so keys that come from untrusted input can degrade the map.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultFxHashMap;
+///
+/// let mut map: DefaultFxHashMap<&str, u32> = DefaultFxHashMap::with_fxhash();
+/// *map.get_mut(&"a") += 1;
+///
+/// assert_eq!(&1, map.get(&"a"));
+/// ```
+#[cfg(feature = "fxhash")]
+pub type DefaultFxHashMap<K, V> = DefaultHashMap<K, V, FxBuildHasher>;
+
+/// A [`DefaultHashMap`] for integer keys that uses the integer itself as the hash instead of
+/// running it through SipHash. This requires the `nohash` feature.
+///
+/// Only keys that implement [`nohash_hasher::IsEnabled`], the primitive integers and types that
+/// opt in, can be used. The hash is not randomized, so keys that come from untrusted input can
+/// degrade the map.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultIntMap;
+///
+/// let mut map: DefaultIntMap<u64, u32> = DefaultIntMap::default();
+/// *map.get_mut(&42) += 1;
+///
+/// assert_eq!(&1, map.get(&42));
+/// assert_eq!(&0, map.get(&7));
+/// ```
+#[cfg(feature = "nohash")]
+pub type DefaultIntMap<K, V> = DefaultHashMap<K, V, BuildNoHashHasher<K>>;
+
+#[cfg(feature = "ahash")]
+impl<K, V> DefaultHashMap<K, V, ahash::RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`DefaultHashMap`] that hashes its keys with a randomly seeded ahash
+    /// hasher. This requires the `ahash` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::with_ahash();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn with_ahash() -> Self {
+        Self::with_hasher(ahash::RandomState::new())
+    }
+}
+
+#[cfg(feature = "fxhash")]
+impl<K, V> DefaultHashMap<K, V, FxBuildHasher>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`DefaultHashMap`] that hashes its keys with FxHash. This requires the
+    /// `fxhash` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::with_fxhash();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn with_fxhash() -> Self {
+        Self::with_hasher(FxBuildHasher::default())
+    }
+}
@@ -0,0 +1,173 @@
+#![deny(missing_docs)]
+
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use crate::DefaultHashMap;
+
+/// The estimated count of a monitored key and how much it may be overestimated.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Slot {
+    count: u64,
+    error: u64,
+    /// When the key started being monitored, which orders keys with equal counts.
+    seq: u64,
+}
+
+/// An approximate counter that finds the most frequent keys of a stream in bounded memory, using
+/// the space-saving algorithm.
+///
+/// At most `capacity` keys are monitored. When a new key arrives while all slots are taken, it
+/// replaces the key with the smallest count and inherits that count as its possible error. Every
+/// key that occurs more than `total / capacity` times is guaranteed to be monitored, and counts
+/// are never underestimated. The monitored keys are kept ordered by their count, so every update
+/// takes O(log capacity).
+///
+/// # Example
+/// ```
+/// use defaultdict::HeavyHitters;
+///
+/// let mut hitters = HeavyHitters::new(2);
+/// for word in "a b a c a a d".split(' ') {
+///     hitters.add(&word);
+/// }
+///
+/// assert_eq!(vec![(&"a", 4)], hitters.most_common(1));
+/// assert_eq!(7, hitters.total());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HeavyHitters<K>
+where
+    K: Eq + Hash,
+{
+    _slots: DefaultHashMap<K, Slot>,
+    /// The monitored keys ordered by `(count, seq)`, the first one is evicted next.
+    _order: BTreeMap<(u64, u64), K>,
+    _capacity: usize,
+    _next_seq: u64,
+    _total: u64,
+}
+
+impl<K> HeavyHitters<K>
+where
+    K: Eq + Hash,
+{
+    /// Creates a counter that monitors at most `capacity` keys.
+    ///
+    /// # Panics
+    /// Panics if the capacity is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity must be at least one");
+        Self {
+            _slots: DefaultHashMap::new(),
+            _order: BTreeMap::new(),
+            _capacity: capacity,
+            _next_seq: 0,
+            _total: 0,
+        }
+    }
+
+    /// Counts one occurrence of the key.
+    #[inline]
+    pub fn add(&mut self, key: &K)
+    where
+        K: Clone,
+    {
+        self.add_n(key, 1);
+    }
+
+    /// Counts `n` occurrences of the key. When all slots are taken and the key is not monitored,
+    /// it replaces the key with the smallest count. Counts saturate at [`u64::MAX`].
+    pub fn add_n(&mut self, key: &K, n: u64)
+    where
+        K: Clone,
+    {
+        self._total = self._total.saturating_add(n);
+        if self._slots.contains_key(key) {
+            let slot = self._slots.get_mut(key);
+            let key = self
+                ._order
+                .remove(&(slot.count, slot.seq))
+                .expect("every monitored key is ordered");
+            slot.count = slot.count.saturating_add(n);
+            self._order.insert((slot.count, slot.seq), key);
+            return;
+        }
+        let error = if self._slots.len() < self._capacity {
+            0
+        } else {
+            let ((minimum, _), evicted) = self._order.pop_first().expect("the slots are full");
+            let _ = self._slots.remove(&evicted);
+            minimum
+        };
+        let slot = Slot {
+            count: error.saturating_add(n),
+            error,
+            seq: self._next_seq,
+        };
+        self._next_seq += 1;
+        self._order.insert((slot.count, slot.seq), key.clone());
+        self._slots.insert(key.clone(), slot);
+    }
+
+    /// Returns the maximum number of monitored keys.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self._capacity
+    }
+
+    /// Returns the estimated count of the key, or `0` if the key is not monitored. The estimate
+    /// is at most [`HeavyHitters::error`] higher than the true count.
+    #[must_use]
+    pub fn count(&self, key: &K) -> u64 {
+        self._slots.get(key).count
+    }
+
+    /// Returns by how much the count of the key may be overestimated.
+    #[must_use]
+    pub fn error(&self, key: &K) -> u64 {
+        self._slots.get(key).error
+    }
+
+    /// Returns `true` if nothing has been counted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._slots.is_empty()
+    }
+
+    /// Returns the number of monitored keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._slots.len()
+    }
+
+    /// Returns the `n` monitored keys with the highest estimated counts, from most to least
+    /// common.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::HeavyHitters;
+    ///
+    /// let mut hitters = HeavyHitters::new(10);
+    /// hitters.add_n(&'a', 5);
+    /// hitters.add_n(&'b', 9);
+    /// hitters.add_n(&'c', 1);
+    ///
+    /// assert_eq!(vec![(&'b', 9), (&'a', 5)], hitters.most_common(2));
+    /// ```
+    pub fn most_common(&self, n: usize) -> Vec<(&K, u64)> {
+        self._order
+            .iter()
+            .rev()
+            .take(n)
+            .map(|(&(count, _), key)| (key, count))
+            .collect()
+    }
+
+    /// Returns the total number of counted occurrences.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self._total
+    }
+}
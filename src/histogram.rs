@@ -0,0 +1,230 @@
+#![deny(missing_docs)]
+
+use crate::DefaultBTreeMap;
+
+/// How samples are assigned to bins.
+#[derive(Clone, Debug, PartialEq)]
+enum Bins {
+    /// Bins of a fixed width, bin `i` covers `[i * width, (i + 1) * width)`.
+    Width(f64),
+    /// Sorted bin edges, bin `i` covers `[edges[i - 1], edges[i])`.
+    Edges(Vec<f64>),
+}
+
+/// Counts numeric samples per bin, using a [`DefaultBTreeMap`] from the bin index to the count.
+///
+/// Bins either have a fixed width, in which case bin `i` covers `[i * width, (i + 1) * width)`
+/// and can be negative, or are defined by sorted edges `e`, in which case bin `0` covers
+/// everything below `e[0]`, bin `i` covers `[e[i - 1], e[i])` and the last bin covers everything
+/// from the last edge up. `NaN` samples are ignored, as are infinite samples in a histogram with a
+/// width, since they do not fall into any bin.
+///
+/// # Example
+/// ```
+/// use defaultdict::Histogram;
+///
+/// let mut latencies = Histogram::with_edges(vec![10.0, 50.0, 100.0]);
+/// for sample in [3.0, 12.0, 20.0, 75.0, 250.0] {
+///     latencies.record(sample);
+/// }
+///
+/// assert_eq!(&1, latencies.counts().get(&0));
+/// assert_eq!(&2, latencies.counts().get(&1));
+/// assert_eq!(5, latencies.total());
+/// assert_eq!((10.0, 50.0), latencies.bin_range(1));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    _bins: Bins,
+    _counts: DefaultBTreeMap<i64, u64>,
+    _total: u64,
+}
+
+impl Histogram {
+    /// Creates a histogram with bins of a fixed width, starting at zero.
+    ///
+    /// # Panics
+    /// Panics if the width is not a positive finite number.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Histogram;
+    ///
+    /// let mut histogram = Histogram::with_width(10.0);
+    /// histogram.record(-5.0);
+    /// histogram.record(15.0);
+    ///
+    /// assert_eq!(&1, histogram.counts().get(&-1));
+    /// assert_eq!(&1, histogram.counts().get(&1));
+    /// ```
+    #[must_use]
+    pub fn with_width(width: f64) -> Self {
+        assert!(
+            width.is_finite() && width > 0.0,
+            "the bin width must be a positive finite number"
+        );
+        Self::from_bins(Bins::Width(width))
+    }
+
+    /// Creates a histogram with bins between the given edges. The edges are sorted and `NaN`
+    /// edges are dropped.
+    ///
+    /// # Panics
+    /// Panics if no edges are left, since a single unbounded bin has no percentiles.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Histogram;
+    ///
+    /// let mut histogram = Histogram::with_edges(vec![1.0, 0.0]);
+    /// histogram.record(0.5);
+    ///
+    /// assert_eq!(1, histogram.bin(0.5));
+    /// assert_eq!(&1, histogram.counts().get(&1));
+    /// ```
+    #[must_use]
+    pub fn with_edges(mut edges: Vec<f64>) -> Self {
+        edges.retain(|edge| !edge.is_nan());
+        edges.sort_by(f64::total_cmp);
+        edges.dedup();
+        assert!(!edges.is_empty(), "a histogram needs at least one bin edge");
+        Self::from_bins(Bins::Edges(edges))
+    }
+
+    /// Returns the index of the bin the sample falls into.
+    pub fn bin(&self, sample: f64) -> i64 {
+        match &self._bins {
+            Bins::Width(width) => (sample / width).floor() as i64,
+            Bins::Edges(edges) => edges.partition_point(|edge| *edge <= sample) as i64,
+        }
+    }
+
+    /// Returns the lower and upper bound of a bin. The lower bound is part of the bin, the upper
+    /// bound is not. The outer bins of a histogram with edges are unbounded.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Histogram;
+    ///
+    /// let histogram = Histogram::with_edges(vec![0.0, 1.0]);
+    ///
+    /// assert_eq!((f64::NEG_INFINITY, 0.0), histogram.bin_range(0));
+    /// assert_eq!((1.0, f64::INFINITY), histogram.bin_range(2));
+    /// ```
+    pub fn bin_range(&self, bin: i64) -> (f64, f64) {
+        match &self._bins {
+            Bins::Width(width) => (bin as f64 * width, bin.saturating_add(1) as f64 * width),
+            Bins::Edges(edges) => {
+                let edge = |index: i64| usize::try_from(index).ok().and_then(|i| edges.get(i));
+                (
+                    bin.checked_sub(1)
+                        .and_then(edge)
+                        .copied()
+                        .unwrap_or(f64::NEG_INFINITY),
+                    edge(bin).copied().unwrap_or(f64::INFINITY),
+                )
+            }
+        }
+    }
+
+    /// Returns the number of samples per bin. Bins without samples are not present.
+    #[inline]
+    pub fn counts(&self) -> &DefaultBTreeMap<i64, u64> {
+        &self._counts
+    }
+
+    /// Adds the counts of another histogram to this one.
+    ///
+    /// # Panics
+    /// Panics if the histograms do not use the same bins.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Histogram;
+    ///
+    /// let mut a = Histogram::with_width(1.0);
+    /// let mut b = Histogram::with_width(1.0);
+    /// a.record(0.5);
+    /// b.record(0.7);
+    /// b.record(3.0);
+    ///
+    /// a.merge(&b);
+    ///
+    /// assert_eq!(&2, a.counts().get(&0));
+    /// assert_eq!(3, a.total());
+    /// ```
+    pub fn merge(&mut self, other: &Histogram) {
+        assert_eq!(
+            self._bins, other._bins,
+            "only histograms with the same bins can be merged"
+        );
+        for (bin, count) in &other._counts {
+            *self._counts.get_mut(bin) += count;
+        }
+        self._total += other._total;
+    }
+
+    /// Estimates the value below which `percentile` percent of the samples fall, by
+    /// interpolating linearly inside the bin that contains it. Returns `None` if no samples were
+    /// recorded. For the unbounded outer bins the finite bound is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Histogram;
+    ///
+    /// let mut histogram = Histogram::with_width(10.0);
+    /// for sample in 0..100 {
+    ///     histogram.record(sample as f64);
+    /// }
+    ///
+    /// assert_eq!(Some(50.0), histogram.percentile(50.0));
+    /// assert_eq!(Some(95.0), histogram.percentile(95.0));
+    /// ```
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        if self._total == 0 {
+            return None;
+        }
+        let target = percentile.clamp(0.0, 100.0) / 100.0 * self._total as f64;
+        let mut seen = 0.0;
+        let mut last = None;
+        for (bin, count) in &self._counts {
+            let count = *count as f64;
+            last = Some(*bin);
+            if seen + count >= target {
+                let (lower, upper) = self.bin_range(*bin);
+                return Some(match (lower.is_finite(), upper.is_finite()) {
+                    (true, true) => lower + (target - seen) / count * (upper - lower),
+                    (true, false) => lower,
+                    _ => upper,
+                });
+            }
+            seen += count;
+        }
+        last.map(|bin| self.bin_range(bin).1)
+    }
+
+    /// Adds a sample to its bin. `NaN` samples are ignored, and so are infinite samples if the
+    /// bins have a width.
+    pub fn record(&mut self, sample: f64) {
+        if sample.is_nan() || (sample.is_infinite() && matches!(self._bins, Bins::Width(_))) {
+            return;
+        }
+        let bin = self.bin(sample);
+        *self._counts.get_mut(&bin) += 1;
+        self._total += 1;
+    }
+
+    /// Returns the number of recorded samples.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self._total
+    }
+
+    fn from_bins(bins: Bins) -> Self {
+        Self {
+            _bins: bins,
+            _counts: DefaultBTreeMap::new(),
+            _total: 0,
+        }
+    }
+}
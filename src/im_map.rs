@@ -0,0 +1,229 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+
+use im::hashmap::{HashMap, Iter, Keys, Values};
+
+use crate::DefaultHashMap;
+
+/// A persistent version of the [`DefaultHashMap`], backed by the hash array mapped trie of the
+/// [`im`] crate. This requires the `im` feature.
+///
+/// Updating the map does not change it, instead a new version is returned that shares most of
+/// its structure with the old one. Both versions stay usable and cloning either is O(1). Missing
+/// keys still read as the default value.
+///
+/// # Example
+/// ```
+/// use defaultdict::ImDefaultHashMap;
+///
+/// let empty = ImDefaultHashMap::<i8, i8>::new();
+/// let one = empty.update(1, 1);
+/// let two = one.modify(1, |value| *value += 1);
+///
+/// assert_eq!(&0, empty.get(&1));
+/// assert_eq!(&1, one.get(&1));
+/// assert_eq!(&2, two.get(&1));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImDefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    _inner: HashMap<K, V>,
+    _default: V,
+}
+
+impl<K, V> ImDefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    /// Creates an empty [`ImDefaultHashMap`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _inner: HashMap::new(),
+            _default: V::default(),
+        }
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ImDefaultHashMap;
+    ///
+    /// let map = ImDefaultHashMap::<i8, i8>::new().update(1, 1);
+    ///
+    /// assert!(map.contains_key(&1));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._inner.contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ImDefaultHashMap;
+    ///
+    /// let map = ImDefaultHashMap::<i8, i8>::new().update(1, 2);
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// assert_eq!(&0, map.get(&2));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._inner.get(key).unwrap_or(&self._default)
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// Returns an iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self._inner.iter()
+    }
+
+    /// Returns an iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self._inner.keys()
+    }
+
+    /// Returns the length of the keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Returns a new version of the map where the value at the key, or the default value if the
+    /// key is not present, has been changed by the function passed in.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ImDefaultHashMap;
+    ///
+    /// let old = ImDefaultHashMap::<&str, Vec<i8>>::new();
+    /// let new = old.modify("a", |value| value.push(1));
+    ///
+    /// assert!(old.get(&"a").is_empty());
+    /// assert_eq!(&vec![1], new.get(&"a"));
+    /// ```
+    #[must_use]
+    pub fn modify<F>(&self, key: K, func: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        let mut value = self.get(&key).clone();
+        func(&mut value);
+        self.update(key, value)
+    }
+
+    /// Returns a new version of the map with the key value pair inserted.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ImDefaultHashMap;
+    ///
+    /// let old = ImDefaultHashMap::<i8, i8>::new();
+    /// let new = old.update(1, 1);
+    ///
+    /// assert!(old.is_empty());
+    /// assert_eq!(1, new.len());
+    /// ```
+    #[must_use]
+    pub fn update(&self, key: K, value: V) -> Self {
+        Self {
+            _inner: self._inner.update(key, value),
+            _default: self._default.clone(),
+        }
+    }
+
+    /// Returns an iterator visiting all values in arbitrary order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        self._inner.values()
+    }
+
+    /// Returns a new version of the map without the key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ImDefaultHashMap;
+    ///
+    /// let old = ImDefaultHashMap::<i8, i8>::new().update(1, 1);
+    /// let new = old.without(&1);
+    ///
+    /// assert_eq!(&1, old.get(&1));
+    /// assert_eq!(&0, new.get(&1));
+    /// ```
+    #[must_use]
+    pub fn without(&self, key: &K) -> Self {
+        Self {
+            _inner: self._inner.without(key),
+            _default: self._default.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for ImDefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> From<DefaultHashMap<K, V>> for ImDefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    fn from(map: DefaultHashMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> From<ImDefaultHashMap<K, V>> for DefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    fn from(map: ImDefaultHashMap<K, V>) -> Self {
+        map._inner.into_iter().collect()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ImDefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self {
+            _inner: iter.into_iter().collect(),
+            _default: V::default(),
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ImDefaultHashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self._inner.iter()
+    }
+}
@@ -0,0 +1,309 @@
+#![deny(missing_docs)]
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A shared pool of strings. Interning a string returns an [`Arc<str>`] that points to the one
+/// copy of that string in the pool, so every key created from it shares a single allocation.
+///
+/// Cloning an interner is cheap and the clones share the same pool, which allows multiple
+/// [`InternedHashMap`]s to share their keys.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use defaultdict::Interner;
+///
+/// let interner = Interner::new();
+/// let a = interner.intern("key");
+/// let b = interner.intern(&String::from("key"));
+///
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert_eq!(1, interner.len());
+/// ```
+#[derive(Clone, Default)]
+pub struct Interner {
+    _strings: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the pooled copy of the string, adding it to the pool if it is not there yet.
+    pub fn intern(&self, string: &str) -> Arc<str> {
+        let mut strings = self.lock();
+        if let Some(interned) = strings.get(string) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<str> = Arc::from(string);
+        strings.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the number of strings in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Drops the strings that are only referenced by the pool itself, returning how many were
+    /// removed.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Interner;
+    ///
+    /// let interner = Interner::new();
+    /// let kept = interner.intern("kept");
+    /// interner.intern("dropped");
+    ///
+    /// assert_eq!(1, interner.remove_unused());
+    /// assert_eq!(1, interner.len());
+    /// ```
+    pub fn remove_unused(&self) -> usize {
+        let mut strings = self.lock();
+        let before = strings.len();
+        strings.retain(|string| Arc::strong_count(string) > 1);
+        before - strings.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashSet<Arc<str>>> {
+        self._strings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A default map with string keys that are interned in an [`Interner`], so repeated keys, in
+/// this map or in other maps that share the interner, are only allocated once.
+///
+/// Lookups take a plain `&str` and only touch the interner when a new key is stored.
+///
+/// # Example
+/// ```
+/// use defaultdict::{InternedHashMap, Interner};
+///
+/// let interner = Interner::new();
+/// let mut errors = InternedHashMap::<u32>::with_interner(interner.clone());
+/// let mut warnings = InternedHashMap::<u32>::with_interner(interner.clone());
+///
+/// *errors.get_mut("service-a") += 1;
+/// *warnings.get_mut("service-a") += 2;
+///
+/// assert_eq!(&1, errors.get("service-a"));
+/// assert_eq!(&2, warnings.get("service-a"));
+/// assert_eq!(&0, errors.get("service-b"));
+/// assert_eq!(1, interner.len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct InternedHashMap<V>
+where
+    V: Default,
+{
+    _inner: HashMap<Arc<str>, V>,
+    _default: V,
+    _interner: Interner,
+}
+
+impl<V> InternedHashMap<V>
+where
+    V: Default,
+{
+    /// Creates an empty [`InternedHashMap`] with its own [`Interner`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let map: InternedHashMap<i8> = InternedHashMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_interner(Interner::new())
+    }
+
+    /// Creates an empty [`InternedHashMap`] that interns its keys in the given interner.
+    #[must_use]
+    pub fn with_interner(interner: Interner) -> Self {
+        Self {
+            _inner: HashMap::new(),
+            _default: V::default(),
+            _interner: interner,
+        }
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// assert!(map.contains_key("a"));
+    /// assert!(!map.contains_key("b"));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self._inner.contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(&1, map.get("a"));
+    /// assert_eq!(&0, map.get("b"));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &str) -> &V {
+        self._inner.get(key).unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key. If the key is not
+    /// present it is interned and inserted with the default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::<u32>::new();
+    /// *map.get_mut("a") += 1;
+    /// *map.get_mut("a") += 1;
+    ///
+    /// assert_eq!(&2, map.get("a"));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &str) -> &mut V {
+        if !self._inner.contains_key(key) {
+            self._inner.insert(self._interner.intern(key), V::default());
+        }
+        self._inner
+            .get_mut(key)
+            .expect("the key was inserted above")
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if the key was
+    /// present. The key is only interned if it is new.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::new();
+    ///
+    /// assert_eq!(None, map.insert("a", 1));
+    /// assert_eq!(Some(1), map.insert("a", 2));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        match self._inner.get_mut(key) {
+            Some(old) => Some(std::mem::replace(old, value)),
+            None => self._inner.insert(self._interner.intern(key), value),
+        }
+    }
+
+    /// Returns the interner the keys are stored in.
+    #[inline]
+    pub fn interner(&self) -> &Interner {
+        &self._interner
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let map: InternedHashMap<i8> = InternedHashMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(vec![("a", &1)], map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self._inner.iter().map(|(key, value)| (&**key, value))
+    }
+
+    /// Returns the length of the keys in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise. The key stays in the interner until
+    /// [`Interner::remove_unused`] is called.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::InternedHashMap;
+    ///
+    /// let mut map = InternedHashMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(1, map.remove("a"));
+    /// assert_eq!(0, map.remove("a"));
+    /// ```
+    #[must_use]
+    pub fn remove(&mut self, key: &str) -> V {
+        self._inner.remove(key).unwrap_or_default()
+    }
+}
+
+impl<V> Default for InternedHashMap<V>
+where
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,167 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{DefaultHashMap, Listener, ObservedHashMap};
+
+/// A single mutation recorded by a [`Journal`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JournalOp<K, V> {
+    /// The key was set to the value, either by an insert, an overwrite or a default.
+    Insert(K, V),
+    /// The key was removed.
+    Remove(K),
+}
+
+/// A [`Listener`] that records every mutation of an [`ObservedHashMap`] as a [`JournalOp`] in an
+/// append-only log. Replaying the log with [`DefaultHashMap::replay`] reconstructs the map, which
+/// can be used to persist it, to debug it or to mirror it somewhere else.
+///
+/// A change made through [`ObservedHashMap::get_mut`] is recorded with its final value once the
+/// guard is dropped.
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultHashMap, JournaledHashMap};
+///
+/// let mut map = JournaledHashMap::journaled();
+/// map.insert("a", 1);
+/// map.update("b", |value| *value += 2);
+/// *map.get_mut(&"c") += 3;
+/// let _ = map.remove(&"a");
+///
+/// let mut mirror = DefaultHashMap::new();
+/// mirror.replay(map.listener_mut().drain());
+///
+/// assert_eq!(map.as_map(), &mirror);
+/// assert!(map.listener().is_empty());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Journal<K, V> {
+    _ops: Vec<JournalOp<K, V>>,
+}
+
+impl<K, V> Journal<K, V> {
+    /// Creates an empty journal.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { _ops: Vec::new() }
+    }
+
+    /// Removes every recorded operation from the journal.
+    #[inline]
+    pub fn clear(&mut self) {
+        self._ops.clear();
+    }
+
+    /// Takes the recorded operations out of the journal, oldest first.
+    pub fn drain(&mut self) -> std::vec::Drain<'_, JournalOp<K, V>> {
+        self._ops.drain(..)
+    }
+
+    /// Consumes the journal and returns the recorded operations, oldest first.
+    #[inline]
+    pub fn into_ops(self) -> Vec<JournalOp<K, V>> {
+        self._ops
+    }
+
+    /// Returns `true` if no operations are recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._ops.is_empty()
+    }
+
+    /// Returns the number of recorded operations.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._ops.len()
+    }
+
+    /// Returns the recorded operations, oldest first.
+    #[inline]
+    pub fn ops(&self) -> &[JournalOp<K, V>] {
+        &self._ops
+    }
+}
+
+impl<K, V> Default for Journal<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Listener<K, V> for Journal<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn on_insert(&mut self, key: &K, value: &V) {
+        self._ops
+            .push(JournalOp::Insert(key.clone(), value.clone()));
+    }
+
+    fn on_overwrite(&mut self, key: &K, _old: &V, new: &V) {
+        self._ops.push(JournalOp::Insert(key.clone(), new.clone()));
+    }
+
+    fn on_remove(&mut self, key: &K, _value: &V) {
+        self._ops.push(JournalOp::Remove(key.clone()));
+    }
+
+    fn on_default(&mut self, key: &K, value: &V) {
+        self._ops
+            .push(JournalOp::Insert(key.clone(), value.clone()));
+    }
+}
+
+/// An [`ObservedHashMap`] that records its mutations in a [`Journal`].
+pub type JournaledHashMap<K, V, S = RandomState> = ObservedHashMap<K, V, Journal<K, V>, S>;
+
+impl<K, V> ObservedHashMap<K, V, Journal<K, V>, RandomState>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    /// Creates an empty map with an empty [`Journal`].
+    #[must_use]
+    pub fn journaled() -> Self {
+        Self::new(Journal::new())
+    }
+}
+
+/// Replaying journals.
+impl<K, V, S> DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Applies the operations of a [`Journal`] to the map, in order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, JournalOp};
+    ///
+    /// let mut map = DefaultHashMap::new();
+    /// map.replay([JournalOp::Insert(1, 10), JournalOp::Insert(2, 20), JournalOp::Remove(1)]);
+    ///
+    /// assert!(!map.contains_key(&1));
+    /// assert_eq!(&20, map.get(&2));
+    /// ```
+    pub fn replay<I>(&mut self, ops: I)
+    where
+        I: IntoIterator<Item = JournalOp<K, V>>,
+    {
+        for op in ops {
+            match op {
+                JournalOp::Insert(key, value) => {
+                    self.insert(key, value);
+                }
+                JournalOp::Remove(key) => {
+                    let _ = self.remove(&key);
+                }
+            }
+        }
+    }
+}
@@ -3,8 +3,148 @@
 //!
 //! This behaviour does require that the type of the value does have the [`Default`] implemented.
 
+mod accumulator;
+mod adjacency;
+mod aggregator;
+#[cfg(feature = "arrow")]
+mod arrow_map;
+mod atomic;
+mod auto_created;
+mod bimap;
+mod builder;
+mod checkpoint;
+mod codec;
+mod count_min;
+mod counter;
+mod cow;
+mod crdt;
+mod deep_clone;
 mod default_btree;
 mod default_hashmap;
+mod dense;
+mod diff;
+mod distribution;
+mod domain;
+mod enum_map;
+mod env;
+mod error;
+#[cfg(feature = "ordered-float")]
+mod float_keys;
+mod flushing;
+mod grouping;
+mod guard;
+mod hashers;
+mod heavy_hitters;
+mod histogram;
+#[cfg(feature = "im")]
+mod im_map;
+mod interned;
+mod journal;
+mod lru;
+mod memo;
+#[cfg(feature = "metrics")]
+mod metrics_listener;
+#[cfg(feature = "mmap")]
+mod mmap_map;
+mod multimap;
+mod normalized;
+mod observed;
+mod pivot;
+#[cfg(feature = "polars")]
+mod polars_map;
+mod policy;
+mod ranked;
+mod seeded;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod shared_values;
+#[cfg(feature = "sled")]
+mod sled_map;
+mod small_map;
+mod sorted;
+mod sparse;
+mod spatial;
+mod stable;
+mod stats;
+#[cfg(feature = "arc-swap")]
+mod swap_map;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod time_buckets;
+mod transaction;
+mod trie;
+mod versioned;
+mod view;
+mod windowed;
 
+pub use accumulator::{Accumulator, AccumulatorShard};
+pub use adjacency::AdjacencyMap;
+pub use aggregator::{Aggregator, AggregatorSender};
+#[cfg(feature = "arrow")]
+pub use arrow_map::ArrowValue;
+pub use auto_created::{AutoCreatedKeys, TrackedHashMap};
+pub use bimap::DefaultBiMap;
+pub use builder::DefaultHashMapBuilder;
+pub use checkpoint::{Change, ChangeTracker, CheckpointedHashMap};
+pub use codec::Codec;
+pub use count_min::CountMinSketch;
+pub use counter::Counter;
+pub use cow::CowHashMap;
+pub use crdt::{GCounterMap, PNCounterMap};
+pub use deep_clone::DeepClone;
 pub use default_btree::DefaultBTreeMap;
 pub use default_hashmap::DefaultHashMap;
+pub use dense::{DenseDefaultMap, DenseKey};
+pub use diff::MapDiff;
+pub use domain::DomainHashMap;
+pub use enum_map::{EnumDefaultMap, EnumKey};
+pub use error::{DuplicateKeyError, Error};
+#[cfg(feature = "ordered-float")]
+pub use float_keys::FloatKey;
+pub use flushing::FlushingMap;
+pub use guard::ValueGuard;
+#[cfg(feature = "ahash")]
+pub use hashers::DefaultAHashMap;
+#[cfg(feature = "fxhash")]
+pub use hashers::DefaultFxHashMap;
+#[cfg(feature = "nohash")]
+pub use hashers::DefaultIntMap;
+pub use heavy_hitters::HeavyHitters;
+pub use histogram::Histogram;
+#[cfg(feature = "im")]
+pub use im_map::ImDefaultHashMap;
+pub use interned::{InternedHashMap, Interner};
+pub use journal::{Journal, JournalOp, JournaledHashMap};
+pub use lru::{EvictionReason, LruDefaultMap};
+pub use memo::MemoMap;
+#[cfg(feature = "metrics")]
+pub use metrics_listener::MetricsListener;
+#[cfg(feature = "mmap")]
+pub use mmap_map::{MmapDefaultMap, MmapMapBuilder};
+pub use multimap::DefaultMultiMap;
+pub use normalized::{Lowercase, NormalizedHashMap, Normalizer, Trim};
+pub use observed::{Listener, ObservedGuard, ObservedHashMap};
+pub use pivot::Pivot;
+#[cfg(feature = "polars")]
+pub use polars_map::PolarsValue;
+pub use policy::{Policy, ShrinkPolicy};
+pub use ranked::{RankedDefaultMap, RankedIter};
+pub use seeded::SeededState;
+#[cfg(feature = "serde")]
+pub use serde_impls::ConflictPolicy;
+pub use shared_values::SharedValueMap;
+#[cfg(feature = "sled")]
+pub use sled_map::PersistentDefaultMap;
+pub use small_map::SmallDefaultMap;
+pub use sorted::{CaseInsensitive, DefaultSortedMap, Descending, Natural, SortBy};
+pub use spatial::SpatialDefaultGrid;
+pub use stable::DefaultStableMap;
+pub use stats::{DefaultStatsMap, Stats};
+#[cfg(feature = "arc-swap")]
+pub use swap_map::SwapDefaultMap;
+pub use time_buckets::TimeBuckets;
+pub use transaction::Transaction;
+pub use trie::DefaultTrieMap;
+pub use versioned::{Snapshot, VersionedHashMap};
+pub use view::DefaultView;
+pub use windowed::WindowedCounter;
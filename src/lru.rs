@@ -0,0 +1,275 @@
+#![deny(missing_docs)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::mem;
+use std::time::{Duration, Instant};
+
+/// Why an entry was evicted from a [`LruDefaultMap`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EvictionReason {
+    /// The map was full and the entry was the least recently used one.
+    Capacity,
+    /// The entry was not used for longer than the time to live of the map.
+    Expired,
+}
+
+/// A stored value together with when it was last used.
+#[derive(Debug)]
+struct Slot<V> {
+    value: V,
+    tick: u64,
+    used: Instant,
+}
+
+/// A default map that holds a bounded number of keys and evicts the least recently used key when
+/// a new key does not fit. Evicted entries are handed to a listener together with an
+/// [`EvictionReason`], so they can be persisted or counted before they disappear.
+///
+/// With [`LruDefaultMap::ttl`] entries that have not been used for the time to live are evicted as
+/// well. Expired entries are evicted whenever the map is read or changed through `&mut self`, and
+/// by [`LruDefaultMap::evict_expired`], which can be called periodically. Entries that are still
+/// in the map when it is dropped, or that are removed explicitly, are not handed to the listener.
+///
+/// # Example
+/// ```
+/// use defaultdict::{EvictionReason, LruDefaultMap};
+///
+/// let mut evicted = Vec::new();
+/// {
+///     let mut sessions = LruDefaultMap::new(2, |key, value, reason| {
+///         evicted.push((key, value, reason));
+///     });
+///     *sessions.get_mut(&"a") += 1;
+///     *sessions.get_mut(&"b") += 1;
+///     *sessions.get_mut(&"a") += 1;
+///     *sessions.get_mut(&"c") += 1;
+///
+///     assert_eq!(&2, sessions.get(&"a"));
+///     assert_eq!(&0, sessions.get(&"b"));
+/// }
+///
+/// assert_eq!(vec![("b", 1, EvictionReason::Capacity)], evicted);
+/// ```
+pub struct LruDefaultMap<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+    F: FnMut(K, V, EvictionReason),
+{
+    _entries: HashMap<K, Slot<V>>,
+    /// The keys ordered from least to most recently used.
+    _order: BTreeMap<u64, K>,
+    _default: V,
+    _capacity: usize,
+    _ttl: Option<Duration>,
+    _listener: F,
+    _tick: u64,
+}
+
+impl<K, V, F> LruDefaultMap<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+    F: FnMut(K, V, EvictionReason),
+{
+    /// Creates an empty map that holds at most `capacity` keys and hands evicted entries to the
+    /// listener.
+    ///
+    /// # Panics
+    /// Panics if the capacity is zero.
+    #[must_use]
+    pub fn new(capacity: usize, listener: F) -> Self {
+        assert!(capacity > 0, "the capacity must be at least one");
+        Self {
+            _entries: HashMap::new(),
+            _order: BTreeMap::new(),
+            _default: V::default(),
+            _capacity: capacity,
+            _ttl: None,
+            _listener: listener,
+            _tick: 0,
+        }
+    }
+
+    /// Evicts entries that have not been used for `ttl`.
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self._ttl = Some(ttl);
+        self
+    }
+
+    /// Returns the maximum number of keys the map holds.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self._capacity
+    }
+
+    /// Returns `true` if the map holds the key. This does not mark the key as used, and an
+    /// expired key is held until it is evicted.
+    #[must_use]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._entries.contains_key(key)
+    }
+
+    /// Evicts every entry that has not been used for the time to live.
+    #[inline]
+    pub fn evict_expired(&mut self) {
+        self.evict_expired_at(Instant::now());
+    }
+
+    /// Evicts every entry that has not been used for the time to live as of `now`. Nothing is
+    /// evicted if the map has no time to live.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use defaultdict::{EvictionReason, LruDefaultMap};
+    ///
+    /// let mut expired = Vec::new();
+    /// let mut cache = LruDefaultMap::new(10, |key, _, reason| expired.push((key, reason)))
+    ///     .ttl(Duration::from_secs(60));
+    /// cache.insert("a", 1);
+    ///
+    /// cache.evict_expired_at(Instant::now() + Duration::from_secs(61));
+    ///
+    /// assert!(cache.is_empty());
+    /// drop(cache);
+    /// assert_eq!(vec![("a", EvictionReason::Expired)], expired);
+    /// ```
+    pub fn evict_expired_at(&mut self, now: Instant) {
+        let Some(ttl) = self._ttl else {
+            return;
+        };
+        while let Some(entry) = self._order.first_entry() {
+            let used = self._entries[entry.get()].used;
+            if now.saturating_duration_since(used) < ttl {
+                break;
+            }
+            let key = entry.remove();
+            let slot = self
+                ._entries
+                .remove(&key)
+                .expect("every ordered key is stored");
+            (self._listener)(key, slot.value, EvictionReason::Expired);
+        }
+    }
+
+    /// Returns a reference to the value of the key and marks the key as most recently used, or
+    /// returns the default value if the key is missing. A missing key is not inserted.
+    pub fn get(&mut self, key: &K) -> &V {
+        self.evict_expired();
+        self.touch(key);
+        self._entries
+            .get(key)
+            .map_or(&self._default, |slot| &slot.value)
+    }
+
+    /// Returns a mutable reference to the value of the key and marks the key as most recently
+    /// used. A missing key is inserted with the default value, which evicts the least recently
+    /// used key if the map is full.
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        self.evict_expired();
+        if self._entries.contains_key(key) {
+            self.touch(key);
+        } else {
+            self.insert_new(key.clone(), V::default());
+        }
+        &mut self
+            ._entries
+            .get_mut(key)
+            .expect("the key was just stored")
+            .value
+    }
+
+    /// Inserts a key value pair into the map and marks the key as most recently used, returning
+    /// the previous value if the key was present. A new key evicts the least recently used key if
+    /// the map is full.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.evict_expired();
+        if let Some(slot) = self._entries.get_mut(&key) {
+            let old = mem::replace(&mut slot.value, value);
+            self.touch(&key);
+            return Some(old);
+        }
+        self.insert_new(key, value);
+        None
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._entries.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs from the least to the most recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self._order
+            .values()
+            .map(|key| (key, &self._entries[key].value))
+    }
+
+    /// Returns the number of keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._entries.len()
+    }
+
+    /// Returns a reference to the value of the key without marking it as used, or the default
+    /// value if the key is missing.
+    #[must_use]
+    pub fn peek(&self, key: &K) -> &V {
+        self._entries
+            .get(key)
+            .map_or(&self._default, |slot| &slot.value)
+    }
+
+    /// Removes the key and returns its value, or the default value if the key was missing. The
+    /// removed entry is not handed to the listener.
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        match self._entries.remove(key) {
+            Some(slot) => {
+                let _ = self._order.remove(&slot.tick);
+                slot.value
+            }
+            None => V::default(),
+        }
+    }
+
+    /// Stores a key that is not in the map, evicting the least recently used key if the map is
+    /// full.
+    fn insert_new(&mut self, key: K, value: V) {
+        if self._entries.len() >= self._capacity {
+            let (_, evicted) = self._order.pop_first().expect("the map is full");
+            let slot = self
+                ._entries
+                .remove(&evicted)
+                .expect("every ordered key is stored");
+            (self._listener)(evicted, slot.value, EvictionReason::Capacity);
+        }
+        let slot = Slot {
+            value,
+            tick: self._tick,
+            used: Instant::now(),
+        };
+        self._order.insert(self._tick, key.clone());
+        self._entries.insert(key, slot);
+        self._tick += 1;
+    }
+
+    /// Marks the key as most recently used if it is in the map.
+    fn touch(&mut self, key: &K) {
+        if let Some(slot) = self._entries.get_mut(key) {
+            let key = self
+                ._order
+                .remove(&slot.tick)
+                .expect("every stored key is ordered");
+            slot.tick = self._tick;
+            slot.used = Instant::now();
+            self._order.insert(self._tick, key);
+            self._tick += 1;
+        }
+    }
+}
@@ -0,0 +1,269 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::Error;
+
+/// The loader that computes missing values of a [`MemoMap`].
+type Loader<K, V, E> = Box<dyn FnMut(&K) -> Result<V, E>>;
+
+/// A read-through cache that computes the value of a missing key with a fallible loader.
+///
+/// The loader runs at most once per key: a successful result is cached and returned by every
+/// later read, an error is passed to the caller and nothing is cached, so the next read of that
+/// key tries again. Because [`MemoMap::get`] borrows the map mutably, a key can never be loaded
+/// twice at the same time.
+///
+/// The loader can fail with any error type, which defaults to the crate's [`Error`]. Loaders that
+/// use it report their failures as [`Error::Loader`].
+///
+/// # Example
+/// ```
+/// use defaultdict::MemoMap;
+///
+/// let mut lengths: MemoMap<String, usize, String> = MemoMap::new(|key: &String| {
+///     if key.is_empty() {
+///         Err(String::from("empty key"))
+///     } else {
+///         Ok(key.len())
+///     }
+/// });
+///
+/// assert_eq!(Ok(&5), lengths.get(&String::from("hello")));
+/// assert_eq!(Err(String::from("empty key")), lengths.get(&String::new()));
+/// assert_eq!(1, lengths.len());
+/// ```
+///
+/// With the default error type:
+/// ```
+/// use defaultdict::{Error, MemoMap};
+///
+/// let mut ports: MemoMap<&str, u16> = MemoMap::new(|key: &&str| {
+///     key.parse()
+///         .map_err(|error| Error::Loader(format!("{key}: {error}")))
+/// });
+///
+/// assert_eq!(Ok(&80), ports.get(&"80"));
+/// assert!(matches!(ports.get(&"http"), Err(Error::Loader(_))));
+/// ```
+pub struct MemoMap<K, V, E = Error, S = RandomState>
+where
+    K: Eq + Hash,
+{
+    _cache: HashMap<K, V, S>,
+    _loader: Loader<K, V, E>,
+}
+
+impl<K, V, E> MemoMap<K, V, E, RandomState>
+where
+    K: Eq + Hash,
+{
+    /// Creates an empty [`MemoMap`] that computes missing values with the given loader.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new<F>(loader: F) -> Self
+    where
+        F: FnMut(&K) -> Result<V, E> + 'static,
+    {
+        Self::with_hasher(loader, RandomState::new())
+    }
+}
+
+impl<K, V, E, S> MemoMap<K, V, E, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Creates an empty [`MemoMap`] which will use the given hash builder to hash keys.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// use defaultdict::MemoMap;
+    ///
+    /// let map: MemoMap<u64, u64, ()> =
+    ///     MemoMap::with_hasher(|key| Ok(key * 2), RandomState::new());
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn with_hasher<F>(loader: F, hash_builder: S) -> Self
+    where
+        F: FnMut(&K) -> Result<V, E> + 'static,
+    {
+        Self {
+            _cache: HashMap::with_hasher(hash_builder),
+            _loader: Box::new(loader),
+        }
+    }
+
+    /// Removes every cached value, so every key is loaded again on its next read.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    /// let _ = map.get(&1);
+    /// map.clear();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self._cache.clear();
+    }
+
+    /// Returns `true` if the value of the key passed in has been cached.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    /// let _ = map.get(&1);
+    ///
+    /// assert!(map.contains_key(&1));
+    /// assert!(!map.contains_key(&2));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._cache.contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key passed in, running the loader if the key has
+    /// not been cached yet. Errors of the loader are returned as is and are not cached.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut calls = 0;
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(move |key| {
+    ///     calls += 1;
+    ///     assert_eq!(1, calls);
+    ///     Ok(key * 2)
+    /// });
+    ///
+    /// assert_eq!(Ok(&2), map.get(&1));
+    /// assert_eq!(Ok(&2), map.get(&1));
+    /// ```
+    pub fn get(&mut self, key: &K) -> Result<&V, E>
+    where
+        K: Clone,
+    {
+        if !self._cache.contains_key(key) {
+            let value = (self._loader)(key)?;
+            return Ok(self
+                ._cache
+                .entry(key.clone())
+                .insert_entry(value)
+                .into_mut());
+        }
+        Ok(&self._cache[key])
+    }
+
+    /// Returns a reference to the cached value of the key passed in, without running the loader.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    ///
+    /// assert_eq!(None, map.get_cached(&1));
+    /// let _ = map.get(&1);
+    /// assert_eq!(Some(&2), map.get_cached(&1));
+    /// ```
+    #[must_use]
+    pub fn get_cached(&self, key: &K) -> Option<&V> {
+        self._cache.get(key)
+    }
+
+    /// Caches a value for the key without running the loader, returning the value that was
+    /// cached before.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    /// map.insert(1, 10);
+    ///
+    /// assert_eq!(Ok(&10), map.get(&1));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self._cache.insert(key, value)
+    }
+
+    /// Removes the cached value of the key passed in, so it is loaded again on its next read.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    /// let _ = map.get(&1);
+    ///
+    /// assert_eq!(Some(2), map.invalidate(&1));
+    /// assert_eq!(None, map.invalidate(&1));
+    /// ```
+    pub fn invalidate(&mut self, key: &K) -> Option<V> {
+        self._cache.remove(key)
+    }
+
+    /// Returns `true` if no values have been cached.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._cache.is_empty()
+    }
+
+    /// Returns the number of cached values.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::MemoMap;
+    ///
+    /// let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|key| Ok(key * 2));
+    /// let _ = map.get(&1);
+    /// let _ = map.get(&2);
+    ///
+    /// assert_eq!(2, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._cache.len()
+    }
+}
+
+impl<K, V, E, S> fmt::Debug for MemoMap<K, V, E, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoMap")
+            .field("_cache", &self._cache)
+            .finish_non_exhaustive()
+    }
+}
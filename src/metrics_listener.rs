@@ -0,0 +1,88 @@
+#![deny(missing_docs)]
+
+use metrics::{counter, gauge};
+
+use crate::Listener;
+
+/// A [`Listener`] that publishes the activity of an [`ObservedHashMap`] through the [`metrics`]
+/// facade, so it shows up in whatever exporter the application installed. This requires the
+/// `metrics` feature.
+///
+/// Every metric carries a `map` label with the name passed to [`MetricsListener::new`]:
+///
+/// | Metric                         | Type    | Description                                   |
+/// |--------------------------------|---------|-----------------------------------------------|
+/// | `defaultdict_entries`          | gauge   | Keys added minus keys removed through the map |
+/// | `defaultdict_inserts_total`    | counter | Inserts of keys that were not present         |
+/// | `defaultdict_overwrites_total` | counter | Inserts that replaced an existing value       |
+/// | `defaultdict_removals_total`   | counter | Keys removed from the map                     |
+/// | `defaultdict_defaults_total`   | counter | Default values materialized for missing keys  |
+/// | `defaultdict_reads_total`      | counter | Reads through [`ObservedHashMap::get`]        |
+/// | `defaultdict_misses_total`     | counter | Reads that returned the default value         |
+///
+/// The entry gauge only tracks changes made through the map, entries that were already present
+/// in a map passed to [`ObservedHashMap::from_map`] are not counted.
+///
+/// [`ObservedHashMap`]: crate::ObservedHashMap
+/// [`ObservedHashMap::get`]: crate::ObservedHashMap::get
+/// [`ObservedHashMap::from_map`]: crate::ObservedHashMap::from_map
+///
+/// # Example
+/// ```
+/// use defaultdict::{MetricsListener, ObservedHashMap};
+///
+/// let mut map: ObservedHashMap<&str, u64, _> =
+///     ObservedHashMap::new(MetricsListener::new("word_counts"));
+///
+/// *map.get_mut(&"hello") += 1;
+/// assert_eq!(&0, map.get(&"world"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MetricsListener {
+    _name: String,
+}
+
+impl MetricsListener {
+    /// Creates a listener that labels its metrics with the given map name.
+    #[must_use]
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self { _name: name.into() }
+    }
+
+    /// Returns the name the metrics are labeled with.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self._name
+    }
+}
+
+impl<K, V> Listener<K, V> for MetricsListener {
+    fn on_insert(&mut self, _key: &K, _value: &V) {
+        counter!("defaultdict_inserts_total", "map" => self._name.clone()).increment(1);
+        gauge!("defaultdict_entries", "map" => self._name.clone()).increment(1.0);
+    }
+
+    fn on_overwrite(&mut self, _key: &K, _old: &V, _new: &V) {
+        counter!("defaultdict_overwrites_total", "map" => self._name.clone()).increment(1);
+    }
+
+    fn on_remove(&mut self, _key: &K, _value: &V) {
+        counter!("defaultdict_removals_total", "map" => self._name.clone()).increment(1);
+        gauge!("defaultdict_entries", "map" => self._name.clone()).decrement(1.0);
+    }
+
+    fn on_default(&mut self, _key: &K, _value: &V) {
+        counter!("defaultdict_defaults_total", "map" => self._name.clone()).increment(1);
+        gauge!("defaultdict_entries", "map" => self._name.clone()).increment(1.0);
+    }
+
+    fn on_read(&self, _key: &K, hit: bool) {
+        counter!("defaultdict_reads_total", "map" => self._name.clone()).increment(1);
+        if !hit {
+            counter!("defaultdict_misses_total", "map" => self._name.clone()).increment(1);
+        }
+    }
+}
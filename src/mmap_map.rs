@@ -0,0 +1,308 @@
+#![deny(missing_docs)]
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Result, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::Codec;
+
+/// Identifies files written by [`MmapMapBuilder`], including the version of the format.
+const MAGIC: &[u8; 8] = b"DDMMAP01";
+
+/// Size of the fixed header: the magic, the number of entries and the length of the default.
+const HEADER_LEN: usize = 24;
+
+/// Collects key-value pairs and writes them to a compact file that can be opened as a
+/// [`MmapDefaultMap`]. This requires the `mmap` feature.
+///
+/// The pairs are sorted by their encoded keys, so the file can be searched without reading it
+/// completely. Inserting a key twice keeps the last value.
+///
+/// # Example
+/// ```
+/// use defaultdict::{defaulthashmap, DefaultHashMap, MmapDefaultMap, MmapMapBuilder};
+///
+/// let map: DefaultHashMap<u32, String> = defaulthashmap!(
+///     (1, String::from("one")),
+///     (2, String::from("two")),
+/// );
+/// let path = std::env::temp_dir().join("defaultdict_mmap_builder_doc");
+///
+/// let builder: MmapMapBuilder<u32, String> = map.into_iter().collect();
+/// builder.default_value(String::from("none")).write(&path)?;
+///
+/// let table: MmapDefaultMap<u32, String> = MmapDefaultMap::open(&path)?;
+///
+/// assert_eq!("two", table.get(&2)?);
+/// assert_eq!("none", table.get(&3)?);
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct MmapMapBuilder<K, V>
+where
+    K: Codec,
+    V: Codec + Default,
+{
+    _entries: BTreeMap<Vec<u8>, Vec<u8>>,
+    _default: V,
+    _key: PhantomData<K>,
+}
+
+impl<K, V> MmapMapBuilder<K, V>
+where
+    K: Codec,
+    V: Codec + Default,
+{
+    /// Creates an empty builder that uses the [`Default`] value of `V` for missing keys.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _entries: BTreeMap::new(),
+            _default: V::default(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Sets the value that the written map returns for missing keys.
+    #[must_use]
+    pub fn default_value(mut self, value: V) -> Self {
+        self._default = value;
+        self
+    }
+
+    /// Adds a key value pair to the map that will be written.
+    pub fn insert(&mut self, key: &K, value: &V) {
+        self._entries.insert(key.encode(), value.encode());
+    }
+
+    /// Returns the number of pairs that will be written.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._entries.len()
+    }
+
+    /// Returns `true` if no pairs have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._entries.is_empty()
+    }
+
+    /// Writes the map to the given path, replacing the file if it exists.
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let default = self._default.encode();
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&(self._entries.len() as u64).to_le_bytes())?;
+        file.write_all(&(default.len() as u64).to_le_bytes())?;
+        file.write_all(&default)?;
+
+        let mut offset = HEADER_LEN + default.len() + self._entries.len() * 8;
+        for (key, value) in &self._entries {
+            file.write_all(&(offset as u64).to_le_bytes())?;
+            offset += 16 + key.len() + value.len();
+        }
+        for (key, value) in &self._entries {
+            file.write_all(&(key.len() as u64).to_le_bytes())?;
+            file.write_all(&(value.len() as u64).to_le_bytes())?;
+            file.write_all(key)?;
+            file.write_all(value)?;
+        }
+        file.flush()
+    }
+}
+
+impl<K, V> Default for MmapMapBuilder<K, V>
+where
+    K: Codec,
+    V: Codec + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Extend<(K, V)> for MmapMapBuilder<K, V>
+where
+    K: Codec,
+    V: Codec + Default,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(&key, &value);
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for MmapMapBuilder<K, V>
+where
+    K: Codec,
+    V: Codec + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut builder = Self::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+/// A read-only default map that answers lookups straight from a memory-mapped file written by
+/// [`MmapMapBuilder`]. This requires the `mmap` feature.
+///
+/// Only the pages that are touched by a lookup are loaded, which makes this suited for large
+/// static lookup tables. Lookups are a binary search over the encoded keys and return owned
+/// values, falling back to the default value that was written with the file.
+///
+/// The file must not be modified while it is mapped.
+#[derive(Debug)]
+pub struct MmapDefaultMap<K, V>
+where
+    K: Codec,
+    V: Codec,
+{
+    _mmap: Mmap,
+    _index_start: usize,
+    _len: usize,
+    _default: V,
+    _key: PhantomData<K>,
+}
+
+impl<K, V> MmapDefaultMap<K, V>
+where
+    K: Codec,
+    V: Codec + Clone,
+{
+    /// Memory-maps the file at the given path. The header and the index are validated, the
+    /// entries themselves are checked when they are read.
+    pub fn open<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only and the file is documented to stay unmodified while it
+        // is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        if mmap.len() < HEADER_LEN || &mmap[..8] != MAGIC {
+            return Err(invalid("not a defaultdict mmap file"));
+        }
+        let len = read_u64(&mmap, 8)?;
+        let default_len = read_u64(&mmap, 16)?;
+        let index_start = HEADER_LEN
+            .checked_add(default_len)
+            .ok_or_else(|| invalid("default value out of bounds"))?;
+        let index_fits = len
+            .checked_mul(8)
+            .and_then(|index_len| index_start.checked_add(index_len))
+            .is_some_and(|index_end| index_end <= mmap.len());
+        if !index_fits {
+            return Err(invalid("index out of bounds"));
+        }
+        let default = V::decode(&mmap[HEADER_LEN..index_start])
+            .ok_or_else(|| invalid("default value could not be decoded"))?;
+
+        Ok(Self {
+            _mmap: mmap,
+            _index_start: index_start,
+            _len: len,
+            _default: default,
+            _key: PhantomData,
+        })
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    pub fn contains_key(&self, key: &K) -> Result<bool> {
+        Ok(self.find(&key.encode())?.is_some())
+    }
+
+    /// Returns the default value that is returned for missing keys.
+    #[inline]
+    pub fn default_value(&self) -> &V {
+        &self._default
+    }
+
+    /// Returns the value of the key passed in, or the default value if the key is not present.
+    pub fn get(&self, key: &K) -> Result<V> {
+        match self.find(&key.encode())? {
+            Some(bytes) => V::decode(bytes).ok_or_else(|| invalid("value could not be decoded")),
+            None => Ok(self._default.clone()),
+        }
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._len == 0
+    }
+
+    /// Returns an iterator over the decoded key-value pairs, in the byte order of the encoded
+    /// keys.
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K, V)>> + '_ {
+        (0..self._len).map(move |index| {
+            let (key, value) = self.entry(index)?;
+            let key = K::decode(key).ok_or_else(|| invalid("key could not be decoded"))?;
+            let value = V::decode(value).ok_or_else(|| invalid("value could not be decoded"))?;
+            Ok((key, value))
+        })
+    }
+
+    /// Returns the length of the keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._len
+    }
+
+    /// Returns the encoded key and value of the entry at the given position of the index.
+    fn entry(&self, index: usize) -> Result<(&[u8], &[u8])> {
+        let offset = read_u64(&self._mmap, self._index_start + index * 8)?;
+        let key_len = read_u64(&self._mmap, offset)?;
+        let value_len = read_u64(&self._mmap, offset.saturating_add(8))?;
+        let key_start = offset + 16;
+        let value_start = key_start.saturating_add(key_len);
+        let key = self
+            ._mmap
+            .get(key_start..value_start)
+            .ok_or_else(|| invalid("key out of bounds"))?;
+        let value = self
+            ._mmap
+            .get(value_start..value_start.saturating_add(value_len))
+            .ok_or_else(|| invalid("value out of bounds"))?;
+        Ok((key, value))
+    }
+
+    /// Binary searches the index for the encoded key.
+    fn find(&self, key: &[u8]) -> Result<Option<&[u8]>> {
+        let (mut low, mut high) = (0, self._len);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let (candidate, value) = self.entry(middle)?;
+            match candidate.cmp(key) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Ok(Some(value)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Reads a little-endian length or offset at the given position.
+fn read_u64(bytes: &[u8], at: usize) -> Result<usize> {
+    let slice = bytes
+        .get(at..at.saturating_add(8))
+        .ok_or_else(|| invalid("offset out of bounds"))?;
+    let word: [u8; 8] = slice.try_into().expect("the slice holds eight bytes");
+    usize::try_from(u64::from_le_bytes(word)).map_err(|_| invalid("offset out of bounds"))
+}
+
+/// Creates the error returned for files that are not valid.
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,85 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "http")]
+use std::hash::BuildHasher;
+
+use crate::DefaultHashMap;
+
+/// A [`DefaultHashMap`] that maps every key to a list of values, like a python
+/// `defaultdict(list)`. Missing keys read as an empty list.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultMultiMap;
+///
+/// let mut headers = DefaultMultiMap::new();
+/// headers.get_mut(&"accept").push("text/html");
+/// headers.get_mut(&"accept").push("application/json");
+///
+/// assert_eq!(2, headers.get(&"accept").len());
+/// assert!(headers.get(&"cookie").is_empty());
+/// ```
+pub type DefaultMultiMap<K, V, S = RandomState> = DefaultHashMap<K, Vec<V>, S>;
+
+/// Parsing and writing query strings and form bodies. This requires the `http` feature.
+#[cfg(feature = "http")]
+impl<S> DefaultHashMap<String, Vec<String>, S>
+where
+    S: BuildHasher,
+{
+    /// Parses an `application/x-www-form-urlencoded` string, such as the query string of a URL,
+    /// collecting the values of every parameter in order. Names and values are percent-decoded
+    /// and `+` is read as a space.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultMultiMap;
+    ///
+    /// let query: DefaultMultiMap<String, String> =
+    ///     DefaultMultiMap::from_query_str("a=1&a=2&b=hello+world&c=%C3%A9");
+    ///
+    /// assert_eq!(&vec!["1", "2"], query.get("a"));
+    /// assert_eq!(&vec!["hello world"], query.get("b"));
+    /// assert_eq!(&vec!["é"], query.get("c"));
+    /// assert!(query.get("d").is_empty());
+    /// ```
+    pub fn from_query_str(query: &str) -> Self
+    where
+        S: Default,
+    {
+        let mut map = Self::with_hasher(S::default());
+        for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+            map.entry(name.into_owned())
+                .or_default()
+                .push(value.into_owned());
+        }
+        map
+    }
+
+    /// Writes the map as an `application/x-www-form-urlencoded` string, percent-encoding names
+    /// and values. Parameters are written in the order of their names, and the values of a
+    /// parameter in the order of its list.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultMultiMap;
+    ///
+    /// let mut query = DefaultMultiMap::<String, String>::new();
+    /// query.get_mut(&"q".to_string()).push("rust maps".to_string());
+    /// query.get_mut(&"tag".to_string()).extend(["a&b".to_string(), "c".to_string()]);
+    ///
+    /// assert_eq!("q=rust+maps&tag=a%26b&tag=c", query.to_query_string());
+    /// ```
+    pub fn to_query_string(&self) -> String {
+        let mut names: Vec<&String> = self.keys().collect();
+        names.sort();
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        for name in names {
+            for value in self.get(name) {
+                serializer.append_pair(name, value);
+            }
+        }
+        serializer.finish()
+    }
+}
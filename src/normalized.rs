@@ -0,0 +1,294 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::DefaultHashMap;
+
+/// Turns a borrowed key `Q` into the normalized key `K` that is stored in a
+/// [`NormalizedHashMap`].
+///
+/// Implemented for every `Fn(&Q) -> K`, so any normalization can be written as a closure, and
+/// for the [`Lowercase`] and [`Trim`] string normalizers.
+pub trait Normalizer<Q: ?Sized, K> {
+    /// Returns the normalized form of the key.
+    fn normalize(&self, key: &Q) -> K;
+}
+
+impl<F, Q, K> Normalizer<Q, K> for F
+where
+    F: Fn(&Q) -> K,
+    Q: ?Sized,
+{
+    fn normalize(&self, key: &Q) -> K {
+        self(key)
+    }
+}
+
+/// Normalizes string keys to their lowercase form, which makes lookups case-insensitive.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Lowercase;
+
+impl Normalizer<str, String> for Lowercase {
+    fn normalize(&self, key: &str) -> String {
+        key.to_lowercase()
+    }
+}
+
+impl Normalizer<String, String> for Lowercase {
+    fn normalize(&self, key: &String) -> String {
+        key.to_lowercase()
+    }
+}
+
+/// Normalizes string keys by removing leading and trailing whitespace.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Trim;
+
+impl Normalizer<str, String> for Trim {
+    fn normalize(&self, key: &str) -> String {
+        key.trim().to_owned()
+    }
+}
+
+impl Normalizer<String, String> for Trim {
+    fn normalize(&self, key: &String) -> String {
+        key.trim().to_owned()
+    }
+}
+
+/// A [`DefaultHashMap`] that normalizes every key with a [`Normalizer`] before it is stored or
+/// looked up, so different spellings of a key share one entry.
+///
+/// The keys are stored in their normalized form.
+///
+/// # Example
+/// ```
+/// use defaultdict::NormalizedHashMap;
+///
+/// let mut headers = NormalizedHashMap::new(|key: &str| key.trim().to_ascii_lowercase());
+/// headers.insert("Content-Type", "text/plain");
+///
+/// assert_eq!(&"text/plain", headers.get("content-type"));
+/// assert_eq!(&"text/plain", headers.get(" CONTENT-TYPE "));
+/// assert_eq!(&"", headers.get("accept"));
+/// ```
+pub struct NormalizedHashMap<K, V, N, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _inner: DefaultHashMap<K, V, S>,
+    _normalizer: N,
+}
+
+impl<K, V, N> NormalizedHashMap<K, V, N, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`NormalizedHashMap`] that normalizes keys with the given normalizer.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let map: NormalizedHashMap<String, i8, Lowercase> = NormalizedHashMap::new(Lowercase);
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new(normalizer: N) -> Self {
+        Self {
+            _inner: DefaultHashMap::new(),
+            _normalizer: normalizer,
+        }
+    }
+}
+
+impl<K, V, N, S> NormalizedHashMap<K, V, N, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Returns a reference to the wrapped [`DefaultHashMap`], which holds the normalized keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Lowercase);
+    /// map.insert("KEY", 1);
+    ///
+    /// let keys: Vec<&String> = map.as_map().keys().collect();
+    ///
+    /// assert_eq!(vec!["key"], keys);
+    /// ```
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<K, V, S> {
+        &self._inner
+    }
+
+    /// Returns `true` if the normalized form of the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Lowercase);
+    /// map.insert("Key", 1);
+    ///
+    /// assert!(map.contains_key("KEY"));
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized,
+        N: Normalizer<Q, K>,
+    {
+        self._inner.contains_key(&self._normalizer.normalize(key))
+    }
+
+    /// Returns a reference to the value of the normalized key, or the default value if it is not
+    /// present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Lowercase);
+    /// map.insert("Key", 1);
+    ///
+    /// assert_eq!(&1, map.get("kEY"));
+    /// assert_eq!(&0, map.get("other"));
+    /// ```
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> &V
+    where
+        Q: ?Sized,
+        N: Normalizer<Q, K>,
+    {
+        self._inner.get(&self._normalizer.normalize(key))
+    }
+
+    /// Returns a mutable reference to the value of the normalized key, inserting the default
+    /// value if it is not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map: NormalizedHashMap<String, u32, _> = NormalizedHashMap::new(Lowercase);
+    /// *map.get_mut("Word") += 1;
+    /// *map.get_mut("WORD") += 1;
+    ///
+    /// assert_eq!(&2, map.get("word"));
+    /// ```
+    #[must_use]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> &mut V
+    where
+        K: Clone,
+        Q: ?Sized,
+        N: Normalizer<Q, K>,
+    {
+        let key = self._normalizer.normalize(key);
+        self._inner.get_mut(&key)
+    }
+
+    /// Inserts a value at the normalized key, returning the previous value if there was one.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Trim, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Trim);
+    ///
+    /// assert_eq!(None, map.insert(" key", 1));
+    /// assert_eq!(Some(1), map.insert("key ", 2));
+    /// ```
+    pub fn insert<Q>(&mut self, key: &Q, value: V) -> Option<V>
+    where
+        Q: ?Sized,
+        N: Normalizer<Q, K>,
+    {
+        self._inner.insert(self._normalizer.normalize(key), value)
+    }
+
+    /// Consumes the wrapper and returns the inner [`DefaultHashMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Lowercase);
+    /// map.insert("Key", 1);
+    ///
+    /// let map: DefaultHashMap<String, i32> = map.into_inner();
+    ///
+    /// assert_eq!(&1, map.get("key"));
+    /// ```
+    pub fn into_inner(self) -> DefaultHashMap<K, V, S> {
+        self._inner
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let map: NormalizedHashMap<String, i8, Lowercase> = NormalizedHashMap::new(Lowercase);
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// Returns the length of the keys in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Lowercase);
+    /// map.insert("Key", 1);
+    /// map.insert("KEY", 2);
+    ///
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Returns a reference to the normalizer.
+    #[inline]
+    pub fn normalizer(&self) -> &N {
+        &self._normalizer
+    }
+
+    /// Removes the normalized key from the map, returning its value if it was present or the
+    /// default value otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{Lowercase, NormalizedHashMap};
+    ///
+    /// let mut map = NormalizedHashMap::new(Lowercase);
+    /// map.insert("Key", 1);
+    ///
+    /// assert_eq!(1, map.remove("KEY"));
+    /// assert_eq!(0, map.remove("key"));
+    /// ```
+    #[must_use]
+    pub fn remove<Q>(&mut self, key: &Q) -> V
+    where
+        Q: ?Sized,
+        N: Normalizer<Q, K>,
+    {
+        let key = self._normalizer.normalize(key);
+        self._inner.remove(&key)
+    }
+}
@@ -0,0 +1,518 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::{Entry, RandomState};
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Deref, DerefMut};
+
+use crate::DefaultHashMap;
+
+/// Receives notifications about the mutations made through an [`ObservedHashMap`].
+///
+/// Every method has an empty default implementation, so only the events of interest need to be
+/// implemented.
+///
+/// # Example
+/// ```
+/// use defaultdict::{Listener, ObservedHashMap};
+///
+/// #[derive(Default)]
+/// struct InsertCounter(usize);
+///
+/// impl Listener<i8, i8> for InsertCounter {
+///     fn on_insert(&mut self, _key: &i8, _value: &i8) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let mut map = ObservedHashMap::new(InsertCounter::default());
+/// map.insert(1, 1);
+/// map.insert(2, 2);
+///
+/// assert_eq!(2, map.listener().0);
+/// ```
+pub trait Listener<K, V> {
+    /// Called after a key that was not present has been inserted.
+    fn on_insert(&mut self, _key: &K, _value: &V) {}
+
+    /// Called after the value of a key that was already present has been replaced.
+    fn on_overwrite(&mut self, _key: &K, _old: &V, _new: &V) {}
+
+    /// Called after a key has been removed from the map.
+    fn on_remove(&mut self, _key: &K, _value: &V) {}
+
+    /// Called after a missing key has been inserted through [`ObservedHashMap::get_mut`]. This
+    /// happens when the returned [`ObservedGuard`] is dropped, and `value` is the default value
+    /// with the changes made through the guard.
+    fn on_default(&mut self, _key: &K, _value: &V) {}
+
+    /// Called when a key is read with [`ObservedHashMap::get`]. `hit` is `false` when the key was
+    /// not present and the default value was returned. This takes a shared reference because
+    /// reading does not borrow the map mutably.
+    fn on_read(&self, _key: &K, _hit: bool) {}
+}
+
+impl<K, V> Listener<K, V> for () {}
+
+/// A [`DefaultHashMap`] that notifies a [`Listener`] about every insert, overwrite, removal and
+/// default materialization made through it.
+///
+/// Read access is available directly or through [`ObservedHashMap::as_map`]. Mutations are only
+/// possible through the methods on this type, so the listener can not miss any of them. Changes
+/// made in place through [`ObservedHashMap::get_mut`] are reported once its guard is dropped.
+pub struct ObservedHashMap<K, V, L, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+{
+    _inner: DefaultHashMap<K, V, S>,
+    _listener: L,
+}
+
+impl<K, V, L> ObservedHashMap<K, V, L, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+{
+    /// Creates an empty [`ObservedHashMap`] that notifies the given listener.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let map: ObservedHashMap<i8, i8, ()> = ObservedHashMap::new(());
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new(listener: L) -> Self {
+        Self {
+            _inner: DefaultHashMap::new(),
+            _listener: listener,
+        }
+    }
+}
+
+impl<K, V, L, S> ObservedHashMap<K, V, L, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    /// Wraps an existing [`DefaultHashMap`]. The entries already in the map are not reported to
+    /// the listener.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap, ObservedHashMap};
+    ///
+    /// let map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 2));
+    /// let map = ObservedHashMap::from_map(map, ());
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn from_map(map: DefaultHashMap<K, V, S>, listener: L) -> Self {
+        Self {
+            _inner: map,
+            _listener: listener,
+        }
+    }
+
+    /// Returns a reference to the wrapped [`DefaultHashMap`] for read access.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    ///
+    /// let keys: Vec<&i8> = map.as_map().keys().collect();
+    ///
+    /// assert_eq!(vec![&1], keys);
+    /// ```
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<K, V, S> {
+        &self._inner
+    }
+
+    /// Removes every key from the map, notifying the listener about each removal.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    /// map.clear();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        for (key, value) in self._inner.drain() {
+            self._listener.on_remove(&key, &value);
+        }
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    ///
+    /// assert!(map.contains_key(&1));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._inner.contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// assert_eq!(&0, map.get(&2));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._listener.on_read(key, self._inner.contains_key(key));
+        self._inner.get(key)
+    }
+
+    /// Returns an [`ObservedGuard`] that dereferences to the value of the key, which is the
+    /// default value if the key is not present. When the guard is dropped the value is written
+    /// back and the listener is notified with [`Listener::on_default`] for a missing key or
+    /// [`Listener::on_overwrite`] for a key that was already present and whose value changed.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map: ObservedHashMap<u8, u32, ()> = ObservedHashMap::new(());
+    /// *map.get_mut(&1) += 5;
+    ///
+    /// assert_eq!(&5, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> ObservedGuard<'_, K, V, L, S>
+    where
+        K: Clone,
+        V: Clone + PartialEq,
+    {
+        ObservedGuard::new(self, key)
+    }
+
+    /// Inserts a key value pair into the map, calling [`Listener::on_insert`] for a new key and
+    /// [`Listener::on_overwrite`] for a key that was already present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    ///
+    /// assert_eq!(None, map.insert(1, 2));
+    /// assert_eq!(Some(2), map.insert(1, 3));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self._inner.entry(key) {
+            Entry::Occupied(mut entry) => {
+                let old = entry.insert(value);
+                self._listener.on_overwrite(entry.key(), &old, entry.get());
+                Some(old)
+            }
+            Entry::Vacant(entry) => {
+                let entry = entry.insert_entry(value);
+                self._listener.on_insert(entry.key(), entry.get());
+                None
+            }
+        }
+    }
+
+    /// Consumes the wrapper and returns the inner [`DefaultHashMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{DefaultHashMap, ObservedHashMap};
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    ///
+    /// let map: DefaultHashMap<i8, i8> = map.into_inner();
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// ```
+    pub fn into_inner(self) -> DefaultHashMap<K, V, S> {
+        self._inner
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let map: ObservedHashMap<i8, i8, ()> = ObservedHashMap::new(());
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// Returns the length of the keys in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Returns a reference to the listener.
+    #[inline]
+    pub fn listener(&self) -> &L {
+        &self._listener
+    }
+
+    /// Returns a mutable reference to the listener.
+    #[inline]
+    pub fn listener_mut(&mut self) -> &mut L {
+        &mut self._listener
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map. If the key is not present the default value is returned and the listener is not
+    /// notified.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(2, map.remove(&1));
+    /// assert_eq!(0, map.remove(&1));
+    /// ```
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        let exists = self._inner.contains_key(key);
+        let value = self._inner.remove(key);
+        if exists {
+            self._listener.on_remove(key, &value);
+        }
+        value
+    }
+
+    /// Retains only the elements specified by the predicate, notifying the listener about every
+    /// removed pair.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map = ObservedHashMap::new(());
+    /// for i in 0..10 {
+    ///     map.insert(i, i);
+    /// }
+    ///
+    /// map.retain(|key, _| key < &5);
+    ///
+    /// assert_eq!(5, map.len());
+    /// ```
+    pub fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let listener = &mut self._listener;
+        self._inner.retain(|key, value| {
+            let keep = func(key, value);
+            if !keep {
+                listener.on_remove(key, value);
+            }
+            keep
+        });
+    }
+
+    /// Changes the value of the key with `func` and writes the result back, so the change is
+    /// reported like an insert with [`Listener::on_insert`] or [`Listener::on_overwrite`]. A
+    /// missing key starts from the default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::ObservedHashMap;
+    ///
+    /// let mut map: ObservedHashMap<u8, u32, ()> = ObservedHashMap::new(());
+    /// map.update(1, |value| *value += 2);
+    /// map.update(1, |value| *value *= 5);
+    ///
+    /// assert_eq!(&10, map.get(&1));
+    /// ```
+    pub fn update<F>(&mut self, key: K, func: F)
+    where
+        V: Clone,
+        F: FnOnce(&mut V),
+    {
+        let mut value = self._inner.get(&key).clone();
+        func(&mut value);
+        self.insert(key, value);
+    }
+}
+
+/// A guard around the value of a single key of an [`ObservedHashMap`]. This is created by calling
+/// [`ObservedHashMap::get_mut`].
+///
+/// The value is taken out of the map for the lifetime of the guard and written back when the
+/// guard is dropped, at which point the listener is notified if a value was created for a missing
+/// key or the value of a present key was changed.
+///
+/// # Example
+/// ```
+/// use defaultdict::JournaledHashMap;
+///
+/// let mut map: JournaledHashMap<&str, u32> = JournaledHashMap::journaled();
+/// {
+///     let mut hits = map.get_mut(&"index");
+///     *hits += 1;
+///     *hits += 1;
+/// }
+/// let _ = map.get_mut(&"index");
+///
+/// assert_eq!(1, map.listener().len());
+/// ```
+pub struct ObservedGuard<'a, K, V, L, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    _map: &'a mut ObservedHashMap<K, V, L, S>,
+    _entry: Option<(K, V)>,
+    /// The value before the guard was created, or `None` if the key was missing.
+    _old: Option<V>,
+    /// Returns `true` if the value differs from the old one.
+    _changed: fn(&V, &V) -> bool,
+}
+
+impl<'a, K, V, L, S> ObservedGuard<'a, K, V, L, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone + PartialEq,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    /// Takes the value of the key out of the map for the lifetime of the guard.
+    fn new(map: &'a mut ObservedHashMap<K, V, L, S>, key: &K) -> Self {
+        let (entry, old) = if map._inner.contains_key(key) {
+            let (key, value) = map._inner.remove_entry(key);
+            let old = value.clone();
+            ((key, value), Some(old))
+        } else {
+            ((key.clone(), map._inner.make_default()), None)
+        };
+        Self {
+            _map: map,
+            _entry: Some(entry),
+            _old: old,
+            _changed: V::ne,
+        }
+    }
+}
+
+impl<K, V, L, S> ObservedGuard<'_, K, V, L, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    /// Returns the key this guard is for.
+    pub fn key(&self) -> &K {
+        &self.entry().0
+    }
+
+    fn entry(&self) -> &(K, V) {
+        self._entry
+            .as_ref()
+            .expect("the entry is only taken on drop")
+    }
+}
+
+impl<K, V, L, S> Deref for ObservedGuard<'_, K, V, L, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.entry().1
+    }
+}
+
+impl<K, V, L, S> DerefMut for ObservedGuard<'_, K, V, L, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self
+            ._entry
+            .as_mut()
+            .expect("the entry is only taken on drop")
+            .1
+    }
+}
+
+impl<K, V, L, S> Drop for ObservedGuard<'_, K, V, L, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    L: Listener<K, V>,
+    S: BuildHasher,
+{
+    fn drop(&mut self) {
+        let Some((key, value)) = self._entry.take() else {
+            return;
+        };
+        let map = &mut *self._map;
+        let Entry::Vacant(entry) = map._inner.entry(key) else {
+            unreachable!("the key is taken out of the map while the guard is alive");
+        };
+        let entry = entry.insert_entry(value);
+        match &self._old {
+            Some(old) if (self._changed)(old, entry.get()) => {
+                map._listener.on_overwrite(entry.key(), old, entry.get());
+            }
+            Some(_) => {}
+            None => map._listener.on_default(entry.key(), entry.get()),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+
+use crate::{DefaultBTreeMap, DefaultHashMap};
+
+/// A cross-tabulation of records: every record is assigned to a row and a column, and the records
+/// that share a cell are folded into one aggregate. Rows are kept in order, missing cells read as
+/// the default aggregate.
+///
+/// Row, column and grand totals are computed on demand by combining the aggregates of the cells.
+///
+/// # Example
+/// ```
+/// use defaultdict::Pivot;
+///
+/// let sales = [("2024", "nl", 3), ("2024", "de", 1), ("2025", "nl", 4), ("2024", "nl", 2)];
+///
+/// let pivot = Pivot::from_records(
+///     sales,
+///     |(year, _, _)| *year,
+///     |(_, country, _)| *country,
+///     |total: &mut u32, (_, _, amount)| *total += amount,
+/// );
+///
+/// assert_eq!(&5, pivot.cell(&"2024", &"nl"));
+/// assert_eq!(&0, pivot.cell(&"2025", &"de"));
+///
+/// let per_year = pivot.row_totals(|total, cell| *total += cell);
+///
+/// assert_eq!(&6, per_year.get(&"2024"));
+/// assert_eq!(10, pivot.grand_total(|total, cell| *total += cell));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pivot<R, C, A>
+where
+    R: Ord,
+    C: Eq + Hash,
+    A: Default,
+{
+    _cells: DefaultBTreeMap<R, DefaultHashMap<C, A>>,
+}
+
+impl<R, C, A> Pivot<R, C, A>
+where
+    R: Ord,
+    C: Eq + Hash,
+    A: Default,
+{
+    /// Builds the cross-tabulation of the records. `row` and `column` pick the cell of a record,
+    /// and `fold` adds the record to the aggregate of that cell, which starts at its default
+    /// value.
+    pub fn from_records<I, T, FR, FC, FF>(
+        records: I,
+        mut row: FR,
+        mut column: FC,
+        mut fold: FF,
+    ) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        FR: FnMut(&T) -> R,
+        FC: FnMut(&T) -> C,
+        FF: FnMut(&mut A, T),
+    {
+        let mut cells = DefaultBTreeMap::new();
+        for record in records {
+            let columns: &mut DefaultHashMap<C, A> = cells.entry(row(&record)).or_default();
+            fold(columns.entry(column(&record)).or_default(), record);
+        }
+        Self { _cells: cells }
+    }
+
+    /// Returns the aggregate of a cell, or the default aggregate if no record fell into it.
+    #[must_use]
+    pub fn cell(&self, row: &R, column: &C) -> &A {
+        self._cells.get(row).get(column)
+    }
+
+    /// Returns the cells as a map from row to a map from column to aggregate.
+    #[inline]
+    pub fn cells(&self) -> &DefaultBTreeMap<R, DefaultHashMap<C, A>> {
+        &self._cells
+    }
+
+    /// Combines the cells of every column into a total per column.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Pivot;
+    ///
+    /// let pivot = Pivot::from_records(
+    ///     [(1, 'a'), (2, 'a'), (2, 'b')],
+    ///     |(row, _)| *row,
+    ///     |(_, column)| *column,
+    ///     |count: &mut usize, _| *count += 1,
+    /// );
+    ///
+    /// let per_column = pivot.column_totals(|total, cell| *total += cell);
+    ///
+    /// assert_eq!(&2, per_column.get(&'a'));
+    /// assert_eq!(&1, per_column.get(&'b'));
+    /// ```
+    pub fn column_totals<F>(&self, mut combine: F) -> DefaultHashMap<C, A>
+    where
+        C: Clone,
+        F: FnMut(&mut A, &A),
+    {
+        let mut totals = DefaultHashMap::new();
+        for columns in self._cells.values() {
+            for (column, cell) in columns {
+                combine(totals.entry(column.clone()).or_default(), cell);
+            }
+        }
+        totals
+    }
+
+    /// Combines all cells into a single total.
+    pub fn grand_total<F>(&self, mut combine: F) -> A
+    where
+        F: FnMut(&mut A, &A),
+    {
+        let mut total = A::default();
+        for cell in self._cells.values().flat_map(DefaultHashMap::values) {
+            combine(&mut total, cell);
+        }
+        total
+    }
+
+    /// Consumes the pivot and returns its cells.
+    #[inline]
+    pub fn into_cells(self) -> DefaultBTreeMap<R, DefaultHashMap<C, A>> {
+        self._cells
+    }
+
+    /// Combines the cells of every row into a total per row.
+    pub fn row_totals<F>(&self, mut combine: F) -> DefaultBTreeMap<R, A>
+    where
+        R: Clone,
+        F: FnMut(&mut A, &A),
+    {
+        let mut totals = DefaultBTreeMap::new();
+        for (row, columns) in &self._cells {
+            let mut total = A::default();
+            for cell in columns.values() {
+                combine(&mut total, cell);
+            }
+            totals.insert(row.clone(), total);
+        }
+        totals
+    }
+}
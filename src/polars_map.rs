@@ -0,0 +1,110 @@
+#![deny(missing_docs)]
+
+use std::hash::{BuildHasher, Hash};
+
+use polars_core::prelude::{Column, DataFrame, PolarsError, PolarsResult};
+
+use crate::DefaultHashMap;
+
+/// A type that can be stored in a polars column without nulls. This requires the `polars`
+/// feature.
+pub trait PolarsValue: Sized {
+    /// Builds a column with the given name from the values.
+    fn to_column(name: &str, values: Vec<Self>) -> Column;
+
+    /// Reads the values of a column. Fails if the column has a different data type or contains
+    /// nulls.
+    fn from_column(column: &Column) -> PolarsResult<Vec<Self>>;
+}
+
+macro_rules! polars_value {
+    ( $( ($ty:ty, $accessor:ident) ),* ) => {
+        $(
+            impl PolarsValue for $ty {
+                fn to_column(name: &str, values: Vec<Self>) -> Column {
+                    Column::new(name.into(), values)
+                }
+
+                fn from_column(column: &Column) -> PolarsResult<Vec<Self>> {
+                    let values = column.$accessor()?;
+                    check_nulls(column)?;
+                    Ok(values.into_no_null_iter().map(Into::into).collect())
+                }
+            }
+        )*
+    };
+}
+
+polars_value!(
+    (i32, i32),
+    (i64, i64),
+    (u32, u32),
+    (u64, u64),
+    (f32, f32),
+    (f64, f64),
+    (bool, bool),
+    (String, str)
+);
+
+/// Rejects columns that contain nulls.
+fn check_nulls(column: &Column) -> PolarsResult<()> {
+    if column.null_count() > 0 {
+        return Err(PolarsError::ComputeError(
+            format!("the column {} contains nulls", column.name()).into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Conversions to and from polars data frames. This requires the `polars` feature.
+impl<K, V, S> DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + PolarsValue,
+    V: Default + PolarsValue,
+    S: BuildHasher,
+{
+    /// Builds a map from two columns of a data frame. Later duplicate keys overwrite earlier
+    /// ones.
+    ///
+    /// # Errors
+    /// Fails if a column does not exist, has an unexpected data type or contains nulls.
+    pub fn from_dataframe(frame: &DataFrame, key_col: &str, value_col: &str) -> PolarsResult<Self>
+    where
+        S: Default,
+    {
+        let keys = K::from_column(frame.column(key_col)?)?;
+        let values = V::from_column(frame.column(value_col)?)?;
+        Ok(keys.into_iter().zip(values).collect())
+    }
+
+    /// Returns the map as a data frame with a `key` and a `value` column.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap};
+    ///
+    /// let counts: DefaultHashMap<String, u32> = defaulthashmap!(("a".to_string(), 2));
+    /// let frame = counts.to_dataframe();
+    ///
+    /// assert_eq!((1, 2), frame.shape());
+    ///
+    /// let back = DefaultHashMap::<String, u32>::from_dataframe(&frame, "key", "value").unwrap();
+    ///
+    /// assert_eq!(counts, back);
+    /// ```
+    pub fn to_dataframe(&self) -> DataFrame
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let (keys, values): (Vec<K>, Vec<V>) = self
+            .into_iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .unzip();
+        DataFrame::new(vec![
+            K::to_column("key", keys),
+            V::to_column("value", values),
+        ])
+        .expect("the columns have the same length and different names")
+    }
+}
@@ -0,0 +1,102 @@
+#![deny(missing_docs)]
+
+/// Determines what happens when a missing key is read through [`DefaultHashMap::lookup`].
+///
+/// [`DefaultHashMap::lookup`]: crate::DefaultHashMap::lookup
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultHashMap, Policy};
+///
+/// let mut map: DefaultHashMap<i8, i8> = DefaultHashMap::builder()
+///     .policy(Policy::InsertOnRead)
+///     .build();
+///
+/// let _ = map.lookup(&1);
+///
+/// assert_eq!(1, map.len());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Policy {
+    /// A missing key returns a reference to the default value without changing the map.
+    #[default]
+    ReturnDefault,
+    /// A missing key is inserted with the default value before it is returned, mirroring the
+    /// python defaultdict.
+    InsertOnRead,
+}
+
+/// Determines when a [`DefaultHashMap`] gives memory back after removing many entries. This is
+/// set with [`DefaultHashMapBuilder::shrink_policy`], maps built without it never shrink on their
+/// own.
+///
+/// After [`DefaultHashMap::clear`], [`DefaultHashMap::retain`],
+/// [`DefaultHashMap::retain_values`] and [`DefaultHashMap::remove_many`] the map shrinks its
+/// capacity to fit its entries when fewer than `threshold` of the capacity is in use. The
+/// capacity is never shrunk below the minimum capacity.
+///
+/// [`DefaultHashMap`]: crate::DefaultHashMap
+/// [`DefaultHashMap::clear`]: crate::DefaultHashMap::clear
+/// [`DefaultHashMap::retain`]: crate::DefaultHashMap::retain
+/// [`DefaultHashMap::retain_values`]: crate::DefaultHashMap::retain_values
+/// [`DefaultHashMap::remove_many`]: crate::DefaultHashMap::remove_many
+/// [`DefaultHashMapBuilder::shrink_policy`]: crate::DefaultHashMapBuilder::shrink_policy
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultHashMap, ShrinkPolicy};
+///
+/// let mut map: DefaultHashMap<u32, u32> = DefaultHashMap::builder()
+///     .shrink_policy(ShrinkPolicy::new(0.25).min_capacity(16))
+///     .build();
+/// map.extend((0..10_000).map(|i| (i, i)));
+///
+/// map.retain(|key, _| *key < 10);
+///
+/// assert!(map.capacity() < 100);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShrinkPolicy {
+    _threshold: f64,
+    _min_capacity: usize,
+}
+
+impl ShrinkPolicy {
+    /// Creates a policy that shrinks a map once less than `threshold` of its capacity is in use,
+    /// with no minimum capacity.
+    ///
+    /// # Panics
+    /// Panics if the threshold is not between 0 and 1.
+    #[must_use]
+    pub fn new(threshold: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "the shrink threshold must be between 0 and 1"
+        );
+        Self {
+            _threshold: threshold,
+            _min_capacity: 0,
+        }
+    }
+
+    /// Sets the capacity the map keeps even when it is shrunk.
+    #[must_use]
+    pub fn min_capacity(mut self, min_capacity: usize) -> Self {
+        self._min_capacity = min_capacity;
+        self
+    }
+
+    /// Returns the capacity a map with `len` entries and the given capacity should shrink to, or
+    /// `None` if it should keep its capacity.
+    pub(crate) fn shrink_to(&self, len: usize, capacity: usize) -> Option<usize> {
+        let target = len.max(self._min_capacity);
+        let sparse = (len as f64) < capacity as f64 * self._threshold;
+        (sparse && capacity > target).then_some(target)
+    }
+
+    /// Returns the fraction of the capacity below which the map shrinks.
+    #[inline]
+    pub fn threshold(&self) -> f64 {
+        self._threshold
+    }
+}
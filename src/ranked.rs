@@ -0,0 +1,385 @@
+#![deny(missing_docs)]
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A link to a subtree of a [`RankedDefaultMap`].
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    priority: u64,
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K, V> Node<K, V> {
+    /// Recomputes the size of the subtree after one of the children changed.
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+/// Splits a subtree into the keys that are smaller than `key` and the keys that are not.
+fn split<K: Ord, V>(link: Link<K, V>, key: &K) -> (Link<K, V>, Link<K, V>) {
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            if node.key < *key {
+                let (smaller, rest) = split(node.right.take(), key);
+                node.right = smaller;
+                node.update();
+                (Some(node), rest)
+            } else {
+                let (smaller, rest) = split(node.left.take(), key);
+                node.left = rest;
+                node.update();
+                (smaller, Some(node))
+            }
+        }
+    }
+}
+
+/// Joins two subtrees, where every key of `left` is smaller than every key of `right`.
+fn merge<K, V>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update();
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update();
+                Some(right)
+            }
+        }
+    }
+}
+
+fn remove<K: Ord, V>(link: &mut Link<K, V>, key: &K) -> Option<V> {
+    let node = link.as_mut()?;
+    let removed = match key.cmp(&node.key) {
+        Ordering::Less => remove(&mut node.left, key),
+        Ordering::Greater => remove(&mut node.right, key),
+        Ordering::Equal => {
+            let mut node = link.take()?;
+            *link = merge(node.left.take(), node.right.take());
+            return Some(node.value);
+        }
+    };
+    if removed.is_some() {
+        node.size -= 1;
+    }
+    removed
+}
+
+/// A sorted map with default values that also answers order-statistic queries:
+/// [`RankedDefaultMap::rank`] counts the keys smaller than a key and
+/// [`RankedDefaultMap::select`] finds the n-th smallest entry.
+///
+/// The map is a balanced search tree in which every node knows the size of its subtree, so
+/// lookups, inserts, removals, ranks and selects all take logarithmic time. This makes it a good
+/// fit for percentiles and leaderboards over keyed data.
+///
+/// # Example
+/// ```
+/// use defaultdict::RankedDefaultMap;
+///
+/// let mut scores = RankedDefaultMap::<u32, &str>::new();
+/// for (score, name) in [(70, "ada"), (95, "bob"), (82, "cy"), (60, "dee")] {
+///     scores.insert(score, name);
+/// }
+///
+/// assert_eq!(2, scores.rank(&82));
+/// assert_eq!(Some((&95, &"bob")), scores.select(scores.len() - 1));
+/// assert_eq!(&"", scores.get(&100));
+/// ```
+pub struct RankedDefaultMap<K, V>
+where
+    K: Ord,
+    V: Default,
+{
+    _root: Link<K, V>,
+    _default: V,
+    _seed: u64,
+}
+
+impl<K, V> RankedDefaultMap<K, V>
+where
+    K: Ord,
+    V: Default,
+{
+    /// Creates an empty [`RankedDefaultMap`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _root: None,
+            _default: V::default(),
+            _seed: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Removes every key from the map.
+    pub fn clear(&mut self) {
+        self._root = None;
+    }
+
+    /// Returns `true` if the map holds the key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Returns a reference to the value of the key, or the default value if the key is missing.
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self.find(key).unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value of the key, inserting the default value if the
+    /// key is missing.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::RankedDefaultMap;
+    ///
+    /// let mut map = RankedDefaultMap::<&str, u32>::new();
+    /// *map.get_mut(&"a") += 3;
+    ///
+    /// assert_eq!(&3, map.get(&"a"));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V
+    where
+        K: Clone,
+    {
+        if !self.contains_key(key) {
+            self.insert(key.clone(), V::default());
+        }
+        let mut link = &mut self._root;
+        while let Some(node) = link {
+            match key.cmp(&node.key) {
+                Ordering::Less => link = &mut node.left,
+                Ordering::Greater => link = &mut node.right,
+                Ordering::Equal => return &mut node.value,
+            }
+        }
+        unreachable!("the key was inserted above")
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value of the key if it was
+    /// present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(previous) = self.find_mut(&key) {
+            return Some(std::mem::replace(previous, value));
+        }
+        let node = Box::new(Node {
+            key,
+            value,
+            priority: self.next_priority(),
+            size: 1,
+            left: None,
+            right: None,
+        });
+        let (smaller, rest) = split(self._root.take(), &node.key);
+        self._root = merge(merge(smaller, Some(node)), rest);
+        None
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._root.is_none()
+    }
+
+    /// An iterator over the entries of the map, in ascending order of the keys.
+    pub fn iter(&self) -> RankedIter<'_, K, V> {
+        let mut iter = RankedIter { _stack: Vec::new() };
+        iter.push_left(&self._root);
+        iter
+    }
+
+    /// Returns the number of keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        size(&self._root)
+    }
+
+    /// Returns the number of keys in the map that are smaller than the key passed in. The key
+    /// does not have to be present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::RankedDefaultMap;
+    ///
+    /// let map: RankedDefaultMap<i32, ()> = [10, 20, 30].into_iter().map(|k| (k, ())).collect();
+    ///
+    /// assert_eq!(0, map.rank(&5));
+    /// assert_eq!(1, map.rank(&20));
+    /// assert_eq!(3, map.rank(&99));
+    /// ```
+    pub fn rank(&self, key: &K) -> usize {
+        let mut rank = 0;
+        let mut link = &self._root;
+        while let Some(node) = link {
+            match key.cmp(&node.key) {
+                Ordering::Less => link = &node.left,
+                Ordering::Equal => return rank + size(&node.left),
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    link = &node.right;
+                }
+            }
+        }
+        rank
+    }
+
+    /// Removes the key from the map and returns its value, or the default value if the key was
+    /// not present.
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        remove(&mut self._root, key).unwrap_or_default()
+    }
+
+    /// Returns the entry with the n-th smallest key, counting from zero, or `None` if the map
+    /// holds `n` or fewer keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::RankedDefaultMap;
+    ///
+    /// let latencies: RankedDefaultMap<u32, u32> = (1..=100).map(|ms| (ms, 1)).collect();
+    ///
+    /// let p90 = latencies.select(latencies.len() * 9 / 10 - 1);
+    ///
+    /// assert_eq!(Some((&90, &1)), p90);
+    /// ```
+    pub fn select(&self, mut n: usize) -> Option<(&K, &V)> {
+        let mut link = &self._root;
+        while let Some(node) = link {
+            let smaller = size(&node.left);
+            match n.cmp(&smaller) {
+                Ordering::Less => link = &node.left,
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    n -= smaller + 1;
+                    link = &node.right;
+                }
+            }
+        }
+        None
+    }
+
+    fn find(&self, key: &K) -> Option<&V> {
+        let mut link = &self._root;
+        while let Some(node) = link {
+            match key.cmp(&node.key) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => link = &node.right,
+                Ordering::Equal => return Some(&node.value),
+            }
+        }
+        None
+    }
+
+    fn find_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut link = &mut self._root;
+        while let Some(node) = link {
+            match key.cmp(&node.key) {
+                Ordering::Less => link = &mut node.left,
+                Ordering::Greater => link = &mut node.right,
+                Ordering::Equal => return Some(&mut node.value),
+            }
+        }
+        None
+    }
+
+    /// Draws the priority of a new node from a xorshift generator. The priorities only have to
+    /// be independent of the keys to keep the tree balanced.
+    fn next_priority(&mut self) -> u64 {
+        self._seed ^= self._seed << 13;
+        self._seed ^= self._seed >> 7;
+        self._seed ^= self._seed << 17;
+        self._seed
+    }
+}
+
+impl<K, V> Default for RankedDefaultMap<K, V>
+where
+    K: Ord,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for RankedDefaultMap<K, V>
+where
+    K: Ord + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RankedDefaultMap<K, V>
+where
+    K: Ord,
+    V: Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a RankedDefaultMap<K, V>
+where
+    K: Ord,
+    V: Default,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = RankedIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`RankedDefaultMap`] in ascending order of the keys. This is
+/// created by calling [`RankedDefaultMap::iter`].
+pub struct RankedIter<'a, K, V> {
+    _stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> RankedIter<'a, K, V> {
+    fn push_left(&mut self, mut link: &'a Link<K, V>) {
+        while let Some(node) = link {
+            self._stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for RankedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self._stack.pop()?;
+        self.push_left(&node.right);
+        Some((&node.key, &node.value))
+    }
+}
@@ -0,0 +1,100 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use crate::DefaultHashMap;
+
+/// A [`BuildHasher`] with fixed keys, so the same keys always produce the same hashes and a map
+/// that receives the same operations always iterates in the same order.
+///
+/// The keys are fed into the SipHash hasher of the standard library before the value is hashed.
+/// The hashes are stable for a given build of a program, but the standard library does not
+/// promise to keep its hash function the same across Rust releases, so they should not be
+/// persisted.
+///
+/// # Example
+/// ```
+/// use std::hash::BuildHasher;
+///
+/// use defaultdict::SeededState;
+///
+/// let a = SeededState::with_seed(7);
+/// let b = SeededState::with_seed(7);
+///
+/// assert_eq!(a.hash_one("key"), b.hash_one("key"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SeededState {
+    _k0: u64,
+    _k1: u64,
+}
+
+impl SeededState {
+    /// Creates a hasher builder from a single seed.
+    #[must_use]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_keys(seed, 0)
+    }
+
+    /// Creates a hasher builder from two keys.
+    #[must_use]
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self { _k0: k0, _k1: k1 }
+    }
+}
+
+impl BuildHasher for SeededState {
+    type Hasher = DefaultHasher;
+
+    fn build_hasher(&self) -> DefaultHasher {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(self._k0);
+        hasher.write_u64(self._k1);
+        hasher
+    }
+}
+
+impl<K, V> DefaultHashMap<K, V, SeededState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`DefaultHashMap`] with a deterministic hasher derived from the seed. See
+    /// [`SeededState`] for the guarantees this gives.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut a = DefaultHashMap::with_seed(42);
+    /// let mut b = DefaultHashMap::with_seed(42);
+    /// for i in 0..100 {
+    ///     a.insert(i, i);
+    ///     b.insert(i, i);
+    /// }
+    ///
+    /// assert!(a.keys().eq(b.keys()));
+    /// ```
+    #[must_use]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_hasher(SeededState::with_seed(seed))
+    }
+
+    /// Creates an empty [`DefaultHashMap`] with a deterministic hasher derived from two keys. See
+    /// [`SeededState`] for the guarantees this gives.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::with_keys(1, 2);
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(&1, map.get(&"a"));
+    /// ```
+    #[must_use]
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_hasher(SeededState::with_keys(k0, k1))
+    }
+}
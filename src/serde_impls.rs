@@ -0,0 +1,292 @@
+#![deny(missing_docs)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DefaultBTreeMap, DefaultHashMap, Error};
+
+/// Determines what happens when serialized data that is merged into a map with
+/// [`DefaultHashMap::merge_from_deserializer`] or [`DefaultBTreeMap::merge_from_deserializer`]
+/// holds a key that is already present.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ConflictPolicy {
+    /// The value from the input replaces the value in the map.
+    #[default]
+    Overwrite,
+    /// The value in the map is kept and the value from the input is dropped.
+    KeepExisting,
+    /// Merging stops with an error at the first key that is already present.
+    Error,
+}
+
+impl ConflictPolicy {
+    /// Returns whether an entry from the input is written to the map, or an error if the key is
+    /// already present and conflicts are not allowed.
+    fn resolve(self, present: bool) -> Result<bool, Error> {
+        match (self, present) {
+            (_, false) | (ConflictPolicy::Overwrite, true) => Ok(true),
+            (ConflictPolicy::KeepExisting, true) => Ok(false),
+            (ConflictPolicy::Error, true) => Err(Error::Occupied),
+        }
+    }
+}
+
+/// Feeds the entries of a serialized map one at a time to `_merge`, without collecting them
+/// into an intermediate map first.
+struct MergeVisitor<K, V, F> {
+    _merge: F,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, F> MergeVisitor<K, V, F> {
+    fn new(merge: F) -> Self {
+        Self {
+            _merge: merge,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, K, V, F> Visitor<'de> for MergeVisitor<K, V, F>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V) -> Result<(), Error>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(mut self, mut access: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some((key, value)) = access.next_entry()? {
+            (self._merge)(key, value).map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes the entries of the map as a map. The default value is not serialized.
+impl<K, V, S> Serialize for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + Serialize,
+    V: Default + Serialize,
+    S: BuildHasher,
+{
+    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    where
+        T: Serializer,
+    {
+        serializer.collect_map(self)
+    }
+}
+
+/// Deserializes a map into a [`DefaultHashMap`] with the [`Default`] value of `V` as its default.
+///
+/// Keys and values may borrow from the input, so a `DefaultHashMap<&'de str, V>` can be
+/// deserialized without copying the keys. This also works for fields marked `#[serde(borrow)]`.
+impl<'de, K, V, S> Deserialize<'de> for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + Deserialize<'de>,
+    V: Default + Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        HashMap::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Serializes the entries of the map as a map, in ascending key order. The default value is not
+/// serialized.
+impl<K, V> Serialize for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord + Serialize,
+    V: Default + Serialize,
+{
+    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    where
+        T: Serializer,
+    {
+        serializer.collect_map(self)
+    }
+}
+
+/// Deserializes a map into a [`DefaultBTreeMap`] with the [`Default`] value of `V` as its default.
+///
+/// Keys and values may borrow from the input, so a `DefaultBTreeMap<&'de str, V>` can be
+/// deserialized without copying the keys. This also works for fields marked `#[serde(borrow)]`.
+impl<'de, K, V> Deserialize<'de> for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord + Deserialize<'de>,
+    V: Default + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BTreeMap::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<K, V, S> DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Deserializes a map and merges its entries into this map, resolving keys that are already
+    /// present with the given policy. The default value, hasher and other settings of this map
+    /// are kept.
+    ///
+    /// # Errors
+    /// Returns the error of the deserializer if the input is not a map of the right types, or if
+    /// the policy is [`ConflictPolicy::Error`] and the input holds a key that is already present.
+    /// The entries read before the error stay merged into the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{ConflictPolicy, DefaultHashMap};
+    ///
+    /// let mut stock = DefaultHashMap::<String, u32>::new();
+    /// stock.insert("apples".to_string(), 3);
+    ///
+    /// let mut input = serde_json::Deserializer::from_str(r#"{"apples": 5, "pears": 2}"#);
+    /// stock
+    ///     .merge_from_deserializer(&mut input, ConflictPolicy::KeepExisting)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&3, stock.get("apples"));
+    /// assert_eq!(&2, stock.get("pears"));
+    /// ```
+    pub fn merge_from_deserializer<'de, D>(
+        &mut self,
+        deserializer: D,
+        policy: ConflictPolicy,
+    ) -> Result<(), D::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MergeVisitor::new(|key, value| {
+            if policy.resolve(self.contains_key(&key))? {
+                self.insert(key, value);
+            }
+            Ok(())
+        }))
+    }
+
+    /// Parses a JSON object and merges its entries into this map, resolving keys that are
+    /// already present with the given policy. See [`DefaultHashMap::merge_from_deserializer`].
+    ///
+    /// # Errors
+    /// Returns an error if the input is not a JSON object of the right types, or if the policy is
+    /// [`ConflictPolicy::Error`] and the input holds a key that is already present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{ConflictPolicy, DefaultHashMap};
+    ///
+    /// let mut config = DefaultHashMap::<String, String>::new();
+    /// config.insert("host".to_string(), "localhost".to_string());
+    ///
+    /// let result = config.merge_from_json(r#"{"host": "example.com"}"#, ConflictPolicy::Error);
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!("localhost", config.get("host"));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn merge_from_json<'de>(
+        &mut self,
+        json: &'de str,
+        policy: ConflictPolicy,
+    ) -> Result<(), serde_json::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        self.merge_from_deserializer(&mut deserializer, policy)?;
+        deserializer.end()
+    }
+}
+
+impl<K, V> DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord,
+    V: Default,
+{
+    /// Deserializes a map and merges its entries into this map, resolving keys that are already
+    /// present with the given policy. The default value of this map is kept.
+    ///
+    /// # Errors
+    /// Returns the error of the deserializer if the input is not a map of the right types, or if
+    /// the policy is [`ConflictPolicy::Error`] and the input holds a key that is already present.
+    /// The entries read before the error stay merged into the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{ConflictPolicy, DefaultBTreeMap};
+    ///
+    /// let mut totals = DefaultBTreeMap::<u8, u32>::new();
+    /// totals.insert(1, 10);
+    ///
+    /// let mut input = serde_json::Deserializer::from_str(r#"{"1": 20, "2": 30}"#);
+    /// totals
+    ///     .merge_from_deserializer(&mut input, ConflictPolicy::Overwrite)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(&20, totals.get(&1));
+    /// assert_eq!(&30, totals.get(&2));
+    /// ```
+    pub fn merge_from_deserializer<'de, D>(
+        &mut self,
+        deserializer: D,
+        policy: ConflictPolicy,
+    ) -> Result<(), D::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MergeVisitor::new(|key, value| {
+            if policy.resolve(self.contains_key(&key))? {
+                self.insert(key, value);
+            }
+            Ok(())
+        }))
+    }
+
+    /// Parses a JSON object and merges its entries into this map, resolving keys that are
+    /// already present with the given policy. See [`DefaultBTreeMap::merge_from_deserializer`].
+    ///
+    /// # Errors
+    /// Returns an error if the input is not a JSON object of the right types, or if the policy is
+    /// [`ConflictPolicy::Error`] and the input holds a key that is already present.
+    #[cfg(feature = "json")]
+    pub fn merge_from_json<'de>(
+        &mut self,
+        json: &'de str,
+        policy: ConflictPolicy,
+    ) -> Result<(), serde_json::Error>
+    where
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        self.merge_from_deserializer(&mut deserializer, policy)?;
+        deserializer.end()
+    }
+}
@@ -0,0 +1,255 @@
+#![deny(missing_docs)]
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+/// A default map that stores every distinct value once and shares it between the keys that hold
+/// it through an [`Arc`], which saves a lot of memory when many keys map to a few values, such as
+/// statuses or short labels.
+///
+/// Values are never mutated in place, since that would change them for every key that shares
+/// them. [`SharedValueMap::update`] copies the value, changes the copy and stores the result like
+/// any other value. The pool counts how many keys hold every value, and a value is dropped from
+/// the pool once the last key holding it is removed or overwritten.
+///
+/// # Example
+/// ```
+/// use defaultdict::SharedValueMap;
+///
+/// let mut status = SharedValueMap::<u32, String>::new();
+/// for id in 0..1000 {
+///     let value = if id % 10 == 0 { "failed" } else { "ok" };
+///     status.insert(id, value.to_string());
+/// }
+///
+/// assert_eq!(1000, status.len());
+/// assert_eq!(2, status.distinct_values());
+/// assert_eq!("failed", status.get(&10));
+/// assert_eq!("", status.get(&5000));
+/// ```
+pub struct SharedValueMap<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Eq + Hash + Clone + Default,
+{
+    _inner: HashMap<K, Arc<V>, S>,
+    /// Every stored value and the number of keys that hold it.
+    _pool: HashMap<Arc<V>, usize>,
+    _default: V,
+}
+
+impl<K, V> SharedValueMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Eq + Hash + Clone + Default,
+{
+    /// Creates an empty [`SharedValueMap`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> SharedValueMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Eq + Hash + Clone + Default,
+    S: BuildHasher,
+{
+    /// Creates an empty [`SharedValueMap`] which will use the given hash builder to hash keys.
+    #[must_use]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            _inner: HashMap::with_hasher(hash_builder),
+            _pool: HashMap::new(),
+            _default: V::default(),
+        }
+    }
+
+    /// Removes every key and every pooled value from the map.
+    pub fn clear(&mut self) {
+        self._inner.clear();
+        self._pool.clear();
+    }
+
+    /// Returns `true` if the map holds the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner.contains_key(key)
+    }
+
+    /// Returns the number of distinct values that are stored.
+    #[inline]
+    pub fn distinct_values(&self) -> usize {
+        self._pool.len()
+    }
+
+    /// Returns a reference to the value of the key, or the default value if the key is missing.
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> &V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner.get(key).map_or(&self._default, |value| value)
+    }
+
+    /// Returns the shared value of the key, or `None` if the key is missing. Keys with equal
+    /// values return the same [`Arc`]. A clone of it that outlives the keys holding the value does
+    /// not keep the value in the pool, so an equal value that is inserted later is stored anew.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use defaultdict::SharedValueMap;
+    ///
+    /// let mut map = SharedValueMap::<&str, String>::new();
+    /// map.insert("a", "same".to_string());
+    /// map.insert("b", "same".to_string());
+    ///
+    /// assert!(Arc::ptr_eq(map.get_shared(&"a").unwrap(), map.get_shared(&"b").unwrap()));
+    /// ```
+    pub fn get_shared<Q>(&self, key: &Q) -> Option<&Arc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner.get(key)
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if the key was
+    /// present. The value is replaced by the pooled copy if an equal value is already stored.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let value = self.share(value);
+        let old = self._inner.insert(key, value)?;
+        Some(self.release(old))
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self._inner.iter().map(|(key, value)| (key, &**value))
+    }
+
+    /// Returns the number of keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// Removes the key and returns its value, or the default value if the key was missing.
+    #[must_use]
+    pub fn remove<Q>(&mut self, key: &Q) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self._inner.remove(key) {
+            Some(value) => self.release(value),
+            None => V::default(),
+        }
+    }
+
+    /// Changes the value of the key with the closure, starting from the default value if the key
+    /// is missing. The closure works on a copy, so the other keys that share the value keep it.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SharedValueMap;
+    ///
+    /// let mut map = SharedValueMap::<&str, u32>::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 1);
+    ///
+    /// map.update("a", |value| *value += 1);
+    ///
+    /// assert_eq!(&2, map.get(&"a"));
+    /// assert_eq!(&1, map.get(&"b"));
+    /// assert_eq!(2, map.distinct_values());
+    /// ```
+    pub fn update<F>(&mut self, key: K, f: F)
+    where
+        F: FnOnce(&mut V),
+    {
+        let mut value = self
+            ._inner
+            .get(&key)
+            .map_or_else(V::default, |value| (**value).clone());
+        f(&mut value);
+        self.insert(key, value);
+    }
+
+    /// Returns the pooled copy of the value, adding it to the pool if it is not there yet.
+    fn share(&mut self, value: V) -> Arc<V> {
+        let shared = match self._pool.get_key_value(&value) {
+            Some((shared, _)) => Arc::clone(shared),
+            None => Arc::new(value),
+        };
+        *self._pool.entry(Arc::clone(&shared)).or_default() += 1;
+        shared
+    }
+
+    /// Takes a value that was removed from a key, dropping it from the pool if no other key
+    /// holds it.
+    fn release(&mut self, value: Arc<V>) -> V {
+        let holders = self
+            ._pool
+            .get_mut(&*value)
+            .expect("every stored value is pooled");
+        *holders -= 1;
+        if *holders == 0 {
+            self._pool.remove(&*value);
+        }
+        Arc::try_unwrap(value).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<K, V, S> Default for SharedValueMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Eq + Hash + Clone + Default,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> fmt::Debug for SharedValueMap<K, V, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: Eq + Hash + Clone + Default + fmt::Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for SharedValueMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Eq + Hash + Clone + Default,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
@@ -0,0 +1,194 @@
+#![deny(missing_docs)]
+
+use std::marker::PhantomData;
+use std::path::Path;
+
+use sled::{Error, Result, Tree};
+
+use crate::Codec;
+
+/// A default map that is stored on disk by [`sled`]. This requires the `sled` feature.
+///
+/// Every write goes straight to the database, so the contents survive a restart of the program.
+/// Keys and values are converted to bytes with [`Codec`]. Reads return owned values, falling back
+/// to the default value for missing keys.
+///
+/// # Example
+/// ```
+/// use defaultdict::PersistentDefaultMap;
+///
+/// let db = sled::Config::new().temporary(true).open()?;
+/// let map: PersistentDefaultMap<String, u64> =
+///     PersistentDefaultMap::from_tree(db.open_tree("counts")?);
+///
+/// map.update(&String::from("a"), |count| *count += 1)?;
+/// map.update(&String::from("a"), |count| *count += 1)?;
+/// map.flush()?;
+///
+/// assert_eq!(2, map.get(&String::from("a"))?);
+/// assert_eq!(0, map.get(&String::from("b"))?);
+/// # Ok::<(), sled::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct PersistentDefaultMap<K, V>
+where
+    K: Codec,
+    V: Codec + Default,
+{
+    _tree: Tree,
+    _default: V,
+    _key: PhantomData<K>,
+}
+
+impl<K, V> PersistentDefaultMap<K, V>
+where
+    K: Codec,
+    V: Codec + Default + Clone,
+{
+    /// Opens, or creates, the database at the given path and uses its default tree.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use defaultdict::PersistentDefaultMap;
+    ///
+    /// let map: PersistentDefaultMap<u32, u64> = PersistentDefaultMap::open("counts.db")?;
+    /// # Ok::<(), sled::Error>(())
+    /// ```
+    pub fn open<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let db = sled::open(path)?;
+        Ok(Self::from_tree(Tree::clone(&db)))
+    }
+
+    /// Uses an already opened [`Tree`] as storage. This allows multiple maps to share one
+    /// database.
+    #[must_use]
+    pub fn from_tree(tree: Tree) -> Self {
+        Self {
+            _tree: tree,
+            _default: V::default(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Removes all key-value pairs from the map.
+    pub fn clear(&self) -> Result<()> {
+        self._tree.clear()
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    pub fn contains_key(&self, key: &K) -> Result<bool> {
+        self._tree.contains_key(key.encode())
+    }
+
+    /// Writes all buffered changes to disk, returning the number of bytes flushed.
+    pub fn flush(&self) -> Result<usize> {
+        self._tree.flush()
+    }
+
+    /// Returns the value of the key passed in, or the default value if the key is not present.
+    pub fn get(&self, key: &K) -> Result<V> {
+        match self._tree.get(key.encode())? {
+            Some(bytes) => decode(&bytes),
+            None => Ok(self._default.clone()),
+        }
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if there was one.
+    pub fn insert(&self, key: &K, value: &V) -> Result<Option<V>> {
+        self._tree
+            .insert(key.encode(), value.encode())?
+            .map(|bytes| decode(&bytes))
+            .transpose()
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._tree.is_empty()
+    }
+
+    /// Returns an iterator over the decoded key-value pairs, in the byte order of the encoded
+    /// keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::PersistentDefaultMap;
+    ///
+    /// let db = sled::Config::new().temporary(true).open()?;
+    /// let map: PersistentDefaultMap<u8, u8> =
+    ///     PersistentDefaultMap::from_tree(db.open_tree("map")?);
+    /// map.insert(&2, &20)?;
+    /// map.insert(&1, &10)?;
+    ///
+    /// let pairs: Vec<(u8, u8)> = map.iter().collect::<sled::Result<_>>()?;
+    ///
+    /// assert_eq!(vec![(1, 10), (2, 20)], pairs);
+    /// # Ok::<(), sled::Error>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K, V)>> {
+        self._tree.iter().map(|pair| {
+            let (key, value) = pair?;
+            Ok((decode(&key)?, decode(&value)?))
+        })
+    }
+
+    /// Returns the length of the keys in the map. This walks the whole tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._tree.len()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise.
+    pub fn remove(&self, key: &K) -> Result<V> {
+        match self._tree.remove(key.encode())? {
+            Some(bytes) => decode(&bytes),
+            None => Ok(self._default.clone()),
+        }
+    }
+
+    /// Atomically changes the value at the key, starting from the default value if the key is not
+    /// present, and returns the new value. The function may be called more than once when other
+    /// threads update the same key concurrently.
+    pub fn update<F>(&self, key: &K, mut func: F) -> Result<V>
+    where
+        F: FnMut(&mut V),
+    {
+        let mut corrupt = false;
+        let bytes = self._tree.update_and_fetch(key.encode(), |old| {
+            let mut value = match old {
+                Some(bytes) => match V::decode(bytes) {
+                    Some(value) => value,
+                    None => {
+                        corrupt = true;
+                        return Some(bytes.to_vec());
+                    }
+                },
+                None => self._default.clone(),
+            };
+            corrupt = false;
+            func(&mut value);
+            Some(value.encode())
+        })?;
+        if corrupt {
+            return Err(corrupt_error());
+        }
+        match bytes {
+            Some(bytes) => decode(&bytes),
+            None => Ok(self._default.clone()),
+        }
+    }
+}
+
+/// Decodes stored bytes, reporting bytes that are not a valid encoding as an error.
+fn decode<T: Codec>(bytes: &[u8]) -> Result<T> {
+    T::decode(bytes).ok_or_else(corrupt_error)
+}
+
+/// The error returned for stored bytes that are not a valid encoding.
+fn corrupt_error() -> Error {
+    Error::Unsupported(String::from("stored bytes could not be decoded"))
+}
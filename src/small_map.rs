@@ -0,0 +1,357 @@
+#![deny(missing_docs)]
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// Where the entries of a [`SmallDefaultMap`] live.
+#[derive(Clone)]
+enum Storage<K, V, const N: usize> {
+    /// Up to `N` entries, packed at the front of the array.
+    Inline {
+        slots: [Option<(K, V)>; N],
+        len: usize,
+    },
+    /// More than `N` entries have been stored at some point.
+    Spilled(HashMap<K, V>),
+}
+
+/// A default map that keeps up to `N` entries inline and only moves them to a heap allocated
+/// [`HashMap`] once it grows beyond that.
+///
+/// Lookups in the inline storage are a linear scan, which beats hashing for the handful of
+/// entries most short lived maps hold. Once the map has spilled it stays a [`HashMap`], even if
+/// entries are removed again.
+///
+/// # Example
+/// ```
+/// use defaultdict::SmallDefaultMap;
+///
+/// let mut map: SmallDefaultMap<&str, u32, 2> = SmallDefaultMap::new();
+/// *map.get_mut(&"a") += 1;
+/// *map.get_mut(&"b") += 1;
+///
+/// assert!(map.is_inline());
+///
+/// *map.get_mut(&"c") += 1;
+///
+/// assert!(!map.is_inline());
+/// assert_eq!(&1, map.get(&"a"));
+/// assert_eq!(&0, map.get(&"d"));
+/// ```
+#[derive(Clone)]
+pub struct SmallDefaultMap<K, V, const N: usize = 8>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _storage: Storage<K, V, N>,
+    _default: V,
+}
+
+impl<K, V, const N: usize> SmallDefaultMap<K, V, N>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`SmallDefaultMap`]. This does not allocate.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _storage: Storage::Inline {
+                slots: std::array::from_fn(|_| None),
+                len: 0,
+            },
+            _default: V::default(),
+        }
+    }
+
+    /// Removes all entries from the map. A spilled map keeps its allocation.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// map.insert(1, 1);
+    /// map.clear();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        match &mut self._storage {
+            Storage::Inline { slots, len } => {
+                for slot in &mut slots[..*len] {
+                    *slot = None;
+                }
+                *len = 0;
+            }
+            Storage::Spilled(map) => map.clear(),
+        }
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// map.insert(1, 1);
+    ///
+    /// assert!(map.contains_key(&1));
+    /// assert!(!map.contains_key(&2));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        match &self._storage {
+            Storage::Inline { .. } => self.position(key).is_some(),
+            Storage::Spilled(map) => map.contains_key(key),
+        }
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// assert_eq!(&0, map.get(&2));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        let value = match &self._storage {
+            Storage::Inline { slots, .. } => self
+                .position(key)
+                .and_then(|index| slots[index].as_ref())
+                .map(|(_, value)| value),
+            Storage::Spilled(map) => map.get(key),
+        };
+        value.unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key. If the key is not
+    /// present the default value is inserted first, which can make the map spill.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// *map.get_mut(&1) += 3;
+    ///
+    /// assert_eq!(&3, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V
+    where
+        K: Clone,
+    {
+        if !self.contains_key(key) {
+            self.insert(key.clone(), V::default());
+        }
+        let index = self.position(key);
+        let value = match &mut self._storage {
+            Storage::Inline { slots, .. } => index
+                .and_then(|index| slots[index].as_mut())
+                .map(|(_, value)| value),
+            Storage::Spilled(map) => map.get_mut(key),
+        };
+        value.expect("the key was inserted above")
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if the key was
+    /// present. Inserting a new key into a full inline map moves all entries to a [`HashMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    ///
+    /// assert_eq!(None, map.insert(1, 2));
+    /// assert_eq!(Some(2), map.insert(1, 3));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let index = self.position(&key);
+        match &mut self._storage {
+            Storage::Inline { slots, len } => {
+                if let Some((_, old)) = index.and_then(|index| slots[index].as_mut()) {
+                    return Some(std::mem::replace(old, value));
+                }
+                if *len < N {
+                    slots[*len] = Some((key, value));
+                    *len += 1;
+                    return None;
+                }
+                let mut map: HashMap<K, V> = slots.iter_mut().filter_map(Option::take).collect();
+                map.insert(key, value);
+                self._storage = Storage::Spilled(map);
+                None
+            }
+            Storage::Spilled(map) => map.insert(key, value),
+        }
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the entries are still stored inline.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8, 1> = SmallDefaultMap::new();
+    /// map.insert(1, 1);
+    ///
+    /// assert!(map.is_inline());
+    ///
+    /// map.insert(2, 2);
+    ///
+    /// assert!(!map.is_inline());
+    /// ```
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self._storage, Storage::Inline { .. })
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(vec![(&1, &2)], map.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let (inline, spilled) = match &self._storage {
+            Storage::Inline { slots, len } => (&slots[..*len], None),
+            Storage::Spilled(map) => (&[][..], Some(map.iter())),
+        };
+        inline
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key, value))
+            .chain(spilled.into_iter().flatten())
+    }
+
+    /// Returns the length of the keys in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(1, map.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self._storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Spilled(map) => map.len(),
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SmallDefaultMap;
+    ///
+    /// let mut map: SmallDefaultMap<i8, i8> = SmallDefaultMap::new();
+    /// map.insert(1, 2);
+    ///
+    /// assert_eq!(2, map.remove(&1));
+    /// assert_eq!(0, map.remove(&1));
+    /// ```
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        let index = self.position(key);
+        let value = match &mut self._storage {
+            Storage::Inline { slots, len } => index.and_then(|index| {
+                let (_, value) = slots[index].take()?;
+                slots.swap(index, *len - 1);
+                *len -= 1;
+                Some(value)
+            }),
+            Storage::Spilled(map) => map.remove(key),
+        };
+        value.unwrap_or_default()
+    }
+
+    /// Returns the position of the key in the inline storage.
+    fn position(&self, key: &K) -> Option<usize> {
+        match &self._storage {
+            Storage::Inline { slots, len } => slots[..*len]
+                .iter()
+                .position(|slot| matches!(slot, Some((candidate, _)) if candidate == key)),
+            Storage::Spilled(_) => None,
+        }
+    }
+}
+
+impl<K, V, const N: usize> Default for SmallDefaultMap<K, V, N>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> fmt::Debug for SmallDefaultMap<K, V, N>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize> FromIterator<(K, V)> for SmallDefaultMap<K, V, N>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
@@ -0,0 +1,408 @@
+#![deny(missing_docs)]
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+/// An ordering of keys of type `K`, used as the `C` type parameter of a [`DefaultSortedMap`].
+///
+/// The ordering is a type rather than a value, so every map with the same `C` sorts its keys the
+/// same way and no comparator has to be stored next to the keys. Implement it on a unit struct
+/// to add an ordering. Keys that compare as [`Ordering::Equal`] share one entry.
+///
+/// # Example
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use defaultdict::{DefaultSortedMap, SortBy};
+///
+/// struct ByLength;
+///
+/// impl SortBy<String> for ByLength {
+///     fn cmp(a: &String, b: &String) -> Ordering {
+///         a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+///     }
+/// }
+///
+/// let map: DefaultSortedMap<String, u8, ByLength> =
+///     ["ccc", "a", "bb"].into_iter().map(|key| (key.to_string(), 1)).collect();
+///
+/// let keys: Vec<&String> = map.keys().collect();
+///
+/// assert_eq!(vec!["a", "bb", "ccc"], keys);
+/// ```
+pub trait SortBy<K: ?Sized> {
+    /// Compares two keys.
+    fn cmp(a: &K, b: &K) -> Ordering;
+}
+
+/// Sorts keys by their [`Ord`] implementation, like a [`DefaultBTreeMap`](crate::DefaultBTreeMap).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Natural;
+
+impl<K> SortBy<K> for Natural
+where
+    K: Ord + ?Sized,
+{
+    fn cmp(a: &K, b: &K) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Reverses another ordering, so the largest key comes first.
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultSortedMap, Descending};
+///
+/// let map: DefaultSortedMap<u8, u8, Descending> = (1..=3).map(|key| (key, key)).collect();
+///
+/// assert_eq!(Some((&3, &3)), map.first_key_value());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Descending<C = Natural>(PhantomData<C>);
+
+impl<K, C> SortBy<K> for Descending<C>
+where
+    K: ?Sized,
+    C: SortBy<K>,
+{
+    fn cmp(a: &K, b: &K) -> Ordering {
+        C::cmp(b, a)
+    }
+}
+
+/// Sorts string keys while ignoring case, so `"Apple"` and `"apple"` are the same key.
+///
+/// # Example
+/// ```
+/// use defaultdict::{CaseInsensitive, DefaultSortedMap};
+///
+/// let mut map = DefaultSortedMap::<&str, u32, CaseInsensitive>::new();
+/// *map.get_mut(&"Apple") += 1;
+/// *map.get_mut(&"APPLE") += 1;
+/// *map.get_mut(&"banana") += 1;
+///
+/// assert_eq!(&2, map.get(&"apple"));
+/// assert_eq!(vec![&"Apple", &"banana"], map.keys().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CaseInsensitive;
+
+impl<K> SortBy<K> for CaseInsensitive
+where
+    K: AsRef<str> + ?Sized,
+{
+    fn cmp(a: &K, b: &K) -> Ordering {
+        let a = a.as_ref().chars().flat_map(char::to_lowercase);
+        let b = b.as_ref().chars().flat_map(char::to_lowercase);
+        a.cmp(b)
+    }
+}
+
+/// A key stored in a [`DefaultSortedMap`], ordered by `C` instead of its own [`Ord`].
+#[repr(transparent)]
+struct Sorted<K: ?Sized, C> {
+    _by: PhantomData<fn() -> C>,
+    key: K,
+}
+
+impl<K, C> Sorted<K, C> {
+    fn new(key: K) -> Self {
+        Self {
+            _by: PhantomData,
+            key,
+        }
+    }
+}
+
+impl<K: ?Sized, C> Sorted<K, C> {
+    /// Views a borrowed key as a stored key, so the map can be searched without cloning it.
+    fn from_ref(key: &K) -> &Self {
+        // SAFETY: `Sorted` is `repr(transparent)` over `K`, its only other field is a zero-sized
+        // marker, so both types have the same layout.
+        unsafe { &*(key as *const K as *const Self) }
+    }
+}
+
+impl<K: Clone, C> Clone for Sorted<K, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.key.clone())
+    }
+}
+
+impl<K: ?Sized, C: SortBy<K>> PartialEq for Sorted<K, C> {
+    fn eq(&self, other: &Self) -> bool {
+        C::cmp(&self.key, &other.key) == Ordering::Equal
+    }
+}
+
+impl<K: ?Sized, C: SortBy<K>> Eq for Sorted<K, C> {}
+
+impl<K: ?Sized, C: SortBy<K>> PartialOrd for Sorted<K, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: ?Sized, C: SortBy<K>> Ord for Sorted<K, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        C::cmp(&self.key, &other.key)
+    }
+}
+
+/// A sorted map with default values whose keys are ordered by the [`SortBy`] type `C` instead
+/// of their [`Ord`] implementation, without wrapping every key in a newtype.
+///
+/// Keys that compare as equal under `C` share one entry, which keeps the key it was first
+/// inserted with. Without a `C` the map orders its keys like a
+/// [`DefaultBTreeMap`](crate::DefaultBTreeMap).
+///
+/// # Example
+/// ```
+/// use defaultdict::{DefaultSortedMap, Descending};
+///
+/// let mut scores = DefaultSortedMap::<u32, Vec<&str>, Descending>::new();
+/// scores.get_mut(&70).push("ada");
+/// scores.get_mut(&95).push("bob");
+/// scores.get_mut(&82).push("cy");
+///
+/// let ranking: Vec<&u32> = scores.keys().collect();
+///
+/// assert_eq!(vec![&95, &82, &70], ranking);
+/// ```
+pub struct DefaultSortedMap<K, V, C = Natural>
+where
+    C: SortBy<K>,
+    V: Default,
+{
+    _inner: BTreeMap<Sorted<K, C>, V>,
+    _default: V,
+}
+
+impl<K, V, C> DefaultSortedMap<K, V, C>
+where
+    C: SortBy<K>,
+    V: Default,
+{
+    /// Creates an empty [`DefaultSortedMap`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _inner: BTreeMap::new(),
+            _default: V::default(),
+        }
+    }
+
+    /// Removes every key from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self._inner.clear();
+    }
+
+    /// Returns `true` if the map holds a key that is equal to the key passed in under `C`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._inner.contains_key(Sorted::from_ref(key))
+    }
+
+    /// Returns the first entry in the order of `C`, or `None` if the map is empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self._inner
+            .first_key_value()
+            .map(|(sorted, value)| (&sorted.key, value))
+    }
+
+    /// Returns a reference to the value of the key, or the default value if the key is missing.
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._inner
+            .get(Sorted::from_ref(key))
+            .unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value of the key, inserting the default value if the
+    /// key is missing.
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V
+    where
+        K: Clone,
+    {
+        if !self.contains_key(key) {
+            self._inner.insert(Sorted::new(key.clone()), V::default());
+        }
+        self._inner
+            .get_mut(Sorted::from_ref(key))
+            .expect("the key was inserted above")
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if an equal key was
+    /// present. The key that was already present is kept.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self._inner.insert(Sorted::new(key), value)
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._inner.is_empty()
+    }
+
+    /// An iterator over the entries of the map, in the order of `C`.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self._inner
+            .iter()
+            .map(|(sorted, value)| (&sorted.key, value))
+    }
+
+    /// An iterator over the entries of the map with mutable references to the values, in the
+    /// order of `C`.
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (&K, &mut V)> + ExactSizeIterator {
+        self._inner
+            .iter_mut()
+            .map(|(sorted, value)| (&sorted.key, value))
+    }
+
+    /// An iterator over the keys of the map, in the order of `C`.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self._inner.keys().map(|sorted| &sorted.key)
+    }
+
+    /// Returns the last entry in the order of `C`, or `None` if the map is empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self._inner
+            .last_key_value()
+            .map(|(sorted, value)| (&sorted.key, value))
+    }
+
+    /// Returns the number of keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._inner.len()
+    }
+
+    /// An iterator over the entries whose keys fall in the range, in the order of `C`. The bounds
+    /// of the range are compared with `C` as well.
+    ///
+    /// # Panics
+    /// Panics if range `start > end` under `C`. Panics if range `start == end` and both bounds
+    /// are `Excluded`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{CaseInsensitive, DefaultSortedMap};
+    ///
+    /// let map: DefaultSortedMap<&str, u8, CaseInsensitive> =
+    ///     [("Ant", 1), ("bee", 2), ("Cat", 3), ("dog", 4)].into_iter().collect();
+    ///
+    /// let keys: Vec<&&str> = map.range("B".."D").map(|(key, _)| key).collect();
+    ///
+    /// assert_eq!(vec![&"bee", &"Cat"], keys);
+    /// ```
+    pub fn range<R>(&self, range: R) -> impl DoubleEndedIterator<Item = (&K, &V)>
+    where
+        R: RangeBounds<K>,
+    {
+        let bounds = (
+            range.start_bound().map(Sorted::from_ref),
+            range.end_bound().map(Sorted::from_ref),
+        );
+        self._inner
+            .range::<Sorted<K, C>, (Bound<&Sorted<K, C>>, Bound<&Sorted<K, C>>)>(bounds)
+            .map(|(sorted, value)| (&sorted.key, value))
+    }
+
+    /// Removes the key from the map and returns its value, or the default value if the key was
+    /// not present.
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        self._inner
+            .remove(Sorted::from_ref(key))
+            .unwrap_or_default()
+    }
+
+    /// An iterator over the values of the map, in the order of their keys under `C`.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self._inner.values()
+    }
+}
+
+impl<K, V, C> Clone for DefaultSortedMap<K, V, C>
+where
+    K: Clone,
+    V: Default + Clone,
+    C: SortBy<K>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _inner: self._inner.clone(),
+            _default: self._default.clone(),
+        }
+    }
+}
+
+impl<K, V, C> fmt::Debug for DefaultSortedMap<K, V, C>
+where
+    K: fmt::Debug,
+    V: Default + fmt::Debug,
+    C: SortBy<K>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, C> Default for DefaultSortedMap<K, V, C>
+where
+    V: Default,
+    C: SortBy<K>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, C> Extend<(K, V)> for DefaultSortedMap<K, V, C>
+where
+    V: Default,
+    C: SortBy<K>,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self._inner.extend(
+            iter.into_iter()
+                .map(|(key, value)| (Sorted::new(key), value)),
+        );
+    }
+}
+
+impl<K, V, C> FromIterator<(K, V)> for DefaultSortedMap<K, V, C>
+where
+    V: Default,
+    C: SortBy<K>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, C> PartialEq for DefaultSortedMap<K, V, C>
+where
+    V: Default + PartialEq,
+    C: SortBy<K>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self._inner == other._inner && self._default == other._default
+    }
+}
+
+impl<K, V, C> Eq for DefaultSortedMap<K, V, C>
+where
+    V: Default + Eq,
+    C: SortBy<K>,
+{
+}
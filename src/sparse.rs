@@ -0,0 +1,95 @@
+#![deny(missing_docs)]
+
+use std::hash::{BuildHasher, Hash};
+
+use crate::DefaultHashMap;
+
+/// Sparse vector math, treating every key as a dimension. Missing dimensions count as `0.0`, even
+/// if the map has a different default value.
+impl<K, S> DefaultHashMap<K, f64, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Returns the cosine similarity of the two vectors, or `None` if either of them has a length
+    /// of zero.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let a: DefaultHashMap<&str, f64> = [("rust", 1.0), ("map", 1.0)].into_iter().collect();
+    /// let b: DefaultHashMap<&str, f64> = [("rust", 2.0), ("map", 2.0)].into_iter().collect();
+    /// let c: DefaultHashMap<&str, f64> = [("python", 1.0)].into_iter().collect();
+    ///
+    /// assert!((a.cosine_similarity(&b).unwrap() - 1.0).abs() < 1e-12);
+    /// assert_eq!(Some(0.0), a.cosine_similarity(&c));
+    /// assert_eq!(None, a.cosine_similarity(&DefaultHashMap::new()));
+    /// ```
+    pub fn cosine_similarity<T>(&self, other: &DefaultHashMap<K, f64, T>) -> Option<f64>
+    where
+        T: BuildHasher,
+    {
+        let norms = self.norm() * other.norm();
+        (norms != 0.0).then(|| self.dot(other) / norms)
+    }
+
+    /// Returns the dot product of the two vectors.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let a: DefaultHashMap<char, f64> = [('x', 1.0), ('y', 2.0)].into_iter().collect();
+    /// let b: DefaultHashMap<char, f64> = [('y', 3.0), ('z', 4.0)].into_iter().collect();
+    ///
+    /// assert_eq!(6.0, a.dot(&b));
+    /// ```
+    pub fn dot<T>(&self, other: &DefaultHashMap<K, f64, T>) -> f64
+    where
+        T: BuildHasher,
+    {
+        if self.len() <= other.len() {
+            self.into_iter()
+                .filter_map(|(key, a)| other.get_present(key).map(|b| a * b))
+                .sum()
+        } else {
+            other
+                .into_iter()
+                .filter_map(|(key, b)| self.get_present(key).map(|a| a * b))
+                .sum()
+        }
+    }
+
+    /// Returns the euclidean length of the vector.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let v: DefaultHashMap<u8, f64> = [(0, 3.0), (1, 4.0)].into_iter().collect();
+    ///
+    /// assert_eq!(5.0, v.norm());
+    /// ```
+    pub fn norm(&self) -> f64 {
+        self.values().map(|value| value * value).sum::<f64>().sqrt()
+    }
+
+    /// Multiplies every dimension of the vector by `factor`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut v: DefaultHashMap<u8, f64> = [(0, 1.0), (1, -2.0)].into_iter().collect();
+    /// v.scale(0.5);
+    ///
+    /// assert_eq!(&0.5, v.get(&0));
+    /// assert_eq!(&-1.0, v.get(&1));
+    /// ```
+    pub fn scale(&mut self, factor: f64) {
+        for value in self.values_mut() {
+            *value *= factor;
+        }
+    }
+}
@@ -0,0 +1,170 @@
+#![deny(missing_docs)]
+
+use crate::DefaultHashMap;
+
+/// A point in the plane.
+type Point = (f64, f64);
+
+/// Buckets items by the cell of a uniform grid that their position falls into, for quick
+/// neighbor queries such as the broad phase of collision detection or clustering.
+///
+/// Cells are squares with sides of `cell_size`, and every cell holds a [`Vec`] of the items in it.
+/// A query only visits the cells that overlap its radius, so it works best when the cell size is
+/// close to the typical query radius.
+///
+/// # Example
+/// ```
+/// use defaultdict::SpatialDefaultGrid;
+///
+/// let mut grid = SpatialDefaultGrid::new(10.0);
+/// grid.insert((1.0, 1.0), "a");
+/// grid.insert((4.0, 5.0), "b");
+/// grid.insert((50.0, 50.0), "c");
+///
+/// let mut near: Vec<&str> = grid.neighbors((0.0, 0.0), 8.0).map(|(_, item)| *item).collect();
+/// near.sort();
+///
+/// assert_eq!(vec!["a", "b"], near);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpatialDefaultGrid<T> {
+    _cells: DefaultHashMap<(i64, i64), Vec<(Point, T)>>,
+    _cell_size: f64,
+    _len: usize,
+}
+
+impl<T> SpatialDefaultGrid<T> {
+    /// Creates an empty grid with square cells of the given size.
+    ///
+    /// # Panics
+    /// Panics if the cell size is not positive and finite.
+    #[must_use]
+    pub fn new(cell_size: f64) -> Self {
+        assert!(
+            cell_size > 0.0 && cell_size.is_finite(),
+            "the cell size must be positive and finite"
+        );
+        Self {
+            _cells: DefaultHashMap::new(),
+            _cell_size: cell_size,
+            _len: 0,
+        }
+    }
+
+    /// Returns the coordinates of the cell that contains the point.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SpatialDefaultGrid;
+    ///
+    /// let grid = SpatialDefaultGrid::<()>::new(2.0);
+    ///
+    /// assert_eq!((1, -1), grid.cell((3.5, -0.5)));
+    /// ```
+    pub fn cell(&self, point: Point) -> (i64, i64) {
+        (
+            (point.0 / self._cell_size).floor() as i64,
+            (point.1 / self._cell_size).floor() as i64,
+        )
+    }
+
+    /// Returns the items in the cell with the given coordinates, or an empty slice if the cell is
+    /// empty.
+    pub fn cell_items(&self, cell: (i64, i64)) -> &[(Point, T)] {
+        self._cells.get(&cell)
+    }
+
+    /// Returns the size of the cells.
+    #[inline]
+    pub fn cell_size(&self) -> f64 {
+        self._cell_size
+    }
+
+    /// Removes every item from the grid.
+    pub fn clear(&mut self) {
+        self._cells.clear();
+        self._len = 0;
+    }
+
+    /// Adds an item at the given position.
+    pub fn insert(&mut self, point: Point, item: T) {
+        let cell = self.cell(point);
+        self._cells.entry(cell).or_default().push((point, item));
+        self._len += 1;
+    }
+
+    /// Returns `true` if the grid does not contain any items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._len == 0
+    }
+
+    /// An iterator visiting every item with its position, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self._cells
+            .values()
+            .flatten()
+            .map(|(point, item)| (point, item))
+    }
+
+    /// Returns the number of items in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._len
+    }
+
+    /// An iterator visiting the items within `radius` of the point, including the edge, with their
+    /// positions. The order is arbitrary.
+    ///
+    /// The cells overlapping the radius are looked up one by one, unless there are more of them
+    /// than occupied cells, in which case the occupied cells are scanned instead.
+    ///
+    /// # Panics
+    /// Panics if the radius is not finite.
+    pub fn neighbors(&self, point: Point, radius: f64) -> impl Iterator<Item = (&Point, &T)> {
+        assert!(radius.is_finite(), "the radius must be finite");
+        let radius = radius.max(0.0);
+        let (min_x, min_y) = self.cell((point.0 - radius, point.1 - radius));
+        let (max_x, max_y) = self.cell((point.0 + radius, point.1 + radius));
+        let cells = (u128::from(max_x.abs_diff(min_x)) + 1)
+            .saturating_mul(u128::from(max_y.abs_diff(min_y)) + 1);
+        let (visit, scan) = if cells <= self._cells.len() as u128 {
+            let visit = (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)));
+            (Some(visit), None)
+        } else {
+            (None, Some((&self._cells).into_iter()))
+        };
+        visit
+            .into_iter()
+            .flatten()
+            .map(move |cell| self.cell_items(cell))
+            .chain(
+                scan.into_iter()
+                    .flatten()
+                    .filter(move |((x, y), _)| {
+                        (min_x..=max_x).contains(x) && (min_y..=max_y).contains(y)
+                    })
+                    .map(|(_, items)| items.as_slice()),
+            )
+            .flatten()
+            .filter(move |(other, _)| {
+                let (dx, dy) = (other.0 - point.0, other.1 - point.1);
+                dx * dx + dy * dy <= radius * radius
+            })
+            .map(|(point, item)| (point, item))
+    }
+
+    /// Removes the items for which the predicate returns `false`.
+    pub fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&Point, &T) -> bool,
+    {
+        let mut len = 0;
+        self._cells.retain(|_, items| {
+            items.retain(|(point, item)| func(point, item));
+            len += items.len();
+            !items.is_empty()
+        });
+        self._len = len;
+    }
+}
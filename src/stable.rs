@@ -0,0 +1,223 @@
+#![deny(missing_docs)]
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::DefaultHashMap;
+
+/// A map with default values whose values never move once they are inserted, so references to
+/// them stay valid while the map keeps growing.
+///
+/// Every value is stored in its own [`Box`], and keys can be added through a shared reference
+/// with [`DefaultStableMap::get_or_default`] and [`DefaultStableMap::get_or_insert`]. A value is
+/// only replaced or dropped through a mutable reference to the map, which the borrow checker does
+/// not hand out while a shared reference is alive. The map is not [`Sync`].
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultStableMap;
+///
+/// let map = DefaultStableMap::<u32, String>::new();
+///
+/// let first = map.get_or_insert(1, String::from("one"));
+/// for key in 2..1000 {
+///     map.get_or_default(key);
+/// }
+///
+/// assert_eq!("one", first);
+/// assert_eq!(999, map.len());
+/// ```
+pub struct DefaultStableMap<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _inner: RefCell<HashMap<K, Box<V>, S>>,
+    _default: V,
+}
+
+impl<K, V> DefaultStableMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates an empty [`DefaultStableMap`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> DefaultStableMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Creates an empty [`DefaultStableMap`] which will use the given hash builder to hash keys.
+    #[must_use]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            _inner: RefCell::new(HashMap::with_hasher(hash_builder)),
+            _default: V::default(),
+        }
+    }
+
+    /// Removes every key from the map.
+    pub fn clear(&mut self) {
+        self._inner.get_mut().clear();
+    }
+
+    /// Returns `true` if the map holds the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner.borrow().contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key, or the default value if the key is missing.
+    /// The key is not inserted.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultStableMap;
+    ///
+    /// let map = DefaultStableMap::<&str, u32>::new();
+    ///
+    /// assert_eq!(&0, map.get("missing"));
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> &V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value: Option<*const V> = self._inner.borrow().get(key).map(|value| &**value as _);
+        match value {
+            // SAFETY: the value lives in its own box, which is only dropped or replaced through
+            // `&mut self`. The returned reference borrows `self`, so that cannot happen while it
+            // is alive, and growing the map only moves the box pointer, not the value.
+            Some(value) => unsafe { &*value },
+            None => &self._default,
+        }
+    }
+
+    /// Returns a mutable reference to the value of the key, inserting the default value if the
+    /// key is missing.
+    #[must_use]
+    pub fn get_mut(&mut self, key: K) -> &mut V {
+        self._inner.get_mut().entry(key).or_default()
+    }
+
+    /// Returns a reference to the value of the key, inserting the default value through a shared
+    /// reference if the key is missing.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultStableMap;
+    ///
+    /// let map = DefaultStableMap::<u8, Vec<u8>>::new();
+    /// let empty = map.get_or_default(1);
+    ///
+    /// assert!(empty.is_empty());
+    /// assert!(map.contains_key(&1));
+    /// ```
+    pub fn get_or_default(&self, key: K) -> &V {
+        self.get_or_insert_with(key, V::default)
+    }
+
+    /// Returns a reference to the value of the key, inserting `value` through a shared reference
+    /// if the key is missing. A present value is kept and `value` is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultStableMap;
+    ///
+    /// let map = DefaultStableMap::<u8, u8>::new();
+    ///
+    /// assert_eq!(&1, map.get_or_insert(0, 1));
+    /// assert_eq!(&1, map.get_or_insert(0, 2));
+    /// ```
+    pub fn get_or_insert(&self, key: K, value: V) -> &V {
+        self.get_or_insert_with(key, || value)
+    }
+
+    /// Consumes the map and returns its entries in a [`DefaultHashMap`].
+    pub fn into_map(self) -> DefaultHashMap<K, V, S>
+    where
+        S: Clone,
+    {
+        let inner = self._inner.into_inner();
+        let mut map = HashMap::with_capacity_and_hasher(inner.len(), inner.hasher().clone());
+        map.extend(inner.into_iter().map(|(key, value)| (key, *value)));
+        DefaultHashMap::from(map)
+    }
+
+    /// Returns `true` if the map holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self._inner.borrow().is_empty()
+    }
+
+    /// Returns the number of keys in the map.
+    pub fn len(&self) -> usize {
+        self._inner.borrow().len()
+    }
+
+    /// Removes the key and returns its value, or the default value if the key was missing.
+    #[must_use]
+    pub fn remove<Q>(&mut self, key: &Q) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._inner
+            .get_mut()
+            .remove(key)
+            .map_or_else(V::default, |value| *value)
+    }
+
+    fn get_or_insert_with<F>(&self, key: K, make: F) -> &V
+    where
+        F: FnOnce() -> V,
+    {
+        let value: *const V = &**self
+            ._inner
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with(|| Box::new(make()));
+        // SAFETY: see `get`. Inserting other keys later may move the box pointer inside the
+        // table, but never the boxed value itself.
+        unsafe { &*value }
+    }
+}
+
+impl<K, V, S> Default for DefaultStableMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> fmt::Debug for DefaultStableMap<K, V, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultStableMap")
+            .field("_inner", &self._inner)
+            .field("_default", &self._default)
+            .finish()
+    }
+}
@@ -0,0 +1,177 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::DefaultHashMap;
+
+/// Running statistics over a stream of samples: the count, sum, minimum, maximum and mean,
+/// updated in constant time per sample.
+///
+/// # Example
+/// ```
+/// use defaultdict::Stats;
+///
+/// let mut stats = Stats::default();
+/// for sample in [4.0, 1.0, 7.0] {
+///     stats.record(sample);
+/// }
+///
+/// assert_eq!(3, stats.count());
+/// assert_eq!(12.0, stats.sum());
+/// assert_eq!(Some(1.0), stats.min());
+/// assert_eq!(Some(7.0), stats.max());
+/// assert_eq!(Some(4.0), stats.mean());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    _count: u64,
+    _sum: f64,
+    _min: Option<f64>,
+    _max: Option<f64>,
+}
+
+impl Stats {
+    /// Returns the number of recorded samples.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self._count
+    }
+
+    /// Returns the largest recorded sample, or `None` if nothing was recorded.
+    #[inline]
+    pub fn max(&self) -> Option<f64> {
+        self._max
+    }
+
+    /// Returns the mean of the recorded samples, or `None` if nothing was recorded.
+    pub fn mean(&self) -> Option<f64> {
+        (self._count > 0).then(|| self._sum / self._count as f64)
+    }
+
+    /// Combines the samples of another [`Stats`] into this one.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::Stats;
+    ///
+    /// let mut a = Stats::default();
+    /// let mut b = Stats::default();
+    /// a.record(1.0);
+    /// b.record(3.0);
+    ///
+    /// a.merge(&b);
+    ///
+    /// assert_eq!(2, a.count());
+    /// assert_eq!(Some(2.0), a.mean());
+    /// assert_eq!(Some(3.0), a.max());
+    /// ```
+    pub fn merge(&mut self, other: &Stats) {
+        self._count += other._count;
+        self._sum += other._sum;
+        self._min = min(self._min, other._min);
+        self._max = max(self._max, other._max);
+    }
+
+    /// Returns the smallest recorded sample, or `None` if nothing was recorded.
+    #[inline]
+    pub fn min(&self) -> Option<f64> {
+        self._min
+    }
+
+    /// Adds a sample. `NaN` samples are ignored.
+    pub fn record(&mut self, sample: f64) {
+        if sample.is_nan() {
+            return;
+        }
+        self._count += 1;
+        self._sum += sample;
+        self._min = min(self._min, Some(sample));
+        self._max = max(self._max, Some(sample));
+    }
+
+    /// Returns the sum of the recorded samples.
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        self._sum
+    }
+}
+
+fn min(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn max(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// A [`DefaultHashMap`] that keeps running [`Stats`] per key, for example the latencies per
+/// endpoint.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultStatsMap;
+///
+/// let mut latencies = DefaultStatsMap::new();
+/// latencies.record("/users", 12.0);
+/// latencies.record("/users", 18.0);
+/// latencies.record("/login", 40.0);
+///
+/// assert_eq!(Some(15.0), latencies.get(&"/users").mean());
+/// assert_eq!(0, latencies.get(&"/health").count());
+/// ```
+pub type DefaultStatsMap<K, S = RandomState> = DefaultHashMap<K, Stats, S>;
+
+/// Recording samples into maps of [`Stats`].
+impl<K, S> DefaultHashMap<K, Stats, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Adds the stats of every key in `other` to the stats of the same key in this map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultStatsMap;
+    ///
+    /// let mut a = DefaultStatsMap::new();
+    /// let mut b = DefaultStatsMap::new();
+    /// a.record("x", 1.0);
+    /// b.record("x", 5.0);
+    /// b.record("y", 2.0);
+    ///
+    /// a.merge_stats(&b);
+    ///
+    /// assert_eq!(Some(5.0), a.get(&"x").max());
+    /// assert_eq!(1, a.get(&"y").count());
+    /// ```
+    pub fn merge_stats(&mut self, other: &DefaultHashMap<K, Stats, S>)
+    where
+        K: Clone,
+    {
+        for (key, stats) in other {
+            self.entry(key.clone()).or_default().merge(stats);
+        }
+    }
+
+    /// Adds a sample to the stats of the key.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultStatsMap;
+    ///
+    /// let mut map = DefaultStatsMap::new();
+    /// map.record(1, 2.5);
+    ///
+    /// assert_eq!(2.5, map.get(&1).sum());
+    /// ```
+    pub fn record(&mut self, key: K, sample: f64) {
+        self.entry(key).or_default().record(sample);
+    }
+}
@@ -0,0 +1,172 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::DefaultHashMap;
+
+/// A read-mostly [`DefaultHashMap`] that can be shared between threads. This requires the
+/// `arc-swap` feature.
+///
+/// Readers load the current version of the map through
+/// [arc-swap](https://crates.io/crates/arc-swap) without taking a lock, and keep reading from that
+/// immutable snapshot for as long as they hold it. A writer builds the next version from a copy
+/// of the current one and publishes it atomically, so readers never observe a partial update.
+/// Missing keys still read as the default value.
+///
+/// Every write copies the map, so this suits tables that are read far more often than written.
+///
+/// # Example
+/// ```
+/// use defaultdict::SwapDefaultMap;
+///
+/// let map = SwapDefaultMap::<&str, u32>::new();
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| map.update(|next| *next.get_mut(&"a") += 1));
+///     scope.spawn(|| map.update(|next| *next.get_mut(&"a") += 1));
+/// });
+///
+/// assert_eq!(2, map.get_cloned(&"a"));
+/// assert_eq!(0, map.get_cloned(&"b"));
+/// ```
+#[derive(Debug)]
+pub struct SwapDefaultMap<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    _current: ArcSwap<DefaultHashMap<K, V, S>>,
+}
+
+impl<K, V> SwapDefaultMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    /// Creates a [`SwapDefaultMap`] that publishes an empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_map(DefaultHashMap::new())
+    }
+}
+
+impl<K, V, S> SwapDefaultMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Creates a [`SwapDefaultMap`] that publishes the given map.
+    #[must_use]
+    pub fn from_map(map: DefaultHashMap<K, V, S>) -> Self {
+        Self {
+            _current: ArcSwap::from_pointee(map),
+        }
+    }
+
+    /// Returns `true` if the key exists in the current version of the map.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._current.load().contains_key(key)
+    }
+
+    /// Returns a copy of the value of the key in the current version of the map, or the default
+    /// value if the key is not present.
+    #[must_use]
+    pub fn get_cloned(&self, key: &K) -> V
+    where
+        V: Clone,
+    {
+        self._current.load().get(key).clone()
+    }
+
+    /// Consumes the wrapper and returns the current version of the map.
+    pub fn into_inner(self) -> Arc<DefaultHashMap<K, V, S>> {
+        self._current.into_inner()
+    }
+
+    /// Replaces the current version of the map with the given map and returns the previous one.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap, SwapDefaultMap};
+    ///
+    /// let map = SwapDefaultMap::<i8, i8>::new();
+    /// let previous = map.publish(defaulthashmap!((1, 1)));
+    ///
+    /// assert!(previous.is_empty());
+    /// assert_eq!(1, map.get_cloned(&1));
+    /// ```
+    pub fn publish(&self, map: DefaultHashMap<K, V, S>) -> Arc<DefaultHashMap<K, V, S>> {
+        self._current.swap(Arc::new(map))
+    }
+
+    /// Runs `func` on the current version of the map. This is the cheapest way to read, but the
+    /// version is kept alive until `func` returns, so it should not block.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap, SwapDefaultMap};
+    ///
+    /// let map = SwapDefaultMap::from_map(defaulthashmap!((1, 2), (2, 3)));
+    ///
+    /// assert_eq!(5, map.read(|current| current.values().sum::<i32>()));
+    /// ```
+    pub fn read<F, T>(&self, func: F) -> T
+    where
+        F: FnOnce(&DefaultHashMap<K, V, S>) -> T,
+    {
+        func(&self._current.load())
+    }
+
+    /// Returns the current version of the map. The snapshot does not change when newer versions
+    /// are published.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::SwapDefaultMap;
+    ///
+    /// let map = SwapDefaultMap::<i8, i8>::new();
+    /// let before = map.snapshot();
+    /// map.update(|next| {
+    ///     next.insert(1, 1);
+    /// });
+    ///
+    /// assert!(before.is_empty());
+    /// assert_eq!(1, map.snapshot().len());
+    /// ```
+    pub fn snapshot(&self) -> Arc<DefaultHashMap<K, V, S>> {
+        self._current.load_full()
+    }
+
+    /// Builds the next version of the map by running `func` on a copy of the current version, and
+    /// publishes it. If another writer published a version in the meantime, `func` is run again
+    /// on a copy of that version, so no update is lost.
+    pub fn update<F>(&self, mut func: F)
+    where
+        K: Clone,
+        V: Clone,
+        S: Clone,
+        F: FnMut(&mut DefaultHashMap<K, V, S>),
+    {
+        self._current.rcu(|current| {
+            let mut next = DefaultHashMap::clone(current);
+            func(&mut next);
+            next
+        });
+    }
+}
+
+impl<K, V> Default for SwapDefaultMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
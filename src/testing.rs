@@ -0,0 +1,333 @@
+//! Model-based testing for maps with default values. This requires the `testing` feature.
+//!
+//! [`ModelMap`] is a deliberately simple reference implementation of the defaultdict semantics,
+//! backed by a vector of pairs. [`check_equivalence`] generates random sequences of [`MapOp`]s
+//! with [proptest](https://crates.io/crates/proptest), runs them against both the model and any
+//! map that implements [`DefaultMap`], and fails as soon as the two disagree.
+//!
+//! # Example
+//! ```
+//! use defaultdict::testing::check_equivalence;
+//! use defaultdict::DefaultHashMap;
+//!
+//! check_equivalence(DefaultHashMap::<u8, i32>::new, 0..8u8, -10..10i32);
+//! ```
+#![deny(missing_docs)]
+
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+
+use proptest::collection::vec;
+use proptest::prelude::{prop_oneof, Just, Strategy};
+use proptest::test_runner::{TestCaseError, TestRunner};
+use proptest::{prop_assert, prop_assert_eq};
+
+use crate::{DefaultBTreeMap, DefaultHashMap};
+
+/// The operations every map with default values supports, used to drive the model-based tests.
+pub trait DefaultMap<K, V> {
+    /// Removes every key from the map.
+    fn clear(&mut self);
+
+    /// Returns `true` if the map holds the key.
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Returns the value of the key, or the default value if the key is missing. This does not
+    /// insert the key.
+    fn get(&self, key: &K) -> &V;
+
+    /// Returns a mutable reference to the value of the key, inserting the default value if the
+    /// key is missing.
+    fn get_mut(&mut self, key: &K) -> &mut V;
+
+    /// Inserts a value for the key, replacing the previous value.
+    fn insert(&mut self, key: K, value: V);
+
+    /// Returns `true` if the map holds no keys.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of keys in the map.
+    fn len(&self) -> usize;
+
+    /// Removes the key and returns its value, or the default value if the key was missing.
+    fn remove(&mut self, key: &K) -> V;
+}
+
+impl<K, V, S> DefaultMap<K, V> for DefaultHashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default,
+    S: BuildHasher,
+{
+    #[inline]
+    fn clear(&mut self) {
+        DefaultHashMap::clear(self);
+    }
+
+    #[inline]
+    fn contains_key(&self, key: &K) -> bool {
+        DefaultHashMap::contains_key(self, key)
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> &V {
+        DefaultHashMap::get(self, key)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &K) -> &mut V {
+        DefaultHashMap::get_mut(self, key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        DefaultHashMap::insert(self, key, value);
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        DefaultHashMap::len(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> V {
+        DefaultHashMap::remove(self, key)
+    }
+}
+
+impl<K, V> DefaultMap<K, V> for DefaultBTreeMap<K, V>
+where
+    K: Eq + Ord + Clone,
+    V: Default,
+{
+    #[inline]
+    fn clear(&mut self) {
+        DefaultBTreeMap::clear(self);
+    }
+
+    #[inline]
+    fn contains_key(&self, key: &K) -> bool {
+        DefaultBTreeMap::contains_key(self, key)
+    }
+
+    #[inline]
+    fn get(&self, key: &K) -> &V {
+        DefaultBTreeMap::get(self, key)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: &K) -> &mut V {
+        DefaultBTreeMap::get_mut(self, key)
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) {
+        DefaultBTreeMap::insert(self, key, value);
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        DefaultBTreeMap::len(self)
+    }
+
+    #[inline]
+    fn remove(&mut self, key: &K) -> V {
+        DefaultBTreeMap::remove(self, key)
+    }
+}
+
+/// A reference model of a map with default values: a vector of pairs that is searched linearly.
+/// It is slow on purpose, so that its behavior is obvious from reading the code.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelMap<K, V> {
+    _entries: Vec<(K, V)>,
+    _default: V,
+}
+
+impl<K, V> ModelMap<K, V>
+where
+    V: Default,
+{
+    /// Creates an empty model.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _entries: Vec::new(),
+            _default: V::default(),
+        }
+    }
+
+    /// Returns the pairs of the model in insertion order.
+    #[inline]
+    pub fn entries(&self) -> &[(K, V)] {
+        &self._entries
+    }
+
+    fn position(&self, key: &K) -> Option<usize>
+    where
+        K: PartialEq,
+    {
+        self._entries.iter().position(|(k, _)| k == key)
+    }
+}
+
+impl<K, V> DefaultMap<K, V> for ModelMap<K, V>
+where
+    K: PartialEq + Clone,
+    V: Default,
+{
+    fn clear(&mut self) {
+        self._entries.clear();
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.position(key).is_some()
+    }
+
+    fn get(&self, key: &K) -> &V {
+        match self.position(key) {
+            Some(index) => &self._entries[index].1,
+            None => &self._default,
+        }
+    }
+
+    fn get_mut(&mut self, key: &K) -> &mut V {
+        let index = match self.position(key) {
+            Some(index) => index,
+            None => {
+                self._entries.push((key.clone(), V::default()));
+                self._entries.len() - 1
+            }
+        };
+        &mut self._entries[index].1
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        match self.position(&key) {
+            Some(index) => self._entries[index].1 = value,
+            None => self._entries.push((key, value)),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self._entries.len()
+    }
+
+    fn remove(&mut self, key: &K) -> V {
+        match self.position(key) {
+            Some(index) => self._entries.remove(index).1,
+            None => V::default(),
+        }
+    }
+}
+
+/// A single operation on a [`DefaultMap`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapOp<K, V> {
+    /// Calls [`DefaultMap::clear`].
+    Clear,
+    /// Calls [`DefaultMap::contains_key`].
+    ContainsKey(K),
+    /// Calls [`DefaultMap::get`].
+    Get(K),
+    /// Calls [`DefaultMap::get_mut`] and writes the value through the returned reference.
+    GetMut(K, V),
+    /// Calls [`DefaultMap::insert`].
+    Insert(K, V),
+    /// Calls [`DefaultMap::len`].
+    Len,
+    /// Calls [`DefaultMap::remove`].
+    Remove(K),
+}
+
+/// A strategy that generates sequences of up to `max_len` operations, drawing keys and values
+/// from the given strategies. Small key strategies give more collisions and so better tests.
+pub fn map_ops<K, V>(
+    keys: impl Strategy<Value = K> + Clone,
+    values: impl Strategy<Value = V> + Clone,
+    max_len: usize,
+) -> impl Strategy<Value = Vec<MapOp<K, V>>>
+where
+    K: Clone + Debug,
+    V: Clone + Debug,
+{
+    let op = prop_oneof![
+        1 => Just(MapOp::Clear),
+        3 => keys.clone().prop_map(MapOp::ContainsKey),
+        4 => keys.clone().prop_map(MapOp::Get),
+        4 => (keys.clone(), values.clone()).prop_map(|(k, v)| MapOp::GetMut(k, v)),
+        6 => (keys.clone(), values).prop_map(|(k, v)| MapOp::Insert(k, v)),
+        1 => Just(MapOp::Len),
+        3 => keys.prop_map(MapOp::Remove),
+    ];
+    vec(op, 0..=max_len)
+}
+
+/// Runs the operations against both `map` and a fresh [`ModelMap`], failing at the first
+/// operation where the results or the lengths disagree. At the end every key the model holds
+/// must hold the same value in the map.
+///
+/// # Errors
+/// Returns a test case failure describing the first difference.
+pub fn check_against_model<M, K, V>(mut map: M, ops: Vec<MapOp<K, V>>) -> Result<(), TestCaseError>
+where
+    M: DefaultMap<K, V>,
+    K: PartialEq + Clone + Debug,
+    V: Default + PartialEq + Clone + Debug,
+{
+    let mut model = ModelMap::new();
+    for op in ops {
+        match op {
+            MapOp::Clear => {
+                map.clear();
+                model.clear();
+            }
+            MapOp::ContainsKey(key) => {
+                prop_assert_eq!(model.contains_key(&key), map.contains_key(&key));
+            }
+            MapOp::Get(key) => prop_assert_eq!(model.get(&key), map.get(&key)),
+            MapOp::GetMut(key, value) => {
+                prop_assert_eq!(&*model.get_mut(&key), &*map.get_mut(&key));
+                *model.get_mut(&key) = value.clone();
+                *map.get_mut(&key) = value;
+            }
+            MapOp::Insert(key, value) => {
+                model.insert(key.clone(), value.clone());
+                map.insert(key, value);
+            }
+            MapOp::Len => prop_assert_eq!(model.len(), map.len()),
+            MapOp::Remove(key) => prop_assert_eq!(model.remove(&key), map.remove(&key)),
+        }
+        prop_assert_eq!(model.len(), map.len());
+    }
+    for (key, value) in model.entries() {
+        prop_assert!(map.contains_key(key), "the map lost the key {:?}", key);
+        prop_assert_eq!(value, map.get(key));
+    }
+    Ok(())
+}
+
+/// Checks that the maps built by `new_map` behave like [`ModelMap`] for random sequences of
+/// operations on keys and values drawn from the given strategies.
+///
+/// # Panics
+/// Panics with the smallest failing sequence of operations if a map disagrees with the model.
+pub fn check_equivalence<M, F, K, V>(
+    new_map: F,
+    keys: impl Strategy<Value = K> + Clone,
+    values: impl Strategy<Value = V> + Clone,
+) where
+    M: DefaultMap<K, V>,
+    F: Fn() -> M,
+    K: PartialEq + Clone + Debug,
+    V: Default + PartialEq + Clone + Debug,
+{
+    let mut runner = TestRunner::default();
+    if let Err(error) = runner.run(&map_ops(keys, values, 64), |ops| {
+        check_against_model(new_map(), ops)
+    }) {
+        panic!("the map disagrees with the model: {}", error);
+    }
+}
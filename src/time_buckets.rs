@@ -0,0 +1,196 @@
+#![deny(missing_docs)]
+
+use crate::DefaultBTreeMap;
+
+/// Groups timestamped data into fixed-width time buckets, stored in a [`DefaultBTreeMap`] from
+/// the start of each bucket to its value.
+///
+/// Timestamps are plain integers in whatever unit the caller uses, for example seconds or
+/// milliseconds since the Unix epoch. Bucket `b` covers `[b, b + width)`, where `b` is a multiple
+/// of the width. The contiguous iterators also visit the buckets without data, which then read
+/// as the default value.
+///
+/// # Example
+/// ```
+/// use defaultdict::TimeBuckets;
+///
+/// let events = [(0, "start"), (59, "tick"), (185, "stop")];
+/// let minutes = TimeBuckets::from_items(60, events);
+///
+/// let buckets: Vec<(i64, &Vec<&str>)> = minutes.iter_contiguous().collect();
+///
+/// assert_eq!(
+///     vec![
+///         (0, &vec!["start", "tick"]),
+///         (60, &vec![]),
+///         (120, &vec![]),
+///         (180, &vec!["stop"]),
+///     ],
+///     buckets
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeBuckets<V>
+where
+    V: Default,
+{
+    _buckets: DefaultBTreeMap<i64, V>,
+    _width: i64,
+}
+
+impl<V> TimeBuckets<V>
+where
+    V: Default,
+{
+    /// Creates an empty set of buckets of the given width.
+    ///
+    /// # Panics
+    /// Panics if the width is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::TimeBuckets;
+    ///
+    /// let mut requests = TimeBuckets::<u32>::new(10);
+    /// *requests.bucket_mut(14) += 1;
+    /// *requests.bucket_mut(19) += 1;
+    ///
+    /// assert_eq!(&2, requests.get(10));
+    /// ```
+    #[must_use]
+    pub fn new(width: i64) -> Self {
+        assert!(width > 0, "the bucket width must be positive");
+        Self {
+            _buckets: DefaultBTreeMap::new(),
+            _width: width,
+        }
+    }
+
+    /// Returns the start of the bucket that contains the timestamp. The lowest bucket starts at
+    /// [`i64::MIN`] when the multiple of the width below it does not fit in an `i64`.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::TimeBuckets;
+    ///
+    /// let buckets = TimeBuckets::<u8>::new(60);
+    ///
+    /// assert_eq!(120, buckets.bucket_start(150));
+    /// assert_eq!(-60, buckets.bucket_start(-1));
+    /// ```
+    #[inline]
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        Self::start_of(timestamp.div_euclid(self._width), self._width)
+    }
+
+    /// Returns a mutable reference to the value of the bucket that contains the timestamp,
+    /// inserting the default value if the bucket is empty.
+    #[must_use]
+    pub fn bucket_mut(&mut self, timestamp: i64) -> &mut V {
+        let start = self.bucket_start(timestamp);
+        self._buckets.get_mut(&start)
+    }
+
+    /// Returns the buckets that hold data, keyed by their start.
+    #[inline]
+    pub fn buckets(&self) -> &DefaultBTreeMap<i64, V> {
+        &self._buckets
+    }
+
+    /// Returns the value of the bucket that contains the timestamp, or the default value if the
+    /// bucket is empty.
+    #[must_use]
+    pub fn get(&self, timestamp: i64) -> &V {
+        self._buckets.get(&self.bucket_start(timestamp))
+    }
+
+    /// Consumes the buckets and returns the underlying map.
+    #[inline]
+    pub fn into_buckets(self) -> DefaultBTreeMap<i64, V> {
+        self._buckets
+    }
+
+    /// An iterator over every bucket from the first to the last bucket that holds data, including
+    /// the empty buckets in between.
+    pub fn iter_contiguous(&self) -> impl Iterator<Item = (i64, &V)> {
+        let first = self._buckets.first_key_value().map(|(start, _)| *start);
+        let last = self._buckets.last_key_value().map(|(start, _)| *start);
+        let (first, last) = match (first, last) {
+            (Some(first), Some(last)) => {
+                (first.div_euclid(self._width), last.div_euclid(self._width))
+            }
+            _ => (1, 0),
+        };
+        self.iter_indices(first, last)
+    }
+
+    /// An iterator over every bucket that overlaps `[from, to)`, including empty ones.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::TimeBuckets;
+    ///
+    /// let mut counts = TimeBuckets::<u32>::new(10);
+    /// *counts.bucket_mut(25) += 1;
+    ///
+    /// let buckets: Vec<(i64, &u32)> = counts.iter_range(0, 40).collect();
+    ///
+    /// assert_eq!(vec![(0, &0), (10, &0), (20, &1), (30, &0)], buckets);
+    /// ```
+    pub fn iter_range(&self, from: i64, to: i64) -> impl Iterator<Item = (i64, &V)> {
+        let (first, last) = if to > from {
+            (
+                from.div_euclid(self._width),
+                (to - 1).div_euclid(self._width),
+            )
+        } else {
+            (1, 0)
+        };
+        self.iter_indices(first, last)
+    }
+
+    /// Returns the width of the buckets.
+    #[inline]
+    pub fn width(&self) -> i64 {
+        self._width
+    }
+
+    /// An iterator over the buckets with the indices `first..=last`, where bucket `i` starts at
+    /// `i * width`.
+    fn iter_indices(&self, first: i64, last: i64) -> impl Iterator<Item = (i64, &V)> {
+        let width = self._width;
+        (first..=last).map(move |index| {
+            let start = Self::start_of(index, width);
+            (start, self._buckets.get(&start))
+        })
+    }
+
+    /// Returns the start of the bucket with the given index, saturating for the lowest bucket.
+    #[inline]
+    fn start_of(index: i64, width: i64) -> i64 {
+        index.saturating_mul(width)
+    }
+}
+
+impl<T> TimeBuckets<Vec<T>> {
+    /// Collects timestamped items into buckets of the given width. Items in the same bucket keep
+    /// their order.
+    ///
+    /// # Panics
+    /// Panics if the width is not positive.
+    pub fn from_items<I>(width: i64, items: I) -> Self
+    where
+        I: IntoIterator<Item = (i64, T)>,
+    {
+        let mut buckets = Self::new(width);
+        for (timestamp, item) in items {
+            buckets.push(timestamp, item);
+        }
+        buckets
+    }
+
+    /// Adds an item to the bucket that contains the timestamp.
+    pub fn push(&mut self, timestamp: i64, item: T) {
+        self.bucket_mut(timestamp).push(item);
+    }
+}
@@ -0,0 +1,261 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::{HashMap, RandomState};
+use std::hash::{BuildHasher, Hash};
+
+use crate::DefaultHashMap;
+
+/// A guard that groups mutations of a [`DefaultHashMap`] so they can be undone as a whole. This is
+/// created by calling [`DefaultHashMap::transaction`].
+///
+/// The first time a key is touched its original state is recorded. Calling
+/// [`Transaction::commit`] keeps every change, calling [`Transaction::rollback`] restores the
+/// recorded state. Dropping the guard without committing also rolls back.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultHashMap;
+///
+/// let mut map = DefaultHashMap::<i8, i8>::new();
+/// map.insert(1, 1);
+///
+/// let mut tx = map.transaction();
+/// tx.insert(2, 2);
+/// *tx.get_mut(&1) += 10;
+/// tx.rollback();
+///
+/// assert_eq!(&1, map.get(&1));
+/// assert!(!map.contains_key(&2));
+///
+/// let mut tx = map.transaction();
+/// tx.insert(2, 2);
+/// tx.commit();
+///
+/// assert_eq!(&2, map.get(&2));
+/// ```
+pub struct Transaction<'a, K, V, S = RandomState>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+    S: BuildHasher,
+{
+    _map: &'a mut DefaultHashMap<K, V, S>,
+    _undo: HashMap<K, Option<V>>,
+}
+
+impl<'a, K, V, S> Transaction<'a, K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+    S: BuildHasher,
+{
+    /// Starts a transaction on the given map.
+    pub(crate) fn new(map: &'a mut DefaultHashMap<K, V, S>) -> Self {
+        Self {
+            _map: map,
+            _undo: HashMap::new(),
+        }
+    }
+
+    /// Keeps every change made through this transaction.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// let mut tx = map.transaction();
+    /// let _ = tx.remove(&1);
+    /// tx.insert(1, 2);
+    /// tx.commit();
+    ///
+    /// assert_eq!(&2, map.get(&1));
+    /// ```
+    pub fn commit(mut self) {
+        self._undo.clear();
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// let mut tx = map.transaction();
+    /// tx.insert(1, 2);
+    ///
+    /// assert!(tx.contains_key(&1));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._map.contains_key(key)
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present. Changes made earlier in the transaction are visible.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// let mut tx = map.transaction();
+    /// tx.insert(1, 2);
+    ///
+    /// assert_eq!(&2, tx.get(&1));
+    /// assert_eq!(&0, tx.get(&2));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._map.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if the key is not present. The value is restored on rollback.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// let mut tx = map.transaction();
+    /// *tx.get_mut(&1) += 1;
+    /// tx.rollback();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        self.record(key);
+        self._map.get_mut(key)
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if there was one. The
+    /// previous state is restored on rollback.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    ///
+    /// let mut tx = map.transaction();
+    ///
+    /// assert_eq!(Some(1), tx.insert(1, 2));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.record(&key);
+        self._map.insert(key, value)
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// let tx = map.transaction();
+    ///
+    /// assert!(tx.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._map.is_empty()
+    }
+
+    /// Returns the length of the keys in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// let mut tx = map.transaction();
+    /// tx.insert(1, 1);
+    ///
+    /// assert_eq!(1, tx.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._map.len()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise. The key is restored on rollback.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    ///
+    /// let mut tx = map.transaction();
+    ///
+    /// assert_eq!(1, tx.remove(&1));
+    /// tx.rollback();
+    ///
+    /// assert_eq!(&1, map.get(&1));
+    /// ```
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        self.record(key);
+        self._map.remove(key)
+    }
+
+    /// Undoes every change made through this transaction.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    ///
+    /// let mut tx = map.transaction();
+    /// tx.insert(1, 1);
+    /// tx.rollback();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn rollback(self) {}
+
+    /// Records the original state of a key the first time it is touched.
+    fn record(&mut self, key: &K) {
+        if !self._undo.contains_key(key) {
+            let original = if self._map.contains_key(key) {
+                Some(self._map.get(key).clone())
+            } else {
+                None
+            };
+            self._undo.insert(key.clone(), original);
+        }
+    }
+}
+
+impl<K, V, S> Drop for Transaction<'_, K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+    S: BuildHasher,
+{
+    fn drop(&mut self) {
+        for (key, original) in self._undo.drain() {
+            match original {
+                Some(value) => {
+                    self._map.insert(key, value);
+                }
+                None => {
+                    let _ = self._map.remove(&key);
+                }
+            }
+        }
+    }
+}
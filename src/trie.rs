@@ -0,0 +1,439 @@
+#![deny(missing_docs)]
+
+use std::fmt;
+use std::ops::Index;
+
+/// A node of the radix tree. The children are sorted by the first byte of their label, and no
+/// two children share a first byte.
+#[derive(Clone, Eq, PartialEq)]
+struct Node<V> {
+    value: Option<V>,
+    children: Vec<Edge<V>>,
+}
+
+/// A labeled edge from a node to one of its children.
+#[derive(Clone, Eq, PartialEq)]
+struct Edge<V> {
+    label: Box<[u8]>,
+    node: Node<V>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Self {
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the position of the child whose label starts with the byte.
+    fn child(&self, byte: u8) -> Result<usize, usize> {
+        self.children
+            .binary_search_by_key(&byte, |edge| edge.label[0])
+    }
+
+    fn find(&self, key: &[u8]) -> Option<&Node<V>> {
+        let mut node = self;
+        let mut rest = key;
+        while let Some(&byte) = rest.first() {
+            let edge = &node.children[node.child(byte).ok()?];
+            rest = rest.strip_prefix(&*edge.label)?;
+            node = &edge.node;
+        }
+        Some(node)
+    }
+
+    fn find_mut(&mut self, key: &[u8]) -> Option<&mut Node<V>> {
+        let mut node = self;
+        let mut rest = key;
+        while let Some(&byte) = rest.first() {
+            let index = node.child(byte).ok()?;
+            let edge = &mut node.children[index];
+            rest = rest.strip_prefix(&*edge.label)?;
+            node = &mut edge.node;
+        }
+        Some(node)
+    }
+
+    /// Returns the node for the key, creating it and splitting edges where needed.
+    fn find_or_create(&mut self, key: &[u8]) -> &mut Node<V> {
+        let Some(&byte) = key.first() else {
+            return self;
+        };
+        match self.child(byte) {
+            Ok(index) => {
+                let edge = &mut self.children[index];
+                let common = edge
+                    .label
+                    .iter()
+                    .zip(key)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if common < edge.label.len() {
+                    let node = std::mem::replace(&mut edge.node, Node::new());
+                    edge.node.children.push(Edge {
+                        label: edge.label[common..].into(),
+                        node,
+                    });
+                    edge.label = edge.label[..common].into();
+                }
+                edge.node.find_or_create(&key[common..])
+            }
+            Err(index) => {
+                self.children.insert(
+                    index,
+                    Edge {
+                        label: key.into(),
+                        node: Node::new(),
+                    },
+                );
+                &mut self.children[index].node
+            }
+        }
+    }
+
+    /// Removes the value of the key, pruning and merging the nodes that become redundant.
+    fn remove(&mut self, key: &[u8]) -> Option<V> {
+        let Some(&byte) = key.first() else {
+            return self.value.take();
+        };
+        let index = self.child(byte).ok()?;
+        let edge = &mut self.children[index];
+        let removed = edge.node.remove(key.strip_prefix(&*edge.label)?)?;
+        if edge.node.value.is_none() {
+            match edge.node.children.len() {
+                0 => {
+                    self.children.remove(index);
+                }
+                1 => {
+                    let child = edge.node.children.remove(0);
+                    edge.label = [&*edge.label, &*child.label].concat().into();
+                    edge.node = child.node;
+                }
+                _ => {}
+            }
+        }
+        Some(removed)
+    }
+}
+
+/// A depth-first iterator over the values below a node, in lexicographic key order.
+struct Iter<'a, V> {
+    stack: Vec<(Vec<u8>, &'a Node<V>)>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            for edge in node.children.iter().rev() {
+                self.stack
+                    .push(([&*key, &*edge.label].concat(), &edge.node));
+            }
+            if let Some(value) = &node.value {
+                let key = String::from_utf8(key).expect("keys are inserted as strings");
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+/// A default map for string keys that stores them in a radix tree, so keys that share a prefix
+/// share the storage of that prefix.
+///
+/// Besides the usual lookups it can visit every key that starts with a given prefix without
+/// looking at the rest of the map, which suits URL and path keyed data. Iteration yields owned
+/// keys in lexicographic byte order.
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultTrieMap;
+///
+/// let mut hits = DefaultTrieMap::<u32>::new();
+/// *hits.get_mut("/api/users") += 1;
+/// *hits.get_mut("/api/users/1") += 1;
+/// *hits.get_mut("/static/app.js") += 1;
+///
+/// let api: Vec<(String, &u32)> = hits.iter_prefix("/api").collect();
+///
+/// assert_eq!(
+///     vec![("/api/users".to_string(), &1), ("/api/users/1".to_string(), &1)],
+///     api
+/// );
+/// assert_eq!(&0, hits.get("/api"));
+/// ```
+#[derive(Clone, Eq, PartialEq)]
+pub struct DefaultTrieMap<V>
+where
+    V: Default,
+{
+    _root: Node<V>,
+    _len: usize,
+    _default: V,
+}
+
+impl<V> DefaultTrieMap<V>
+where
+    V: Default,
+{
+    /// Creates an empty [`DefaultTrieMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let map = DefaultTrieMap::<i8>::new();
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _root: Node::new(),
+            _len: 0,
+            _default: V::default(),
+        }
+    }
+
+    /// Removes all keys from the map.
+    pub fn clear(&mut self) {
+        self._root = Node::new();
+        self._len = 0;
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::new();
+    /// map.insert("ab", 1);
+    ///
+    /// assert!(map.contains_key("ab"));
+    /// assert!(!map.contains_key("a"));
+    /// ```
+    pub fn contains_key(&self, key: &str) -> bool {
+        self._root
+            .find(key.as_bytes())
+            .is_some_and(|node| node.value.is_some())
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::new();
+    /// map.insert("key", 1);
+    ///
+    /// assert_eq!(&1, map.get("key"));
+    /// assert_eq!(&0, map.get("ke"));
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: &str) -> &V {
+        self._root
+            .find(key.as_bytes())
+            .and_then(|node| node.value.as_ref())
+            .unwrap_or(&self._default)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key. If the key is not
+    /// present it is inserted with the default value.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::<Vec<u8>>::new();
+    /// map.get_mut("key").push(1);
+    ///
+    /// assert_eq!(&vec![1], map.get("key"));
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, key: &str) -> &mut V {
+        let node = self._root.find_or_create(key.as_bytes());
+        if node.value.is_none() {
+            self._len += 1;
+        }
+        node.value.get_or_insert_with(V::default)
+    }
+
+    /// Inserts a key value pair into the map, returning the previous value if the key was
+    /// present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::new();
+    ///
+    /// assert_eq!(None, map.insert("key", 1));
+    /// assert_eq!(Some(1), map.insert("key", 2));
+    /// ```
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        let previous = self
+            ._root
+            .find_or_create(key.as_bytes())
+            .value
+            .replace(value);
+        if previous.is_none() {
+            self._len += 1;
+        }
+        previous
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._len == 0
+    }
+
+    /// An iterator visiting all key-value pairs in lexicographic key order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::new();
+    /// map.insert("b", 2);
+    /// map.insert("a", 1);
+    ///
+    /// let keys: Vec<String> = map.iter().map(|(key, _)| key).collect();
+    ///
+    /// assert_eq!(vec!["a", "b"], keys);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (String, &V)> {
+        Iter {
+            stack: vec![(Vec::new(), &self._root)],
+        }
+    }
+
+    /// An iterator visiting the key-value pairs whose key starts with the prefix, in lexicographic
+    /// key order. Only the part of the tree below the prefix is visited.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::new();
+    /// map.insert("car", 1);
+    /// map.insert("cart", 2);
+    /// map.insert("cat", 3);
+    ///
+    /// let keys: Vec<String> = map.iter_prefix("car").map(|(key, _)| key).collect();
+    ///
+    /// assert_eq!(vec!["car", "cart"], keys);
+    /// ```
+    pub fn iter_prefix(&self, prefix: &str) -> impl Iterator<Item = (String, &V)> {
+        let mut node = &self._root;
+        let mut key = Vec::new();
+        let mut rest = prefix.as_bytes();
+        let mut stack = Vec::new();
+        loop {
+            let Some(&byte) = rest.first() else {
+                stack.push((key, node));
+                break;
+            };
+            let Ok(index) = node.child(byte) else {
+                break;
+            };
+            let edge = &node.children[index];
+            if let Some(remaining) = rest.strip_prefix(&*edge.label) {
+                rest = remaining;
+            } else if edge.label.starts_with(rest) {
+                rest = &[];
+            } else {
+                break;
+            }
+            key.extend_from_slice(&edge.label);
+            node = &edge.node;
+        }
+        Iter { stack }
+    }
+
+    /// Returns the length of the keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._len
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultTrieMap;
+    ///
+    /// let mut map = DefaultTrieMap::new();
+    /// map.insert("key", 1);
+    ///
+    /// assert_eq!(1, map.remove("key"));
+    /// assert_eq!(0, map.remove("key"));
+    /// ```
+    #[must_use]
+    pub fn remove(&mut self, key: &str) -> V {
+        match self._root.remove(key.as_bytes()) {
+            Some(value) => {
+                self._len -= 1;
+                value
+            }
+            None => V::default(),
+        }
+    }
+
+    /// Returns a mutable reference to the value of the key if it is present, without inserting
+    /// the default value.
+    pub fn try_get_mut(&mut self, key: &str) -> Option<&mut V> {
+        self._root
+            .find_mut(key.as_bytes())
+            .and_then(|node| node.value.as_mut())
+    }
+}
+
+impl<V> Default for DefaultTrieMap<V>
+where
+    V: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> fmt::Debug for DefaultTrieMap<V>
+where
+    V: Default + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for DefaultTrieMap<V>
+where
+    K: AsRef<str>,
+    V: Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key.as_ref(), value);
+        }
+        map
+    }
+}
+
+impl<V> Index<&str> for DefaultTrieMap<V>
+where
+    V: Default,
+{
+    type Output = V;
+
+    fn index(&self, key: &str) -> &V {
+        self.get(key)
+    }
+}
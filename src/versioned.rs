@@ -0,0 +1,407 @@
+#![deny(missing_docs)]
+
+use std::collections::hash_map::{Iter, RandomState};
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use crate::DefaultHashMap;
+
+/// An immutable view of a [`VersionedHashMap`] at a point in time. This is created by calling
+/// [`VersionedHashMap::snapshot`].
+///
+/// Snapshots are cheap to create and clone, and can be sent to other threads while the map they
+/// were taken from keeps changing.
+///
+/// # Example
+/// ```
+/// use defaultdict::VersionedHashMap;
+///
+/// let mut map = VersionedHashMap::<i8, i8>::new();
+/// map.insert(1, 1);
+///
+/// let snapshot = map.snapshot();
+/// map.insert(1, 2);
+///
+/// assert_eq!(&1, snapshot.get(&1));
+/// assert_eq!(&2, map.get(&1));
+/// ```
+#[derive(Debug)]
+pub struct Snapshot<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _generation: u64,
+    _map: Arc<DefaultHashMap<K, V, S>>,
+}
+
+impl<K, V, S> Clone for Snapshot<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn clone(&self) -> Self {
+        Self {
+            _generation: self._generation,
+            _map: Arc::clone(&self._map),
+        }
+    }
+}
+
+impl<K, V, S> Snapshot<K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Returns a reference to the [`DefaultHashMap`] as it was when the snapshot was taken.
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<K, V, S> {
+        &self._map
+    }
+
+    /// Returns an iterator over the keys that differ between this snapshot and another one. A key
+    /// differs when it is present in only one of them or when the values are not equal. Missing
+    /// keys are compared using the default value, so a key that was inserted with the default
+    /// value does not show up.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::VersionedHashMap;
+    ///
+    /// let mut map = VersionedHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    /// map.insert(2, 2);
+    /// let old = map.snapshot();
+    ///
+    /// map.insert(1, 10);
+    /// let _ = map.remove(&2);
+    /// map.insert(3, 0);
+    /// let new = map.snapshot();
+    ///
+    /// let mut changed: Vec<&i8> = old.changed_keys(&new).collect();
+    /// changed.sort();
+    ///
+    /// assert_eq!(vec![&1, &2], changed);
+    /// ```
+    pub fn changed_keys<'a>(&'a self, other: &'a Snapshot<K, V, S>) -> impl Iterator<Item = &'a K>
+    where
+        V: PartialEq,
+    {
+        let removed_or_changed = self
+            ._map
+            .keys()
+            .filter(move |key| self._map.get(*key) != other._map.get(*key));
+        let added = other._map.keys().filter(move |key| {
+            !self._map.contains_key(key) && self._map.get(*key) != other._map.get(*key)
+        });
+        removed_or_changed.chain(added)
+    }
+
+    /// Returns the generation of the map when the snapshot was taken. The generation increases
+    /// with every mutation of a [`VersionedHashMap`].
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::VersionedHashMap;
+    ///
+    /// let mut map = VersionedHashMap::<i8, i8>::new();
+    /// let first = map.snapshot();
+    /// map.insert(1, 1);
+    /// let second = map.snapshot();
+    ///
+    /// assert_eq!(0, first.generation());
+    /// assert_eq!(1, second.generation());
+    /// ```
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self._generation
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key was
+    /// not present when the snapshot was taken.
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._map.get(key)
+    }
+
+    /// Returns an iterator visiting all key-value pairs of the snapshot in arbitrary order.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::VersionedHashMap;
+    ///
+    /// let mut map = VersionedHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    /// let snapshot = map.snapshot();
+    /// map.clear();
+    ///
+    /// let collected: Vec<(&i8, &i8)> = snapshot.iter().collect();
+    ///
+    /// assert_eq!(vec![(&1, &1)], collected);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self._map.as_ref().into_iter()
+    }
+
+    /// Returns `true` if the snapshot does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._map.is_empty()
+    }
+
+    /// Returns the length of the keys in the snapshot.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._map.len()
+    }
+}
+
+/// A [`DefaultHashMap`] that keeps track of its generations and hands out cheap immutable
+/// [`Snapshot`]s.
+///
+/// The current state is shared with the snapshots taken from it, the underlying map is only
+/// copied when it is mutated while a snapshot of the current generation is still alive. Every
+/// snapshot is also recorded in the history of the map until it is truncated.
+///
+/// Because the history keeps its snapshots alive, the first mutation after every snapshot copies
+/// the whole map, and each recorded snapshot holds on to its own copy. A long-running map should
+/// limit its history with [`VersionedHashMap::with_max_history`] or call
+/// [`VersionedHashMap::truncate_history`] regularly.
+///
+/// # Example
+/// ```
+/// use defaultdict::VersionedHashMap;
+///
+/// let mut map = VersionedHashMap::<&str, u32>::new();
+///
+/// *map.get_mut(&"a") += 1;
+/// let _ = map.snapshot();
+/// *map.get_mut(&"a") += 1;
+/// let _ = map.snapshot();
+///
+/// let history: Vec<u32> = map.history().map(|snapshot| *snapshot.get(&"a")).collect();
+///
+/// assert_eq!(vec![1, 2], history);
+/// ```
+#[derive(Debug)]
+pub struct VersionedHashMap<K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _current: Arc<DefaultHashMap<K, V, S>>,
+    _generation: u64,
+    _history: VecDeque<Snapshot<K, V, S>>,
+    _max_history: Option<usize>,
+}
+
+impl<K, V> VersionedHashMap<K, V, RandomState>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    /// Creates an empty [`VersionedHashMap`] at generation 0.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_map(DefaultHashMap::new())
+    }
+}
+
+impl<K, V> Default for VersionedHashMap<K, V, RandomState>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> VersionedHashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Default + Clone,
+    S: BuildHasher + Clone,
+{
+    /// Wraps an existing [`DefaultHashMap`] as generation 0.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::{defaulthashmap, DefaultHashMap, VersionedHashMap};
+    ///
+    /// let map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1));
+    /// let map = VersionedHashMap::from_map(map);
+    ///
+    /// assert_eq!(&1, map.get(&1));
+    /// assert_eq!(0, map.generation());
+    /// ```
+    #[must_use]
+    pub fn from_map(map: DefaultHashMap<K, V, S>) -> Self {
+        Self {
+            _current: Arc::new(map),
+            _generation: 0,
+            _history: VecDeque::new(),
+            _max_history: None,
+        }
+    }
+
+    /// Keeps at most `max` snapshots in the history, dropping the oldest ones when a new snapshot
+    /// is recorded. With a maximum of `0` no history is kept.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::VersionedHashMap;
+    ///
+    /// let mut map = VersionedHashMap::<i8, i8>::new().with_max_history(2);
+    /// for i in 0..10 {
+    ///     map.insert(i, i);
+    ///     let _ = map.snapshot();
+    /// }
+    ///
+    /// let generations: Vec<u64> = map.history().map(|snapshot| snapshot.generation()).collect();
+    ///
+    /// assert_eq!(vec![9, 10], generations);
+    /// ```
+    #[must_use]
+    pub fn with_max_history(mut self, max: usize) -> Self {
+        self._max_history = Some(max);
+        self.truncate_history(max);
+        self
+    }
+
+    /// Returns a reference to the current state of the map.
+    #[inline]
+    pub fn as_map(&self) -> &DefaultHashMap<K, V, S> {
+        &self._current
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        self.current_mut().clear();
+    }
+
+    /// Returns `true` if the key passed in exists in the current state of the map.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._current.contains_key(key)
+    }
+
+    /// Returns the current generation of the map.
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self._generation
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value if the key is
+    /// not present.
+    #[must_use]
+    pub fn get(&self, key: &K) -> &V {
+        self._current.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, inserting the default
+    /// value if the key is not present. This always starts a new generation.
+    #[must_use]
+    pub fn get_mut(&mut self, key: &K) -> &mut V {
+        self.current_mut().get_mut(key)
+    }
+
+    /// Returns an iterator over the recorded snapshots, from the oldest to the newest.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Snapshot<K, V, S>> {
+        self._history.iter()
+    }
+
+    /// Inserts a key value pair into the map, starting a new generation.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.current_mut().insert(key, value)
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._current.is_empty()
+    }
+
+    /// Returns the length of the keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._current.len()
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the
+    /// map or the default value otherwise. This starts a new generation.
+    #[must_use]
+    pub fn remove(&mut self, key: &K) -> V {
+        self.current_mut().remove(key)
+    }
+
+    /// Retains only the elements specified by the predicate, starting a new generation.
+    pub fn retain<F>(&mut self, func: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.current_mut().retain(func);
+    }
+
+    /// Returns a [`Snapshot`] of the current generation and records it in the history of the map,
+    /// dropping the oldest snapshot if the history is full. Taking multiple snapshots of the same
+    /// generation shares a single copy of the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::VersionedHashMap;
+    ///
+    /// let mut map = VersionedHashMap::<i8, i8>::new();
+    /// map.insert(1, 1);
+    ///
+    /// let snapshot = map.snapshot();
+    /// std::thread::spawn(move || assert_eq!(&1, snapshot.get(&1)))
+    ///     .join()
+    ///     .unwrap();
+    /// ```
+    pub fn snapshot(&mut self) -> Snapshot<K, V, S> {
+        let snapshot = Snapshot {
+            _generation: self._generation,
+            _map: Arc::clone(&self._current),
+        };
+        if self._history.back().map(Snapshot::generation) != Some(self._generation) {
+            self._history.push_back(snapshot.clone());
+        }
+        if let Some(max) = self._max_history {
+            self.truncate_history(max);
+        }
+        snapshot
+    }
+
+    /// Drops all but the `len` most recent snapshots from the history.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::VersionedHashMap;
+    ///
+    /// let mut map = VersionedHashMap::<i8, i8>::new();
+    /// for i in 0..10 {
+    ///     map.insert(i, i);
+    ///     let _ = map.snapshot();
+    /// }
+    ///
+    /// map.truncate_history(2);
+    /// let generations: Vec<u64> = map.history().map(|snapshot| snapshot.generation()).collect();
+    ///
+    /// assert_eq!(vec![9, 10], generations);
+    /// ```
+    pub fn truncate_history(&mut self, len: usize) {
+        while self._history.len() > len {
+            self._history.pop_front();
+        }
+    }
+
+    /// Starts a new generation and returns the map to mutate, copying it first if a snapshot
+    /// still refers to it.
+    fn current_mut(&mut self) -> &mut DefaultHashMap<K, V, S> {
+        self._generation += 1;
+        Arc::make_mut(&mut self._current)
+    }
+}
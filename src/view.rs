@@ -0,0 +1,210 @@
+#![deny(missing_docs)]
+
+use std::borrow::Borrow;
+use std::collections::hash_map::{Iter, Keys, RandomState, Values};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::ops::Index;
+
+use crate::DefaultHashMap;
+
+/// A read-only view of a [`DefaultHashMap`] that returns a different default value for missing
+/// keys. This is created by calling [`DefaultHashMap::view_with_default`].
+///
+/// # Example
+/// ```
+/// use defaultdict::DefaultHashMap;
+///
+/// let mut scores = DefaultHashMap::<&str, i32>::new();
+/// scores.insert("alice", 10);
+///
+/// let view = scores.view_with_default(&-1);
+///
+/// assert_eq!(&10, view.get("alice"));
+/// assert_eq!(&-1, view.get("bob"));
+/// assert_eq!(&0, scores.get("bob"));
+/// ```
+pub struct DefaultView<'a, K, V, S = RandomState>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    _map: &'a DefaultHashMap<K, V, S>,
+    _default: &'a V,
+}
+
+impl<'a, K, V, S> DefaultView<'a, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    /// Creates a view of the given map.
+    pub(crate) fn new(map: &'a DefaultHashMap<K, V, S>, default: &'a V) -> Self {
+        Self {
+            _map: map,
+            _default: default,
+        }
+    }
+
+    /// Returns `true` if the key passed in exists in the map.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 2);
+    ///
+    /// let view = map.view_with_default(&5);
+    ///
+    /// assert!(view.contains_key(&1));
+    /// assert!(!view.contains_key(&2));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._map.contains_key(key)
+    }
+
+    /// Returns the default value of this view.
+    #[inline]
+    pub fn default_value(&self) -> &'a V {
+        self._default
+    }
+
+    /// Returns a reference to the value of the key passed in, or the default value of this view if
+    /// the key is not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let mut map = DefaultHashMap::<i8, i8>::new();
+    /// map.insert(1, 2);
+    ///
+    /// let view = map.view_with_default(&5);
+    ///
+    /// assert_eq!(&2, view.get(&1));
+    /// assert_eq!(&5, view.get(&2));
+    /// ```
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> &'a V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self._map.get_present(key).unwrap_or(self._default)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key, using the default value of
+    /// this view if the key is not present.
+    ///
+    /// # Example
+    /// ```
+    /// use defaultdict::DefaultHashMap;
+    ///
+    /// let map = DefaultHashMap::<i8, i8>::new();
+    /// let view = map.view_with_default(&5);
+    ///
+    /// assert_eq!((&1, &5), view.get_key_value(&1));
+    /// ```
+    #[must_use]
+    pub fn get_key_value<'b>(&self, key: &'b K) -> (&'b K, &'b V)
+    where
+        'a: 'b,
+    {
+        if self._map.contains_key(key) {
+            self._map.get_key_value(key)
+        } else {
+            (key, self._default)
+        }
+    }
+
+    /// Returns `true` if the map does not contain any keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._map.is_empty()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, K, V> {
+        self._map.into_iter()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    #[inline]
+    pub fn keys(&self) -> Keys<'a, K, V> {
+        self._map.keys()
+    }
+
+    /// Returns the length of the keys in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self._map.len()
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    #[inline]
+    pub fn values(&self) -> Values<'a, K, V> {
+        self._map.values()
+    }
+}
+
+impl<K, V, S> Clone for DefaultView<'_, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, S> Copy for DefaultView<'_, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+}
+
+impl<K, V, S> fmt::Debug for DefaultView<'_, K, V, S>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: Default + fmt::Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultView")
+            .field("_map", &self._map)
+            .field("_default", &self._default)
+            .finish()
+    }
+}
+
+impl<K, V, S> Index<&K> for DefaultView<'_, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        self.get(key)
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for DefaultView<'a, K, V, S>
+where
+    K: Eq + Hash,
+    V: Default,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,179 @@
+#![deny(missing_docs)]
+
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use crate::DefaultHashMap;
+
+/// Counts events per key over a sliding window of the last `N` time buckets.
+///
+/// Counts are stored per `(key, bucket)` pair in a [`DefaultHashMap`]. Recording an event in a
+/// new bucket drops the buckets that fell out of the window, so the map only ever holds the
+/// active window.
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use defaultdict::WindowedCounter;
+///
+/// let mut requests = WindowedCounter::new(Duration::from_secs(1), 3);
+/// let start = Instant::now();
+///
+/// requests.record_at("client-a", start);
+/// requests.record_at("client-a", start + Duration::from_secs(1));
+/// requests.record_at("client-a", start + Duration::from_secs(3));
+///
+/// assert_eq!(2, requests.count_at(&"client-a", start + Duration::from_secs(3)));
+/// assert_eq!(0, requests.count_at(&"client-b", start + Duration::from_secs(3)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct WindowedCounter<K>
+where
+    K: Eq + Hash,
+{
+    _counts: DefaultHashMap<(K, u64), u64>,
+    _bucket_width: Duration,
+    _buckets: u64,
+    _start: Instant,
+    _current: u64,
+}
+
+impl<K> WindowedCounter<K>
+where
+    K: Eq + Hash,
+{
+    /// Creates an empty counter with a window of `buckets` buckets that each span `bucket_width`.
+    /// The first bucket starts now.
+    ///
+    /// # Panics
+    /// Panics if `bucket_width` or `buckets` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use defaultdict::WindowedCounter;
+    ///
+    /// let counter: WindowedCounter<&str> = WindowedCounter::new(Duration::from_secs(10), 6);
+    ///
+    /// assert_eq!(Duration::from_secs(60), counter.window());
+    /// ```
+    #[must_use]
+    pub fn new(bucket_width: Duration, buckets: usize) -> Self {
+        assert!(!bucket_width.is_zero(), "the bucket width must not be zero");
+        assert!(buckets > 0, "the window must contain at least one bucket");
+        Self {
+            _counts: DefaultHashMap::new(),
+            _bucket_width: bucket_width,
+            _buckets: buckets as u64,
+            _start: Instant::now(),
+            _current: 0,
+        }
+    }
+
+    /// Removes all counts.
+    #[inline]
+    pub fn clear(&mut self) {
+        self._counts.clear();
+    }
+
+    /// Returns the number of events recorded for the key in the window that ends now.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use defaultdict::WindowedCounter;
+    ///
+    /// let mut counter = WindowedCounter::new(Duration::from_secs(60), 5);
+    /// counter.record("key");
+    /// counter.record("key");
+    ///
+    /// assert_eq!(2, counter.count(&"key"));
+    /// ```
+    pub fn count(&self, key: &K) -> u64
+    where
+        K: Clone,
+    {
+        self.count_at(key, Instant::now())
+    }
+
+    /// Returns the number of events recorded for the key in the window that ends at `at`.
+    pub fn count_at(&self, key: &K, at: Instant) -> u64
+    where
+        K: Clone,
+    {
+        let last = self.bucket(at);
+        let first = (last + 1).saturating_sub(self._buckets);
+        (first..=last)
+            .map(|bucket| self._counts.get(&(key.clone(), bucket)))
+            .sum()
+    }
+
+    /// Drops the buckets that are no longer part of the window that ends now.
+    pub fn expire(&mut self) {
+        self.expire_at(Instant::now());
+    }
+
+    /// Drops the buckets that are no longer part of the window that ends at `at`.
+    pub fn expire_at(&mut self, at: Instant) {
+        let bucket = self.bucket(at);
+        if bucket <= self._current {
+            return;
+        }
+        self._current = bucket;
+        let first = (bucket + 1).saturating_sub(self._buckets);
+        self._counts.retain(|(_, bucket), _| *bucket >= first);
+    }
+
+    /// Returns `true` if no events are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self._counts.is_empty()
+    }
+
+    /// Records an event for the key now.
+    pub fn record(&mut self, key: K) {
+        self.record_at(key, Instant::now());
+    }
+
+    /// Records an event for the key at `at`, dropping the buckets that fell out of the window.
+    /// Events older than the window are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use defaultdict::WindowedCounter;
+    ///
+    /// let mut counter = WindowedCounter::new(Duration::from_secs(1), 2);
+    /// let start = Instant::now();
+    ///
+    /// counter.record_at("key", start + Duration::from_secs(5));
+    /// counter.record_at("key", start);
+    ///
+    /// assert_eq!(1, counter.count_at(&"key", start + Duration::from_secs(5)));
+    /// ```
+    pub fn record_at(&mut self, key: K, at: Instant) {
+        self.expire_at(at);
+        let bucket = self.bucket(at);
+        if bucket + self._buckets <= self._current {
+            return;
+        }
+        *self._counts.entry((key, bucket)).or_default() += 1;
+    }
+
+    /// Returns the total duration covered by the window.
+    #[inline]
+    pub fn window(&self) -> Duration {
+        self._bucket_width
+            .saturating_mul(u32::try_from(self._buckets).unwrap_or(u32::MAX))
+    }
+
+    /// Returns the bucket that contains `at`.
+    fn bucket(&self, at: Instant) -> u64 {
+        let elapsed = at.saturating_duration_since(self._start).as_nanos();
+        (elapsed / self._bucket_width.as_nanos()) as u64
+    }
+}
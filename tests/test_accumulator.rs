@@ -0,0 +1,52 @@
+use defaultdict::*;
+
+#[test]
+fn merge_threads_accumulator() {
+    let accumulator: Accumulator<i32, i32> = Accumulator::new();
+
+    std::thread::scope(|scope| {
+        for worker in 0..4 {
+            let accumulator = &accumulator;
+            scope.spawn(move || {
+                let mut shard = accumulator.shard();
+                for i in 0..100 {
+                    *shard.get_mut(&(i % 10)) += worker;
+                }
+            });
+        }
+    });
+
+    assert_eq!(4, accumulator.shard_count());
+
+    let merged = accumulator.merge(|total, value| *total += value);
+
+    assert_eq!(10, merged.len());
+    for key in 0..10 {
+        assert_eq!(&60, merged.get(&key));
+    }
+}
+
+#[test]
+fn empty_shard_accumulator() {
+    let accumulator: Accumulator<i8, i8> = Accumulator::new();
+    drop(accumulator.shard());
+
+    assert_eq!(0, accumulator.shard_count());
+    assert!(accumulator.merge(|_, _| unreachable!()).is_empty());
+}
+
+#[test]
+fn merge_combiner_accumulator() {
+    let accumulator = Accumulator::new();
+    accumulator.shard().insert("a", vec![1]);
+    accumulator.shard().insert("a", vec![2]);
+    accumulator.shard().insert("b", vec![3]);
+
+    let merged = accumulator.merge(|values, mut other| values.append(&mut other));
+
+    let mut values = merged.get(&"a").clone();
+    values.sort_unstable();
+
+    assert_eq!(vec![1, 2], values);
+    assert_eq!(&vec![3], merged.get(&"b"));
+}
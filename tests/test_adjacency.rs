@@ -0,0 +1,36 @@
+use defaultdict::*;
+
+#[test]
+fn directed_adjacency() {
+    let mut graph = AdjacencyMap::new();
+    graph.add_edge(1, 2);
+    graph.add_edge(1, 3);
+    graph.add_edge(3, 1);
+
+    assert_eq!(&[2, 3], graph.neighbors(&1));
+    assert_eq!(&[1], graph.neighbors(&3));
+    assert!(graph.neighbors(&2).is_empty());
+    assert!(graph.contains_edge(&3, &1));
+    assert!(!graph.contains_edge(&2, &1));
+
+    assert_eq!(1, graph.remove_edge(&1, &2));
+    assert_eq!(0, graph.remove_edge(&1, &2));
+    assert_eq!(1, graph.degree(&1));
+}
+
+#[test]
+fn undirected_adjacency() {
+    let mut graph = AdjacencyMap::undirected();
+    graph.add_edge("a", "b");
+    graph.add_edge("b", "c");
+    graph.add_edge("c", "c");
+
+    assert_eq!(&["b"], graph.neighbors(&"a"));
+    assert_eq!(&["a", "c"], graph.neighbors(&"b"));
+    assert_eq!(&["b", "c"], graph.neighbors(&"c"));
+
+    graph.remove_edge(&"a", &"b");
+
+    assert!(!graph.contains_edge(&"b", &"a"));
+    assert_eq!(2, graph.as_map().len());
+}
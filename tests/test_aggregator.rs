@@ -0,0 +1,49 @@
+use defaultdict::*;
+
+#[test]
+fn aggregator_many_producers() {
+    let aggregator = Aggregator::spawn(|count: &mut u64, delta: u64| *count += delta);
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            let sender = aggregator.sender();
+            scope.spawn(move || {
+                for i in 0..100 {
+                    sender.send(i % 10, 1).unwrap();
+                }
+            });
+        }
+    });
+
+    let counts = aggregator.finish();
+
+    assert_eq!(10, counts.len());
+    assert!(counts.values().all(|count| *count == 40));
+}
+
+#[test]
+fn aggregator_snapshot() {
+    let aggregator = Aggregator::spawn(|values: &mut Vec<u8>, value: u8| values.push(value));
+    aggregator.send("a", 1).unwrap();
+    aggregator.send("a", 2).unwrap();
+
+    let snapshot = aggregator.snapshot();
+    aggregator.send("b", 3).unwrap();
+    let map = aggregator.finish();
+
+    assert_eq!(&vec![1, 2], snapshot.get(&"a"));
+    assert!(!snapshot.contains_key(&"b"));
+    assert_eq!(&vec![3], map.get(&"b"));
+}
+
+#[test]
+fn aggregator_send_after_finish() {
+    let aggregator = Aggregator::spawn(|count: &mut u8, delta: u8| *count += delta);
+    let sender = aggregator.sender();
+    sender.send('a', 1).unwrap();
+
+    let map = aggregator.finish();
+
+    assert_eq!(&1, map.get(&'a'));
+    assert_eq!(('a', 2), sender.send('a', 2).unwrap_err().0);
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "ahash")]
+
+use defaultdict::*;
+
+#[test]
+fn with_ahash_ahash() {
+    let mut map: DefaultAHashMap<String, u32> = DefaultHashMap::with_ahash();
+    for word in "a b a c a".split(' ') {
+        *map.get_mut(&word.to_string()) += 1;
+    }
+
+    assert_eq!(&3, map.get(&String::from("a")));
+    assert_eq!(&0, map.get(&String::from("d")));
+}
+
+#[test]
+fn default_ahash() {
+    let map: DefaultAHashMap<i8, i8> = DefaultAHashMap::default();
+
+    assert!(map.is_empty());
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "arrow")]
+
+use defaultdict::*;
+
+use arrow_array::{Array, Int64Array, RecordBatch, StringArray};
+
+#[test]
+fn arrow_arrays_roundtrip() {
+    let map: DefaultHashMap<String, f64> =
+        defaulthashmap!(("a".to_string(), 1.5), ("b".to_string(), -2.0));
+
+    let (keys, values) = map.to_arrow_arrays();
+    let back = DefaultHashMap::<String, f64>::try_from_arrow_arrays(&keys, &values).unwrap();
+
+    assert_eq!(map, back);
+    assert_eq!(&0.0, back.get("c"));
+}
+
+#[test]
+fn record_batch_roundtrip() {
+    let map: DefaultHashMap<u16, String> = (0..10).map(|i| (i, i.to_string())).collect();
+
+    let batch = map.to_record_batch("id", "name");
+    let back = DefaultHashMap::<u16, String>::try_from_record_batch(&batch).unwrap();
+
+    assert_eq!(10, batch.num_rows());
+    assert!(!batch.schema().field(0).is_nullable());
+    assert_eq!(map, back);
+}
+
+#[test]
+fn from_arrow_rejects_invalid_input() {
+    let keys = StringArray::from(vec!["a", "b"]);
+    let values = Int64Array::from(vec![1]);
+    let nulls = Int64Array::from(vec![Some(1), None]);
+
+    assert!(DefaultHashMap::<String, i64>::try_from_arrow_arrays(&keys, &values).is_err());
+    assert!(DefaultHashMap::<String, i64>::try_from_arrow_arrays(&keys, &nulls).is_err());
+    assert!(DefaultHashMap::<String, i32>::try_from_arrow_arrays(&keys, &keys).is_err());
+    assert_eq!(2, keys.len());
+
+    let batch = RecordBatch::try_from_iter([("a", std::sync::Arc::new(keys) as _)]).unwrap();
+    assert!(DefaultHashMap::<String, i64>::try_from_record_batch(&batch).is_err());
+}
@@ -0,0 +1,48 @@
+use defaultdict::*;
+
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+
+#[test]
+fn fetch_add_threads_atomic() {
+    let mut map = DefaultHashMap::<u32, AtomicU64>::new();
+    for key in 0..4 {
+        let _ = map.get_mut(&key);
+    }
+
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for i in 0..1000 {
+                    map.fetch_add(&(i % 4), 1);
+                }
+            });
+        }
+    });
+
+    for key in 0..4 {
+        assert_eq!(2000, map.load(&key));
+    }
+}
+
+#[test]
+fn fetch_add_missing_atomic() {
+    let map = DefaultHashMap::<u8, AtomicUsize>::new();
+
+    assert_eq!(None, map.fetch_add(&1, 1));
+    assert_eq!(0, map.load(&1));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn snapshot_atomic() {
+    let mut map = DefaultHashMap::<&str, AtomicUsize>::new();
+    map.insert("a", AtomicUsize::new(1));
+    map.insert("b", AtomicUsize::new(2));
+
+    assert_eq!(Some(1), map.fetch_add(&"a", 4));
+
+    let snapshot: DefaultHashMap<&str, usize> = map.snapshot();
+    let expected: DefaultHashMap<&str, usize> = defaulthashmap!(("a", 5), ("b", 2));
+
+    assert_eq!(expected, snapshot);
+}
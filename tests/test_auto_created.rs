@@ -0,0 +1,48 @@
+use defaultdict::*;
+
+#[test]
+fn auto_created_keys_tracked() {
+    let mut map = TrackedHashMap::<i8, i8>::tracked();
+    map.insert(1, 1);
+    *map.get_mut(&1) += 1;
+    *map.get_mut(&2) += 2;
+    let _ = map.get_mut(&3);
+    let _ = map.get(&4);
+
+    let mut keys: Vec<i8> = map.auto_created_keys().copied().collect();
+    keys.sort();
+
+    assert_eq!(vec![2, 3], keys);
+    assert!(!map.listener().contains(&1));
+}
+
+#[test]
+fn auto_created_keys_become_explicit() {
+    let mut map = TrackedHashMap::<i8, i8>::tracked();
+    let _ = map.get_mut(&1);
+    let _ = map.get_mut(&2);
+    let _ = map.get_mut(&3);
+
+    map.insert(1, 10);
+    let _ = map.remove(&2);
+
+    assert_eq!(vec![&3], map.auto_created_keys().collect::<Vec<_>>());
+
+    map.clear();
+
+    assert!(map.listener().is_empty());
+}
+
+#[test]
+fn remove_auto_created_entries() {
+    let mut map = TrackedHashMap::<u8, Vec<u8>>::tracked();
+    map.insert(0, vec![0]);
+    for key in 0..5 {
+        let _ = map.get_mut(&key);
+    }
+
+    assert_eq!(4, map.remove_auto_created());
+    assert_eq!(0, map.remove_auto_created());
+    assert_eq!(1, map.len());
+    assert_eq!(&vec![0], map.get(&0));
+}
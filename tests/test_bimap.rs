@@ -0,0 +1,57 @@
+use defaultdict::*;
+
+#[test]
+fn insert_bimap() {
+    let mut map = DefaultBiMap::new();
+
+    assert_eq!((None, None), map.insert(1, 'a'));
+    assert_eq!((None, None), map.insert(2, 'b'));
+
+    assert_eq!(&'a', map.get_by_left(&1));
+    assert_eq!(&2, map.get_by_right(&'b'));
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn insert_overwrite_bimap() {
+    let mut map = DefaultBiMap::new();
+    map.insert(1, 'a');
+    map.insert(2, 'b');
+
+    assert_eq!((Some('a'), Some(2)), map.insert(1, 'b'));
+
+    assert_eq!(1, map.len());
+    assert!(!map.contains_left(&2));
+    assert!(!map.contains_right(&'a'));
+    assert_eq!(&'b', map.get_by_left(&1));
+    assert_eq!(&1, map.get_by_right(&'b'));
+}
+
+#[test]
+fn default_bimap() {
+    let map = DefaultBiMap::<String, u8>::new();
+
+    assert_eq!(&0, map.get_by_left(&String::from("missing")));
+    assert_eq!(&String::new(), map.get_by_right(&1));
+}
+
+#[test]
+fn remove_bimap() {
+    let mut map: DefaultBiMap<i8, i8> = vec![(1, 10), (2, 20)].into_iter().collect();
+
+    assert_eq!(10, map.remove_by_left(&1));
+    assert_eq!(2, map.remove_by_right(&20));
+    assert_eq!(0, map.remove_by_left(&1));
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn iter_bimap() {
+    let map: DefaultBiMap<i8, i8> = vec![(1, 10), (2, 20)].into_iter().collect();
+
+    let mut pairs: Vec<(i8, i8)> = map.iter().map(|(l, r)| (*l, *r)).collect();
+    pairs.sort_unstable();
+
+    assert_eq!(vec![(1, 10), (2, 20)], pairs);
+}
@@ -1,6 +1,8 @@
 use defaultdict::*;
 
+use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Included, Unbounded};
 
 #[test]
 fn clear_btree() {
@@ -232,6 +234,21 @@ fn first_key_value_empty_btree() {
     assert!(entry.is_none())
 }
 
+#[test]
+fn first_last_key_value_or_default_btree() {
+    let mut map = DefaultBTreeMap::<i8, i8>::new();
+    map.set_range_default(-10..0, -1);
+
+    assert_eq!((&-5, &-1), map.first_key_value_or_default(&-5));
+    assert_eq!((&5, &0), map.last_key_value_or_default(&5));
+
+    map.insert(2, 20);
+    map.insert(-3, 30);
+
+    assert_eq!((&-3, &30), map.first_key_value_or_default(&-5));
+    assert_eq!((&2, &20), map.last_key_value_or_default(&5));
+}
+
 #[test]
 fn into_keys_btree() {
     let map: DefaultBTreeMap<i8, String> = defaultbtreemap!(
@@ -304,14 +321,34 @@ fn range_btree() {
         (9, 9),
     );
 
-    let mut index: i8 = 0;
-    for (&key, &val) in map.range(0..10) {
+    for (index, (&key, &val)) in (0_i8..).zip(map.range(0..10)) {
         assert_eq!(&index, &key);
         assert_eq!(&index, &val);
-        index += 1
     }
 }
 
+#[test]
+fn keys_values_range_btree() {
+    let map: DefaultBTreeMap<String, u32> = defaultbtreemap!(
+        ("apple".to_string(), 1),
+        ("banana".to_string(), 2),
+        ("cherry".to_string(), 3),
+        ("date".to_string(), 4),
+    );
+
+    let keys: Vec<&String> = map
+        .keys_range::<str, _>((Included("b"), Excluded("d")))
+        .collect();
+    let values: Vec<&u32> = map
+        .values_range::<str, _>((Included("banana"), Unbounded))
+        .rev()
+        .collect();
+
+    assert_eq!(vec!["banana", "cherry"], keys);
+    assert_eq!(vec![&4, &3, &2], values);
+    assert_eq!(0, map.keys_range("x".to_string()..).count());
+}
+
 #[test]
 fn range_mut_btree() {
     let mut map: DefaultBTreeMap<i8, i8> = defaultbtreemap!(
@@ -327,27 +364,19 @@ fn range_mut_btree() {
         (9, 9),
     );
 
-    let mut index: i8 = 0;
-    for (&key, &val) in map.range(0..10) {
+    for (index, (&key, &val)) in (0_i8..).zip(map.range(0..10)) {
         assert_eq!(&index, &key);
         assert_eq!(&index, &val);
-        index += 1
     }
 
-    index = 0;
-
     for (&_key, val) in map.range_mut(0..10) {
         *val += 100;
-        index += 1;
     }
 
-    index = 0;
-
-    for (&key, &val) in map.range(0..10) {
+    for (index, (&key, &val)) in (0_i8..).zip(map.range(0..10)) {
         let comparison = index + 100;
         assert_eq!(&index, &key);
         assert_eq!(&comparison, &val);
-        index += 1
     }
 }
 
@@ -459,8 +488,8 @@ fn contains_key_btree() {
     map.insert(1, 2);
     map.insert(3, 4);
 
-    assert_eq!(map.contains_key(&1), true);
-    assert_eq!(map.contains_key(&2), false);
+    assert!(map.contains_key(&1));
+    assert!(!map.contains_key(&2));
 }
 
 #[test]
@@ -493,8 +522,8 @@ fn is_empty_btree() {
 
     let map1 = DefaultBTreeMap::<i8, Vec<i8>>::new();
 
-    assert_eq!(map.is_empty(), false);
-    assert_eq!(map1.is_empty(), true);
+    assert!(!map.is_empty());
+    assert!(map1.is_empty());
 }
 
 #[test]
@@ -527,6 +556,19 @@ fn into_iter_default_btree() {
     assert_eq!(v, correct_v);
 }
 
+#[test]
+fn into_iter_key_without_clone_btree() {
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Key(u8);
+
+    let mut map = DefaultBTreeMap::<Key, u8>::new();
+    map.insert(Key(1), 2);
+
+    let v: Vec<(Key, u8)> = map.into_iter().collect();
+
+    assert_eq!(vec![(Key(1), 2)], v);
+}
+
 #[test]
 fn borrow_loop_over_default_btree() {
     let mut map: DefaultBTreeMap<i8, u8> = DefaultBTreeMap::new();
@@ -611,6 +653,18 @@ fn index_btree_no_panic() {
     assert_eq!(0, default_value);
 }
 
+#[test]
+fn index_mut_btree() {
+    let mut map: DefaultBTreeMap<&str, u32> = DefaultBTreeMap::new();
+    for word in "a b a c a".split(' ') {
+        map[&word] += 1;
+    }
+
+    assert_eq!(3, map[&"a"]);
+    assert_eq!(1, map[&"c"]);
+    assert_eq!(3, map.len());
+}
+
 #[test]
 fn btree_into_defaultbtree() {
     let mut btree: BTreeMap<u8, i8> = BTreeMap::new();
@@ -681,6 +735,97 @@ fn macro_test_btree() {
     assert_eq!(map1, _map1);
 }
 
+#[test]
+fn macro_arrow_btree() {
+    let map: DefaultBTreeMap<String, i8> =
+        defaultbtreemap!("a".to_string() => 1, "b".to_string() => 2,);
+    let mixed: DefaultBTreeMap<String, i8> = defaultbtreemap!(
+        format!("c{}", 1),
+        "d".to_string() => 4,
+        String::from("e")
+    );
+
+    let mut _map: DefaultBTreeMap<String, i8> = DefaultBTreeMap::new();
+    _map.insert("a".to_string(), 1);
+    _map.insert("b".to_string(), 2);
+
+    let mut _mixed: DefaultBTreeMap<String, i8> = DefaultBTreeMap::new();
+    let _ = _mixed.get_mut(&"c1".to_string());
+    _mixed.insert("d".to_string(), 4);
+    let _ = _mixed.get_mut(&"e".to_string());
+
+    assert_eq!(map, _map);
+    assert_eq!(mixed, _mixed);
+    assert_eq!(3, mixed.len());
+}
+
+#[test]
+fn macro_arrow_many_entries_btree() {
+    #[rustfmt::skip]
+    let map: DefaultBTreeMap<i32, i32> = defaultbtreemap!(
+        0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7, 8 => 8, 9 => 9, 10 => 10,
+        11 => 11, 12 => 12, 13 => 13, 14 => 14, 15 => 15, 16 => 16, 17 => 17, 18 => 18, 19 => 19,
+        20 => 20, 21 => 21, 22 => 22, 23 => 23, 24 => 24, 25 => 25, 26 => 26, 27 => 27, 28 => 28,
+        29 => 29, 30 => 30, 31 => 31, 32 => 32, 33 => 33, 34 => 34, 35 => 35, 36 => 36, 37 => 37,
+        38 => 38, 39 => 39, 40 => 40, 41 => 41, 42 => 42, 43 => 43, 44 => 44, 45 => 45, 46 => 46,
+        47 => 47, 48 => 48, 49 => 49, 50 => 50, 51 => 51, 52 => 52, 53 => 53, 54 => 54, 55 => 55,
+        56 => 56, 57 => 57, 58 => 58, 59 => 59, 60 => 60, 61 => 61, 62 => 62, 63 => 63, 64 => 64,
+        65 => 65, 66 => 66, 67 => 67, 68 => 68, 69 => 69, 70 => 70, 71 => 71, 72 => 72, 73 => 73,
+        74 => 74, 75 => 75, 76 => 76, 77 => 77, 78 => 78, 79 => 79, 80 => 80, 81 => 81, 82 => 82,
+        83 => 83, 84 => 84, 85 => 85, 86 => 86, 87 => 87, 88 => 88, 89 => 89, 90 => 90, 91 => 91,
+        92 => 92, 93 => 93, 94 => 94, 95 => 95, 96 => 96, 97 => 97, 98 => 98, 99 => 99, 100 => 100,
+        101 => 101, 102 => 102, 103 => 103, 104 => 104, 105 => 105, 106 => 106, 107 => 107,
+        108 => 108, 109 => 109, 110 => 110, 111 => 111, 112 => 112, 113 => 113, 114 => 114,
+        115 => 115, 116 => 116, 117 => 117, 118 => 118, 119 => 119, 120 => 120, 121 => 121,
+        122 => 122, 123 => 123, 124 => 124, 125 => 125, 126 => 126, 127 => 127, 128 => 128,
+        129 => 129, 130 => 130, 131 => 131, 132 => 132, 133 => 133, 134 => 134, 135 => 135,
+        136 => 136, 137 => 137, 138 => 138, 139 => 139, 140 => 140, 141 => 141, 142 => 142,
+        143 => 143, 144 => 144, 145 => 145, 146 => 146, 147 => 147, 148 => 148, 149 => 149,
+        150 => 150, 151 => 151, 152 => 152, 153 => 153, 154 => 154, 155 => 155, 156 => 156,
+        157 => 157, 158 => 158, 159 => 159, 160 => 160, 161 => 161, 162 => 162, 163 => 163,
+        164 => 164, 165 => 165, 166 => 166, 167 => 167, 168 => 168, 169 => 169, 170 => 170,
+        171 => 171, 172 => 172, 173 => 173, 174 => 174, 175 => 175, 176 => 176, 177 => 177,
+        178 => 178, 179 => 179, 180 => 180, 181 => 181, 182 => 182, 183 => 183, 184 => 184,
+        185 => 185, 186 => 186, 187 => 187, 188 => 188, 189 => 189, 190 => 190, 191 => 191,
+        192 => 192, 193 => 193, 194 => 194, 195 => 195, 196 => 196, 197 => 197, 198 => 198,
+        199 => 199,
+    );
+
+    assert_eq!(200, map.len());
+    assert_eq!(&199, map.get(&199));
+    assert_eq!(&0, map.get(&200));
+}
+
+#[test]
+fn macro_mixed_many_entries_btree() {
+    #[rustfmt::skip]
+    let map: DefaultBTreeMap<i32, i32> = defaultbtreemap!(
+        0, 1 => 1, 2, 3 => 3, 4, 5 => 5, 6, 7 => 7, 8, 9 => 9, 10, 11 => 11, 12, 13 => 13, 14,
+        15 => 15, 16, 17 => 17, 18, 19 => 19, 20, 21 => 21, 22, 23 => 23, 24, 25 => 25, 26,
+        27 => 27, 28, 29 => 29, 30, 31 => 31, 32, 33 => 33, 34, 35 => 35, 36, 37 => 37, 38,
+        39 => 39, 40, 41 => 41, 42, 43 => 43, 44, 45 => 45, 46, 47 => 47, 48, 49 => 49, 50,
+        51 => 51, 52, 53 => 53, 54, 55 => 55, 56, 57 => 57, 58, 59 => 59, 60, 61 => 61, 62,
+        63 => 63, 64, 65 => 65, 66, 67 => 67, 68, 69 => 69, 70, 71 => 71, 72, 73 => 73, 74,
+        75 => 75, 76, 77 => 77, 78, 79 => 79, 80, 81 => 81, 82, 83 => 83, 84, 85 => 85, 86,
+        87 => 87, 88, 89 => 89, 90, 91 => 91, 92, 93 => 93, 94, 95 => 95, 96, 97 => 97, 98,
+        99 => 99, 100, 101 => 101, 102, 103 => 103, 104, 105 => 105, 106, 107 => 107, 108,
+        109 => 109, 110, 111 => 111, 112, 113 => 113, 114, 115 => 115, 116, 117 => 117, 118,
+        119 => 119, 120, 121 => 121, 122, 123 => 123, 124, 125 => 125, 126, 127 => 127, 128,
+        129 => 129, 130, 131 => 131, 132, 133 => 133, 134, 135 => 135, 136, 137 => 137, 138,
+        139 => 139, 140, 141 => 141, 142, 143 => 143, 144, 145 => 145, 146, 147 => 147, 148,
+        149 => 149, 150, 151 => 151, 152, 153 => 153, 154, 155 => 155, 156, 157 => 157, 158,
+        159 => 159, 160, 161 => 161, 162, 163 => 163, 164, 165 => 165, 166, 167 => 167, 168,
+        169 => 169, 170, 171 => 171, 172, 173 => 173, 174, 175 => 175, 176, 177 => 177, 178,
+        179 => 179, 180, 181 => 181, 182, 183 => 183, 184, 185 => 185, 186, 187 => 187, 188,
+        189 => 189, 190, 191 => 191, 192, 193 => 193, 194, 195 => 195, 196, 197 => 197, 198,
+        199 => 199,
+    );
+
+    assert_eq!(200, map.len());
+    assert_eq!(&0, map.get(&198));
+    assert_eq!(&199, map.get(&199));
+}
+
 #[test]
 fn macro_test_slight_change_btree() {
     let map: DefaultBTreeMap<i8, i8> = defaultbtreemap!(1, 2, 3);
@@ -710,3 +855,527 @@ fn from_iter_btreemap() {
 
     assert_eq!(map, correct_map);
 }
+
+#[test]
+fn diff_apply_patch_btree() {
+    let old: DefaultBTreeMap<i8, i8> = defaultbtreemap!((0, 0), (1, 1), (2, 2), (3, 3));
+    let new: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 1), (2, 20), (4, 4), (5, 0));
+
+    let diff = old.diff(&new);
+
+    assert_eq!(&[(2, 2, 20)], diff.changed());
+    assert_eq!(&[3], diff.removed());
+    assert_eq!(&[(4, 4)], diff.added());
+
+    let mut mirror = old.clone();
+    mirror.apply_patch(diff);
+    for key in 0..6 {
+        assert_eq!(new.get(&key), mirror.get(&key));
+    }
+    assert!(mirror.diff(&new).is_empty());
+}
+
+#[test]
+fn range_default_btree() {
+    let mut map = DefaultBTreeMap::<i32, i32>::new();
+    map.set_range_default(10..20, -1);
+    map.set_range_default(15..30, -2);
+    map.insert(12, 7);
+
+    assert_eq!(&0, map.get(&9));
+    assert_eq!(&-1, map.get(&10));
+    assert_eq!(&7, map.get(&12));
+    assert_eq!(&-2, map.get(&15));
+    assert_eq!(&-2, &map[&29]);
+    assert_eq!(&0, map.get(&30));
+
+    *map.get_mut(&11) -= 1;
+    assert_eq!(&-2, map.get(&11));
+    assert_eq!(-1, map.remove(&13));
+    assert_eq!((25, -2), map.remove_entry(&25));
+}
+
+#[test]
+fn range_default_not_send_btree() {
+    use std::rc::Rc;
+
+    let mut map = DefaultBTreeMap::<u8, Rc<u8>>::new();
+    map.set_range_default(5..10, Rc::new(3));
+    let _ = map.get_mut(&6);
+
+    assert_eq!(&Rc::new(3), map.get(&6));
+    assert_eq!(&Rc::new(0), map.get(&10));
+}
+
+#[test]
+fn range_default_split_off_append_btree() {
+    let mut map = DefaultBTreeMap::<i32, i32>::new();
+    map.set_range_default(0..100, -1);
+    map.insert(10, 10);
+    map.insert(60, 60);
+
+    let mut upper = map.split_off(&50);
+
+    assert_eq!(&-1, upper.get(&70));
+    assert_eq!(&-1, map.get(&20));
+    assert_eq!(&60, upper.get(&60));
+
+    let mut other = DefaultBTreeMap::<i32, i32>::new();
+    other.set_range_default(200..300, -2);
+    other.insert(250, 1);
+    upper.append(&mut other);
+
+    assert_eq!(&1, upper.get(&250));
+    assert_eq!(&0, upper.get(&260));
+    assert_eq!(&-1, upper.get(&70));
+}
+
+#[test]
+fn split_off_value_not_clone_btree() {
+    #[derive(Debug, Default, PartialEq)]
+    struct NotClone(u8);
+
+    let mut map = DefaultBTreeMap::<u8, NotClone>::new();
+    map.insert(1, NotClone(1));
+    map.insert(7, NotClone(7));
+
+    let upper = map.split_off(&5);
+
+    assert_eq!(&NotClone(1), map.get(&1));
+    assert_eq!(&NotClone(7), upper.get(&7));
+    assert_eq!(&NotClone(0), upper.get(&1));
+}
+
+#[test]
+fn get_floor_ceiling_btree() {
+    let map: DefaultBTreeMap<i8, i8> = defaultbtreemap!((0, 1), (10, 2), (20, 3));
+
+    assert_eq!((&0, &1), map.get_floor(&5));
+    assert_eq!((&10, &2), map.get_floor(&10));
+    assert_eq!((&20, &3), map.get_floor(&100));
+    assert_eq!((&-5, &0), map.get_floor(&-5));
+
+    assert_eq!((&10, &2), map.get_ceiling(&5));
+    assert_eq!((&0, &1), map.get_ceiling(&0));
+    assert_eq!((&0, &1), map.get_ceiling(&-100));
+    assert_eq!((&21, &0), map.get_ceiling(&21));
+}
+
+#[test]
+fn range_prefix_btree() {
+    let map: DefaultBTreeMap<&str, i8> = defaultbtreemap!(
+        ("a", 0),
+        ("ab", 1),
+        ("abc", 2),
+        ("abd", 3),
+        ("ac", 4),
+        ("b", 5),
+    );
+
+    let values: Vec<i8> = map.range_prefix("ab").map(|(_, value)| *value).collect();
+
+    assert_eq!(vec![1, 2, 3], values);
+    assert_eq!(6, map.range_prefix("").count());
+    assert_eq!(0, map.range_prefix("z").count());
+}
+
+#[test]
+fn fill_range_btree() {
+    let mut map = DefaultBTreeMap::<i8, i8>::new();
+    map.set_range_default(5..10, -1);
+    map.insert(2, 20);
+    map.fill_range(0..7);
+
+    let pairs: Vec<(i8, i8)> = map.into_iter().collect();
+
+    assert_eq!(
+        vec![(0, 0), (1, 0), (2, 20), (3, 0), (4, 0), (5, -1), (6, -1)],
+        pairs
+    );
+}
+
+#[test]
+fn iter_range_defaulted_btree() {
+    let mut map: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 10), (3, 30));
+    map.set_range_default(4..6, -1);
+
+    let pairs: Vec<(i8, i8)> = map
+        .iter_range_defaulted(0..6)
+        .map(|(key, value)| (key, *value))
+        .collect();
+
+    assert_eq!(
+        vec![(0, 0), (1, 10), (2, 0), (3, 30), (4, -1), (5, -1)],
+        pairs
+    );
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn iter_range_defaulted_unsorted_btree() {
+    let mut map: DefaultBTreeMap<i8, i8> = defaultbtreemap!((1, 10), (3, 30), (7, 70));
+    map.set_range_default(4..6, -1);
+    let keys = [3, 3, 8, 1, 5, 0, 7, 2, 7, -4];
+
+    let pairs: Vec<(i8, i8)> = map
+        .iter_range_defaulted(keys)
+        .map(|(key, value)| (key, *value))
+        .collect();
+    let expected: Vec<(i8, i8)> = keys.into_iter().map(|key| (key, *map.get(&key))).collect();
+
+    assert_eq!(expected, pairs);
+    assert_eq!(3, map.len());
+}
+
+#[test]
+fn to_sorted_vec_by_value_btree() {
+    let mut map = DefaultBTreeMap::<i8, i8>::new();
+    map.insert(3, 1);
+    map.insert(1, 2);
+    map.insert(2, 1);
+
+    assert_eq!(vec![(2, 1), (3, 1), (1, 2)], map.to_sorted_vec_by_value());
+    assert_eq!(
+        vec![(3, 1), (2, 1), (1, 2)],
+        map.to_sorted_vec_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+    );
+}
+
+#[test]
+fn value_counts_btree() {
+    let map: DefaultBTreeMap<u8, u8> = (0..10).map(|i| (i, i % 3)).collect();
+
+    let counts: Vec<(&u8, usize)> = map.value_counts().into_iter().collect();
+
+    assert_eq!(vec![(&0, 4), (&1, 3), (&2, 3)], counts);
+}
+
+#[test]
+fn swap_values_btree() {
+    let mut map: DefaultBTreeMap<u8, u8> = (0..3).map(|i| (i, i * 10)).collect();
+
+    map.swap_values(&0, &2);
+    map.swap_values(&1, &5);
+
+    let collected: Vec<(u8, u8)> = map.into_iter().collect();
+
+    assert_eq!(vec![(0, 20), (1, 0), (2, 0), (5, 10)], collected);
+}
+
+#[test]
+fn remove_many_btree() {
+    let mut map: DefaultBTreeMap<u8, u8> = (0..5).map(|i| (i, i + 1)).collect();
+
+    assert_eq!(vec![5, 1, 0], map.remove_many(vec![4, 0, 9]));
+    assert_eq!(vec![1, 2, 3], map.into_keys().collect::<Vec<_>>());
+}
+
+#[test]
+fn key_set_operations_btree() {
+    let old: DefaultBTreeMap<u8, u8> = (0..4).map(|i| (i, i)).collect();
+    let new: DefaultBTreeMap<u8, u8> = (2..6).map(|i| (i, i * 10)).collect();
+
+    assert_eq!(
+        vec![(&0, &0), (&1, &1)],
+        old.difference(&new).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![(&2, &2, &20), (&3, &3, &30)],
+        old.intersection(&new).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![(&0, &0), (&1, &1), (&4, &40), (&5, &50)],
+        old.symmetric_difference(&new).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn predicates_btree() {
+    let map: DefaultBTreeMap<u8, u8> = (0..10).map(|i| (i, i * i)).collect();
+
+    assert!(map.all(|key, value| value >= key));
+    assert!(map.any(|_, value| *value == 81));
+    assert_eq!(Some((&4, &16)), map.find(|_, value| *value > 10));
+}
+
+#[test]
+fn reverse_lookup_btree() {
+    let map: DefaultBTreeMap<u8, u8> = (0..6).map(|i| (i, i / 2)).collect();
+
+    assert_eq!(vec![&4, &5], map.keys_with_value(&2).collect::<Vec<_>>());
+    assert!(map.contains_value(&0));
+    assert!(!map.contains_value(&3));
+}
+
+#[test]
+fn clear_to_default_btree() {
+    let mut map: DefaultBTreeMap<u8, u8> = (0..4).map(|i| (i, 100)).collect();
+    map.set_range_default(2..10, 5);
+
+    map.clear_to_default();
+
+    let collected: Vec<(u8, u8)> = map.into_iter().collect();
+
+    assert_eq!(vec![(0, 0), (1, 0), (2, 5), (3, 5)], collected);
+}
+
+#[test]
+fn ensure_keys_btree() {
+    let mut map: DefaultBTreeMap<u8, u8> = DefaultBTreeMap::new();
+    map.set_range_default(10..20, 1);
+    map.insert(12, 5);
+
+    map.ensure_keys([5, 11, 12]);
+
+    let collected: Vec<(u8, u8)> = map.into_iter().collect();
+
+    assert_eq!(vec![(5, 0), (11, 1), (12, 5)], collected);
+}
+
+#[test]
+fn get_key_value_mut_btree() {
+    let mut map: DefaultBTreeMap<String, Vec<usize>> = DefaultBTreeMap::new();
+
+    for word in ["apple", "banana", "apple"] {
+        let (key, positions) = map.get_key_value_mut(&word.to_string());
+        positions.push(key.len());
+    }
+
+    assert_eq!(&vec![5, 5], map.get(&"apple".to_string()));
+    assert_eq!(&vec![6], map.get(&"banana".to_string()));
+}
+
+#[test]
+fn clone_from_btree() {
+    let mut source: DefaultBTreeMap<u8, String> = (0..10).map(|i| (i, i.to_string())).collect();
+    source.set_range_default(100..200, String::from("high"));
+    let mut target = source.clone();
+
+    target.get_mut(&3).push('!');
+    target.clone_from(&source);
+
+    assert_eq!(source, target);
+    assert_eq!("high", target.get(&150));
+
+    target.insert(50, String::new());
+    target.clone_from(&source);
+
+    assert_eq!(source, target);
+    assert!(!target.contains_key(&50));
+}
+
+#[test]
+fn clone_from_keys_equal_by_ord_btree() {
+    #[derive(Clone, Debug)]
+    struct Key {
+        id: u8,
+        label: &'static str,
+    }
+
+    impl PartialEq for Key {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Key {}
+
+    impl PartialOrd for Key {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Key {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.id.cmp(&other.id)
+        }
+    }
+
+    let mut source = DefaultBTreeMap::<Key, u8>::new();
+    source.insert(
+        Key {
+            id: 1,
+            label: "new",
+        },
+        1,
+    );
+    let mut target = DefaultBTreeMap::<Key, u8>::new();
+    target.insert(
+        Key {
+            id: 1,
+            label: "old",
+        },
+        2,
+    );
+
+    target.clone_from(&source);
+
+    let labels: Vec<&str> = target.keys().map(|key| key.label).collect();
+    assert_eq!(vec!["new"], labels);
+    assert_eq!(&1, target.get(&Key { id: 1, label: "" }));
+}
+
+#[test]
+fn cloned_iterators_btree() {
+    let map: DefaultBTreeMap<u8, String> = (0..3).map(|i| (i, i.to_string())).collect();
+
+    assert_eq!(vec![0, 1, 2], map.keys_cloned().collect::<Vec<_>>());
+    assert_eq!(vec!["0", "1", "2"], map.values_cloned().collect::<Vec<_>>());
+}
+
+#[test]
+fn retain_values_btree() {
+    let mut map: DefaultBTreeMap<u8, u8> = (0..6).map(|i| (i, i % 3)).collect();
+
+    map.retain_values(|value| *value == 0);
+
+    assert_eq!(vec![0, 3], map.into_keys().collect::<Vec<_>>());
+}
+
+#[test]
+fn pop_by_value_btree() {
+    let mut map: DefaultBTreeMap<u8, u8> = [(1, 5), (2, 9), (3, 9), (4, 5)].into_iter().collect();
+
+    assert_eq!(Some((3, 9)), map.pop_max_by_value());
+    assert_eq!(Some((1, 5)), map.pop_min_by_value());
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn get_mut_cow_btree() {
+    let mut map = DefaultBTreeMap::<String, Vec<u8>>::new();
+
+    map.get_mut_cow(Cow::Borrowed("a")).push(1);
+    map.get_mut_cow(Cow::Borrowed("a")).push(2);
+    map.get_mut_cow(Cow::<str>::Owned(String::from("b")))
+        .push(3);
+
+    let golden: Vec<(String, Vec<u8>)> = vec![("a".into(), vec![1, 2]), ("b".into(), vec![3])];
+    assert_eq!(golden, map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn get_disjoint_mut_or_default_btree() {
+    let mut map = DefaultBTreeMap::<u8, u8>::new();
+    map.set_range_default(10..20, 5);
+    map.insert(30, 1);
+    map.insert(1, 2);
+
+    let [a, b, c] = map.get_disjoint_mut_or_default([&30, &15, &1]);
+    *b += *a + *c;
+    *a = 0;
+
+    let golden: Vec<(u8, u8)> = vec![(1, 2), (15, 8), (30, 0)];
+    assert_eq!(golden, map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn get_disjoint_mut_or_default_far_apart_btree() {
+    let mut map: DefaultBTreeMap<u32, u32> = (0..10_000).map(|key| (key * 2, key)).collect();
+
+    let [last, first, missing] = map.get_disjoint_mut_or_default([&19_998, &0, &20_001]);
+    *missing = *first + *last;
+    *first = 1;
+
+    assert_eq!(&1, map.get(&0));
+    assert_eq!(&9_999, map.get(&20_001));
+    assert_eq!(10_001, map.len());
+}
+
+#[test]
+#[should_panic(expected = "duplicate keys found")]
+fn get_disjoint_mut_or_default_duplicate_btree() {
+    let mut map = DefaultBTreeMap::<u8, u8>::new();
+    let _ = map.get_disjoint_mut_or_default([&2, &1, &2]);
+}
+
+#[test]
+fn insert_into_btree() {
+    let mut map = DefaultBTreeMap::<String, u64>::new();
+
+    map.insert_into("a", 1u8);
+    map.insert_into("a", 2u32);
+    map.insert_into(String::from("b"), 3u64);
+
+    let golden: Vec<(String, u64)> = vec![("a".into(), 2), ("b".into(), 3)];
+    assert_eq!(golden, map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn insert_or_previous_btree() {
+    let mut map = DefaultBTreeMap::<u8, u8>::new();
+    map.set_range_default(10..20, 9);
+
+    assert_eq!(0, map.insert_or_previous(1, 1));
+    assert_eq!(9, map.insert_or_previous(15, 2));
+    assert_eq!(2, map.insert_or_previous(15, 3));
+
+    let golden: Vec<(u8, u8)> = vec![(1, 1), (15, 3)];
+    assert_eq!(golden, map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn update_many_btree() {
+    let mut map = DefaultBTreeMap::<u8, u8>::new();
+    map.set_range_default(10..20, 100);
+    map.insert(1, 1);
+
+    map.update_many([1, 2, 15, 15], |key, value| *value += key);
+
+    let golden: Vec<(u8, u8)> = vec![(1, 2), (2, 2), (15, 130)];
+    assert_eq!(golden, map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn try_from_iter_btree() {
+    let map = DefaultBTreeMap::<u8, u8>::try_from_iter((0..4).map(|i| (i, i * 2))).unwrap();
+    assert_eq!(4, map.len());
+    assert_eq!(&6, map.get(&3));
+
+    let error = DefaultBTreeMap::<u8, u8>::try_from_iter([(1, 1), (2, 2), (2, 3), (1, 4)]);
+    assert_eq!(2, error.unwrap_err().into_key());
+}
+
+#[test]
+fn from_iter_merge_btree() {
+    let pairs = [(1, 5), (2, 3), (1, 9), (1, 2)];
+
+    let map =
+        DefaultBTreeMap::<u8, u8>::from_iter_merge(pairs, |max, value| *max = (*max).max(value));
+
+    assert_eq!(vec![(1, 9), (2, 3)], map.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn const_new_btree() {
+    static COUNTS: std::sync::Mutex<DefaultBTreeMap<u8, Vec<u8>>> =
+        std::sync::Mutex::new(DefaultBTreeMap::new());
+    static EMPTY: DefaultBTreeMap<u8, u8> = DefaultBTreeMap::new();
+
+    std::thread::scope(|scope| {
+        for i in 0..4 {
+            scope.spawn(move || COUNTS.lock().unwrap().get_mut(&(i % 2)).push(i));
+        }
+    });
+
+    let mut counts = COUNTS.lock().unwrap();
+    counts.get_mut(&0).sort();
+    counts.get_mut(&1).sort();
+    assert_eq!(&vec![0, 2], counts.get(&0));
+    assert_eq!(&vec![1, 3], counts.get(&1));
+    assert!(counts.get(&5).is_empty());
+
+    assert!(EMPTY.is_empty());
+    assert_eq!(&0, EMPTY.get(&1));
+}
+
+#[test]
+fn eq_ignores_unused_default_btree() {
+    let read: DefaultBTreeMap<u8, u8> = DefaultBTreeMap::new();
+    let unread: DefaultBTreeMap<u8, u8> = DefaultBTreeMap::new();
+
+    assert_eq!(&0, read.get(&1));
+    assert_eq!(read, unread);
+    assert_eq!(format!("{read:?}"), format!("{unread:?}"));
+}
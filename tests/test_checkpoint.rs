@@ -0,0 +1,70 @@
+use defaultdict::*;
+
+fn sorted<'a>(map: &'a CheckpointedHashMap<&str, u32>) -> Vec<(&'a &'a str, Change)> {
+    let mut changes: Vec<_> = map.changed_since_checkpoint().collect();
+    changes.sort();
+    changes
+}
+
+#[test]
+fn changes_since_checkpoint() {
+    let mut map = CheckpointedHashMap::change_tracked();
+    map.insert("a", 1);
+    let _ = map.get_mut(&"b");
+
+    assert_eq!(
+        vec![(&"a", Change::Inserted), (&"b", Change::Inserted)],
+        sorted(&map)
+    );
+
+    map.checkpoint();
+    assert_eq!(0, map.changed_since_checkpoint().count());
+
+    map.insert("a", 5);
+    map.insert("c", 1);
+    let _ = map.remove(&"c");
+    let _ = map.remove(&"b");
+    map.insert("b", 7);
+
+    assert_eq!(
+        vec![(&"a", Change::Modified), (&"b", Change::Modified)],
+        sorted(&map)
+    );
+    assert_eq!(3, map.listener().len());
+}
+
+#[test]
+fn clear_checkpoint() {
+    let mut map = CheckpointedHashMap::change_tracked();
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.checkpoint();
+
+    map.clear();
+    map.update("d", |value| *value += 1);
+
+    assert_eq!(
+        vec![
+            (&"a", Change::Removed),
+            (&"b", Change::Removed),
+            (&"d", Change::Inserted)
+        ],
+        sorted(&map)
+    );
+}
+
+#[test]
+fn get_mut_modifies_checkpoint() {
+    let mut map = CheckpointedHashMap::change_tracked();
+    map.insert("a", 1);
+    map.checkpoint();
+
+    *map.get_mut(&"a") += 1;
+    *map.get_mut(&"b") += 1;
+
+    assert_eq!(
+        vec![(&"a", Change::Modified), (&"b", Change::Inserted)],
+        sorted(&map)
+    );
+    assert_eq!(&2, map.get(&"a"));
+}
@@ -0,0 +1,40 @@
+use defaultdict::*;
+
+#[test]
+fn count_min_never_underestimates() {
+    let mut sketch = CountMinSketch::new(50, 4);
+    let mut exact = DefaultHashMap::<u32, u64>::new();
+    for i in 0..5000u32 {
+        let key = (i * 7919) % 400;
+        sketch.add(&key);
+        *exact.get_mut(&key) += 1;
+    }
+
+    for (key, count) in &exact {
+        assert!(sketch.estimate(key) >= *count);
+    }
+    assert_eq!(5000, sketch.total());
+}
+
+#[test]
+fn count_min_exact_for_single_key() {
+    let mut sketch = CountMinSketch::<str>::with_error(0.001, 0.001);
+    sketch.add_n("only", 42);
+
+    assert_eq!(42, sketch.estimate("only"));
+    assert_eq!(0, sketch.estimate("other"));
+
+    sketch.clear();
+
+    assert_eq!(0, sketch.estimate("only"));
+    assert_eq!(0, sketch.total());
+}
+
+#[test]
+#[should_panic]
+fn count_min_merge_mismatched() {
+    let mut a = CountMinSketch::<u8>::new(10, 2);
+    let b = CountMinSketch::<u8>::new(10, 3);
+
+    a.merge(&b);
+}
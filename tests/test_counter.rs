@@ -0,0 +1,91 @@
+use defaultdict::*;
+
+#[test]
+fn counter_add_and_count() {
+    let mut counter = Counter::new();
+    for word in "the cat and the hat".split(' ') {
+        counter.add(&word);
+    }
+
+    assert_eq!(2, counter.count("the"));
+    assert_eq!(1, counter.count("cat"));
+    assert_eq!(0, counter.count("dog"));
+    assert_eq!(4, counter.len());
+    assert_eq!(5, counter.total());
+}
+
+#[test]
+fn counter_subtract_removes_at_zero() {
+    let mut counter: Counter<char> = "aab".chars().collect();
+    counter.subtract(&'a');
+
+    assert_eq!(1, counter.count(&'a'));
+    assert_eq!(2, counter.total());
+
+    counter.subtract_n(&'a', 10);
+    counter.subtract(&'z');
+
+    assert_eq!(0, counter.count(&'a'));
+    assert_eq!(1, counter.len());
+    assert_eq!(1, counter.total());
+}
+
+#[test]
+fn counter_most_common() {
+    let counter: Counter<u8> = [3, 1, 3, 2, 3, 1].into_iter().collect();
+
+    assert_eq!(vec![(&3, 3), (&1, 2), (&2, 1)], counter.most_common(10));
+    assert_eq!(vec![(&3, 3)], counter.most_common(1));
+    assert!(counter.most_common(0).is_empty());
+}
+
+#[test]
+fn counter_elements() {
+    let mut counter = Counter::new();
+    counter.add_n(&"x", 2);
+    counter.add_n(&"y", 0);
+
+    assert_eq!(vec![&"x", &"x"], counter.elements().collect::<Vec<_>>());
+    assert_eq!(1, counter.len());
+}
+
+#[test]
+fn counter_clear() {
+    let mut counter: Counter<u8> = [1, 2, 2].into_iter().collect();
+    counter.extend([4]);
+    counter.clear();
+
+    assert!(counter.is_empty());
+    assert_eq!(0, counter.total());
+}
+
+#[test]
+fn counter_matches_approximate_counters() {
+    let mut counter = Counter::new();
+    let mut sketch = CountMinSketch::new(64, 4);
+    let mut hitters = HeavyHitters::new(8);
+    for word in "a b a c a b".split(' ') {
+        counter.add(&word);
+        sketch.add(&word);
+        hitters.add(&word);
+    }
+    counter.add_n(&"d", 4);
+    sketch.add_n(&"d", 4);
+    hitters.add_n(&"d", 4);
+
+    assert_eq!(counter.total(), sketch.total());
+    assert_eq!(counter.total(), hitters.total());
+    assert_eq!(counter.most_common(2), hitters.most_common(2));
+    assert!(sketch.estimate(&"a") >= counter.count(&"a"));
+}
+
+#[test]
+fn counter_saturates() {
+    let mut counter = Counter::new();
+    counter.add_n(&'a', u64::MAX);
+    counter.add(&'a');
+    counter.add(&'b');
+
+    assert_eq!(u64::MAX, counter.count(&'a'));
+    assert_eq!(u64::MAX, counter.total());
+}
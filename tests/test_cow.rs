@@ -0,0 +1,40 @@
+use defaultdict::*;
+
+#[test]
+fn clone_shares_cow() {
+    let mut map = CowHashMap::<i8, i8>::new();
+    for i in 0..10 {
+        map.insert(i, i);
+    }
+
+    let clone = map.clone();
+
+    assert!(map.is_shared());
+    assert!(clone.is_shared());
+    assert_eq!(map, clone);
+}
+
+#[test]
+fn mutate_copies_cow() {
+    let mut map = CowHashMap::<i8, i8>::new();
+    map.insert(1, 1);
+
+    let mut clone = map.clone();
+    *clone.get_mut(&1) += 1;
+    let _ = clone.remove(&2);
+
+    assert!(!map.is_shared());
+    assert!(!clone.is_shared());
+    assert_eq!(&1, map.get(&1));
+    assert_eq!(&2, clone.get(&1));
+}
+
+#[test]
+fn into_inner_cow() {
+    let map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1));
+    let cow = CowHashMap::from(map.clone());
+    let clone = cow.clone();
+
+    assert_eq!(map, cow.into_inner());
+    assert_eq!(map, clone.into_inner());
+}
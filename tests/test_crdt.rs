@@ -0,0 +1,79 @@
+use defaultdict::*;
+
+#[test]
+fn gcounter_merge_converges() {
+    let mut a = GCounterMap::new(1);
+    let mut b = GCounterMap::new(2);
+    let mut c = GCounterMap::new(3);
+    a.increment("x", 1);
+    b.increment("x", 2);
+    b.increment("y", 5);
+    c.increment("x", 4);
+
+    a.merge(&b);
+    a.merge(&c);
+    c.merge(&b);
+    c.merge(&a);
+    b.merge(&c);
+
+    for counter in [&a, &b, &c] {
+        assert_eq!(7, counter.get(&"x"));
+        assert_eq!(5, counter.get(&"y"));
+        assert_eq!(0, counter.get(&"z"));
+    }
+}
+
+#[test]
+fn gcounter_merge_idempotent() {
+    let mut a = GCounterMap::new('a');
+    let mut b = GCounterMap::new('b');
+    a.increment(1, 3);
+    b.increment(1, 2);
+
+    a.merge(&b);
+    a.merge(&b);
+    a.merge(&a.clone());
+
+    assert_eq!(5, a.get(&1));
+    assert_eq!(1, a.len());
+    assert_eq!(&'a', a.replica());
+}
+
+#[test]
+fn gcounter_local_increments_after_merge() {
+    let mut a = GCounterMap::new(1);
+    let mut b = GCounterMap::new(2);
+    a.increment("x", 1);
+    b.merge(&a);
+    a.increment("x", 1);
+    b.increment("x", 1);
+
+    b.merge(&a);
+
+    assert_eq!(3, b.get(&"x"));
+    assert_eq!(&3, b.to_map().get(&"x"));
+}
+
+#[test]
+fn pncounter_merge_converges() {
+    let mut a = PNCounterMap::new(1);
+    let mut b = PNCounterMap::new(2);
+    a.increment("stock", 10);
+    a.decrement("stock", 2);
+    b.decrement("stock", 3);
+    b.decrement("returns", 1);
+
+    a.merge(&b);
+    b.merge(&a);
+    b.merge(&a);
+
+    assert_eq!(5, a.get(&"stock"));
+    assert_eq!(5, b.get(&"stock"));
+    assert_eq!(-1, a.get(&"returns"));
+    assert!(a.contains_key(&"returns"));
+    assert!(!a.is_empty());
+
+    let totals = b.to_map();
+    assert_eq!(2, totals.len());
+    assert_eq!(&-1, totals.get(&"returns"));
+}
@@ -0,0 +1,72 @@
+use defaultdict::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+type Shared = Rc<RefCell<Vec<u8>>>;
+
+#[test]
+fn nested_maps_deep_clone() {
+    let mut map: DefaultHashMap<&str, DefaultHashMap<u8, Shared>> = DefaultHashMap::new();
+    let shared = Rc::new(RefCell::new(vec![1]));
+    map.get_mut(&"a").insert(1, Rc::clone(&shared));
+    map.get_mut(&"a").insert(2, Rc::clone(&shared));
+
+    let copy = map.deep_clone();
+    shared.borrow_mut().push(2);
+
+    assert_eq!(vec![1, 2], *map.get(&"a").get(&1).borrow());
+    assert_eq!(vec![1], *copy.get(&"a").get(&1).borrow());
+    assert!(!Rc::ptr_eq(copy.get(&"a").get(&1), copy.get(&"a").get(&2)));
+}
+
+#[test]
+fn keeps_configuration_deep_clone() {
+    let mut map: DefaultHashMap<u8, Arc<String>> = DefaultHashMap::builder()
+        .default_fn(|| Arc::new(String::from("none")))
+        .policy(Policy::InsertOnRead)
+        .build();
+    map.insert(1, Arc::new(String::from("one")));
+
+    let mut copy = map.deep_clone();
+
+    assert_eq!(map, copy);
+    assert!(!Arc::ptr_eq(map.get(&1), copy.get(&1)));
+    assert_eq!(Policy::InsertOnRead, copy.policy());
+    assert_eq!("none", copy.get_mut(&2).as_str());
+}
+
+#[test]
+fn btree_deep_clone() {
+    let mut map = DefaultBTreeMap::<u8, Option<Rc<RefCell<u8>>>>::new();
+    map.insert(1, Some(Rc::new(RefCell::new(1))));
+    map.insert(2, None);
+
+    let copy = map.deep_clone();
+    *map.get(&1).as_ref().unwrap().borrow_mut() = 5;
+
+    assert_eq!(1, *copy.get(&1).as_ref().unwrap().borrow());
+    assert_eq!(&None, copy.get(&2));
+    assert_eq!(2, copy.len());
+}
+
+#[derive(Clone, Default)]
+struct Counter(Arc<Mutex<u32>>);
+
+impl DeepClone for Counter {
+    fn deep_clone(&self) -> Self {
+        Counter(Arc::new(Mutex::new(*self.0.lock().unwrap())))
+    }
+}
+
+#[test]
+fn custom_deep_clone() {
+    let mut map = DefaultHashMap::<u8, Vec<Counter>>::new();
+    map.get_mut(&1).push(Counter::default());
+
+    let copy = map.deep_clone();
+    *map.get(&1)[0].0.lock().unwrap() += 1;
+
+    assert_eq!(0, *copy.get(&1)[0].0.lock().unwrap());
+}
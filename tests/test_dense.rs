@@ -0,0 +1,90 @@
+use defaultdict::*;
+
+use std::collections::HashMap;
+
+#[test]
+fn dense_counts_dense() {
+    let mut map = DenseDefaultMap::<u8, u32>::new();
+    for id in (0..=255).chain(0..10) {
+        *map.get_mut(id) += 1;
+    }
+
+    assert!(map.is_dense());
+    assert_eq!(256, map.len());
+    assert_eq!(&2, map.get(9));
+    assert_eq!(&1, map.get(10));
+
+    let keys: Vec<u8> = map.iter().map(|(key, _)| key).collect();
+    assert_eq!((0..=255).collect::<Vec<u8>>(), keys);
+}
+
+#[test]
+fn switch_to_sparse_dense() {
+    let mut map: DenseDefaultMap<u32, u32> = (0..10).map(|key| (key, key * 10)).collect();
+    assert!(map.is_dense());
+    assert_eq!(None, map.insert(63, 630));
+    assert!(map.is_dense());
+
+    assert_eq!(None, map.insert(1_000, 10_000));
+    assert!(!map.is_dense());
+    assert_eq!(12, map.len());
+    assert_eq!(&90, map.get(9));
+    assert_eq!(&630, map.get(63));
+    assert_eq!(&10_000, map.get(1_000));
+    assert_eq!(&0, map.get(11));
+
+    let entries: HashMap<u32, u32> = map.iter().map(|(key, value)| (key, *value)).collect();
+    let expected: HashMap<u32, u32> = (0..10)
+        .map(|key| (key, key * 10))
+        .chain([(63, 630), (1_000, 10_000)])
+        .collect();
+    assert_eq!(expected, entries);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert!(!map.is_dense());
+}
+
+#[test]
+fn matches_hashmap_dense() {
+    let mut map = DenseDefaultMap::<u64, u64>::new();
+    let mut reference: HashMap<u64, u64> = HashMap::new();
+    let mut state: u64 = 7;
+    for step in 0..3000 {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        let key = if step < 2000 {
+            (state >> 33) % 200
+        } else {
+            state >> 20
+        };
+        match step % 4 {
+            0 => assert_eq!(reference.remove(&key).unwrap_or_default(), map.remove(key)),
+            1 => *map.get_mut(key) += step,
+            _ => assert_eq!(reference.insert(key, step), map.insert(key, step)),
+        }
+        if step % 4 == 1 {
+            *reference.entry(key).or_default() += step;
+        }
+        assert_eq!(reference.len(), map.len());
+    }
+
+    assert!(!map.is_dense());
+    for (key, value) in &reference {
+        assert!(map.contains_key(*key));
+        assert_eq!(value, map.get(*key));
+    }
+}
+
+#[test]
+fn remove_dense() {
+    let mut map: DenseDefaultMap<usize, i32> = (0..4).map(|key| (key, 1)).collect();
+
+    assert_eq!(1, map.remove(2));
+    assert_eq!(0, map.remove(2));
+    assert_eq!(0, map.remove(100));
+    assert_eq!(3, map.len());
+    assert!(!map.contains_key(2));
+    assert_eq!("{0: 1, 1: 1, 3: 1}", format!("{map:?}"));
+}
@@ -0,0 +1,50 @@
+use defaultdict::*;
+
+#[test]
+fn normalize_counts() {
+    let counts: DefaultHashMap<&str, usize> = defaulthashmap!(("a", 1), ("b", 3), ("c", 0));
+
+    let probabilities = counts.normalize();
+
+    assert_eq!(3, probabilities.len());
+    assert_eq!(&0.25, probabilities.get(&"a"));
+    assert_eq!(&0.75, probabilities.get(&"b"));
+    assert_eq!(&0.0, probabilities.get(&"c"));
+}
+
+#[test]
+fn normalize_all_zero() {
+    let counts: DefaultHashMap<u8, u32> = defaulthashmap!((1, 0));
+
+    assert_eq!(&0.0, counts.normalize().get(&1));
+}
+
+#[test]
+fn entropy_counts() {
+    let uniform: DefaultHashMap<u8, u64> = (0..8).map(|i| (i, 3)).collect();
+    let certain: DefaultHashMap<u8, u64> = defaulthashmap!((0, 10), (1, 0));
+
+    assert!((uniform.entropy() - 3.0).abs() < 1e-12);
+    assert_eq!(0.0, certain.entropy());
+    assert_eq!(0.0, DefaultHashMap::<u8, u64>::new().entropy());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn sample_counts() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let counts: DefaultHashMap<&str, u32> =
+        defaulthashmap!(("rare", 1), ("common", 99), ("never", 0));
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let mut drawn = DefaultHashMap::<&str, u32>::new();
+    for _ in 0..1000 {
+        *drawn.get_mut(counts.sample(&mut rng).unwrap()) += 1;
+    }
+
+    assert_eq!(&0, drawn.get(&"never"));
+    assert!(drawn.get(&"common") > &900);
+    assert_eq!(None, DefaultHashMap::<u8, u32>::new().sample(&mut rng));
+}
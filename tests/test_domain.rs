@@ -0,0 +1,33 @@
+use defaultdict::*;
+
+#[test]
+fn domain_keys_reject_outside() {
+    let mut map = DomainHashMap::<&str, u32>::with_keys(["a", "b"]);
+
+    assert_eq!(Ok(None), map.insert("a", 1));
+    assert_eq!(Ok(Some(1)), map.insert("a", 2));
+    assert!(matches!(map.insert("c", 1), Err(Error::Validation(_))));
+    assert!(map.get_mut(&"c").is_err());
+
+    assert_eq!(&0, map.get(&"c"));
+    assert!(!map.contains_key(&"c"));
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn domain_predicate() {
+    let mut map = DomainHashMap::<i32, Vec<i32>>::with_predicate(|key| key % 2 == 0);
+
+    for i in 0..6 {
+        if let Ok(values) = map.get_mut(&(i % 4)) {
+            values.push(i);
+        }
+    }
+
+    assert!(map.is_allowed(&2));
+    assert!(!map.is_allowed(&3));
+    assert_eq!(&vec![0, 4], map.get(&0));
+    assert_eq!(&vec![2], map.get(&2));
+    assert_eq!(vec![2], map.remove(&2));
+    assert_eq!(1, map.into_inner().len());
+}
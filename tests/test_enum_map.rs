@@ -0,0 +1,72 @@
+use defaultdict::*;
+
+enum_key! {
+    #[derive(Debug, PartialEq)]
+    enum Direction {
+        North,
+        East,
+        South,
+        West,
+    }
+}
+
+#[test]
+fn enum_key_enum_map() {
+    assert_eq!(4, Direction::COUNT);
+    assert_eq!(2, Direction::South.index());
+    assert_eq!(Some(Direction::West), Direction::from_index(3));
+    assert_eq!(None, Direction::from_index(4));
+}
+
+#[test]
+fn get_mut_enum_map() {
+    let mut map = EnumDefaultMap::<Direction, u32>::new();
+    for direction in [Direction::North, Direction::North, Direction::West] {
+        *map.get_mut(&direction) += 1;
+    }
+
+    assert_eq!(&2, map.get(&Direction::North));
+    assert_eq!(&1, map.get(&Direction::West));
+    assert_eq!(&0, map.get(&Direction::East));
+    assert_eq!(&0, &map[&Direction::South]);
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn insert_remove_enum_map() {
+    let mut map = EnumDefaultMap::new();
+
+    assert_eq!(None, map.insert(Direction::East, String::from("a")));
+    assert_eq!(
+        Some(String::from("a")),
+        map.insert(Direction::East, String::from("b"))
+    );
+    assert_eq!(String::from("b"), map.remove(&Direction::East));
+    assert_eq!(String::new(), map.remove(&Direction::East));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn iter_enum_map() {
+    let map: EnumDefaultMap<Direction, i8> = vec![(Direction::West, 4), (Direction::North, 1)]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        vec![(Direction::North, &1), (Direction::West, &4)],
+        map.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn clone_eq_enum_map() {
+    let mut map = EnumDefaultMap::new();
+    map.insert(Direction::South, 3);
+    let clone = map.clone();
+
+    assert_eq!(map, clone);
+
+    map.clear();
+
+    assert_ne!(map, clone);
+}
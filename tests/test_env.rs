@@ -0,0 +1,28 @@
+use defaultdict::*;
+
+#[test]
+fn from_env_prefix_strips_prefix() {
+    std::env::set_var("DEFAULTDICT_TEST_ENV_A", "1");
+    std::env::set_var("DEFAULTDICT_TEST_ENV_B", "two");
+    std::env::set_var("DEFAULTDICT_OTHER_C", "3");
+
+    let map: DefaultHashMap<String, String> =
+        DefaultHashMap::from_env_prefix("DEFAULTDICT_TEST_ENV_");
+
+    assert_eq!(2, map.len());
+    assert_eq!("1", map.get("A"));
+    assert_eq!("two", map.get("B"));
+    assert_eq!("", map.get("C"));
+}
+
+#[test]
+fn from_env_prefix_normalized() {
+    std::env::set_var("DEFAULTDICT_NORM_DB__HOST", "localhost");
+
+    let map: DefaultHashMap<String, String> =
+        DefaultHashMap::from_env_prefix_with("DEFAULTDICT_NORM_", |name| {
+            name.to_lowercase().replace("__", ".")
+        });
+
+    assert_eq!("localhost", map.get("db.host"));
+}
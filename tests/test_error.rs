@@ -0,0 +1,61 @@
+use defaultdict::*;
+
+#[test]
+fn display_error() {
+    assert_eq!("the key is already present", Error::Occupied.to_string());
+    assert_eq!(
+        "loading the value failed: timeout",
+        Error::Loader("timeout".to_string()).to_string()
+    );
+    assert_eq!(
+        "validation failed: empty key",
+        Error::Validation("empty key".to_string()).to_string()
+    );
+}
+
+#[test]
+fn boxed_error() {
+    fn fail() -> Result<(), Box<dyn std::error::Error>> {
+        Err(Error::Occupied)?
+    }
+
+    let error = fail().expect_err("the error is returned");
+
+    assert_eq!(Some(&Error::Occupied), error.downcast_ref::<Error>());
+}
+
+#[test]
+fn duplicate_key_error() {
+    let error =
+        DefaultHashMap::<String, u8>::try_from_iter([("a".to_string(), 1), ("a".into(), 2)])
+            .unwrap_err();
+
+    assert_eq!("a", error.key());
+    assert_eq!("duplicate key \"a\"", error.to_string());
+
+    let boxed: Box<dyn std::error::Error> = Box::new(error);
+    assert!(boxed.downcast_ref::<DuplicateKeyError<String>>().is_some());
+}
+
+#[test]
+fn duplicate_key_into_error() {
+    fn build() -> Result<DefaultBTreeMap<u8, u8>, Error> {
+        Ok(DefaultBTreeMap::try_from_iter([(1, 1), (2, 2), (1, 3)])?)
+    }
+
+    assert_eq!(Err(Error::DuplicateKey(String::from("1"))), build());
+}
+
+#[test]
+fn loader_error() {
+    let mut map: MemoMap<u8, u8> = MemoMap::new(|key: &u8| {
+        key.checked_mul(2)
+            .ok_or_else(|| Error::Loader(format!("{key} is too large")))
+    });
+
+    assert_eq!(Ok(&4), map.get(&2));
+    assert_eq!(
+        Err(Error::Loader(String::from("200 is too large"))),
+        map.get(&200)
+    );
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use defaultdict::*;
+
+#[test]
+fn flush_on_max_entries() {
+    let mut batches: Vec<DefaultHashMap<u8, u8>> = Vec::new();
+    {
+        let mut map = FlushingMap::new(|batch| batches.push(batch)).max_entries(3);
+        for i in 0..10 {
+            map.update(i, |value| *value += i);
+            assert!(map.len() <= 3);
+        }
+    }
+
+    assert_eq!(3, batches.len());
+    assert_eq!(
+        vec![4, 4, 2],
+        batches.iter().map(|batch| batch.len()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        45,
+        batches
+            .iter()
+            .flat_map(|batch| batch.values())
+            .map(|v| *v as u32)
+            .sum::<u32>()
+    );
+}
+
+#[test]
+fn flush_on_interval() {
+    let mut batches: Vec<DefaultHashMap<&str, u8>> = Vec::new();
+    {
+        let mut map =
+            FlushingMap::new(|batch| batches.push(batch)).interval(Duration::from_secs(60));
+        let start = Instant::now();
+        map.update("a", |value| *value += 1);
+
+        map.tick_at(start);
+        assert_eq!(1, map.len());
+
+        map.tick_at(start + Duration::from_secs(61));
+        assert!(map.is_empty());
+
+        map.tick_at(start + Duration::from_secs(62));
+    }
+
+    assert_eq!(1, batches.len());
+    assert_eq!(&1, batches[0].get(&"a"));
+}
+
+#[test]
+fn flush_manually() {
+    let mut batches: Vec<DefaultHashMap<u8, Vec<u8>>> = Vec::new();
+    {
+        let mut map = FlushingMap::new(|batch| batches.push(batch));
+        map.update(1, |values| values.push(1));
+        map.flush();
+        map.flush();
+        map.update(1, |values| values.push(2));
+        assert_eq!(&vec![2], map.as_map().get(&1));
+    }
+
+    assert_eq!(2, batches.len());
+    assert_eq!(&vec![1], batches[0].get(&1));
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "fxhash")]
+
+use defaultdict::*;
+
+#[test]
+fn with_fxhash_fxhash() {
+    let mut map: DefaultFxHashMap<String, u32> = DefaultHashMap::with_fxhash();
+    for word in "a b a c a".split(' ') {
+        *map.get_mut(&word.to_string()) += 1;
+    }
+
+    assert_eq!(&3, map.get(&String::from("a")));
+    assert_eq!(&0, map.get(&String::from("d")));
+}
+
+#[test]
+fn default_fxhash() {
+    let map: DefaultFxHashMap<i8, i8> = DefaultFxHashMap::default();
+
+    assert!(map.is_empty());
+}
@@ -0,0 +1,72 @@
+use defaultdict::*;
+
+#[test]
+fn guard_writes_back() {
+    let mut map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1));
+
+    {
+        let mut one = map.guard(1);
+        *one += 10;
+        let mut two = DefaultHashMap::<i8, i8>::new();
+        *two.guard(2) += 2;
+    }
+    *map.guard(3) += 3;
+
+    let golden: DefaultHashMap<i8, i8> = defaulthashmap!((1, 11), (3, 3));
+    assert_eq!(golden, map);
+}
+
+#[test]
+fn guard_key() {
+    let mut map = DefaultHashMap::<&str, u8>::new();
+    let guard = map.guard("key");
+
+    assert_eq!(&"key", guard.key());
+    assert_eq!(0, *guard);
+}
+
+#[test]
+fn guard_remove_if_default() {
+    let mut map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+
+    *map.guard(1).remove_if_default() -= 1;
+    *map.guard(2).remove_if_default() -= 1;
+    let _ = map.guard(3).remove_if_default();
+
+    let golden: DefaultHashMap<i8, i8> = defaulthashmap!((2, 1));
+    assert_eq!(golden, map);
+}
+
+#[test]
+fn guard_remove_if_default_custom_default() {
+    let mut map: DefaultHashMap<&str, i8> = DefaultHashMap::builder().default_value(5).build();
+    map.insert("a", 4);
+
+    *map.guard("a").remove_if_default() += 1;
+
+    assert!(!map.contains_key(&"a"));
+}
+
+#[test]
+fn guard_keep_if() {
+    let mut map: DefaultHashMap<i8, Vec<i8>> = defaulthashmap!((1, vec![1]));
+
+    map.guard(1)
+        .keep_if(|values| values.len() < 3)
+        .extend([2, 3]);
+    map.guard(2).keep_if(|values| values.len() < 3).push(1);
+
+    assert!(!map.contains_key(&1));
+    assert_eq!(&vec![1], map.get(&2));
+}
+
+#[test]
+fn guard_keep_if_all_checks() {
+    let mut map = DefaultHashMap::<i8, i8>::new();
+
+    *map.guard(1).keep_if(|v| *v > 0).keep_if(|v| *v < 10) = 20;
+    *map.guard(2).keep_if(|v| *v > 0).keep_if(|v| *v < 10) = 5;
+
+    assert!(!map.contains_key(&1));
+    assert_eq!(&5, map.get(&2));
+}
@@ -1,5 +1,6 @@
 use defaultdict::*;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 #[test]
@@ -87,6 +88,30 @@ fn capacity_hashmap() {
     assert_eq!(map.capacity(), 14);
 }
 
+#[test]
+fn with_capacity_hashmap() {
+    let mut map = DefaultHashMap::<i8, i8>::with_capacity(50);
+    let capacity = map.capacity();
+    for i in 0..50 {
+        map.insert(i, i);
+    }
+
+    assert!(capacity >= 50);
+    assert_eq!(capacity, map.capacity());
+    assert_eq!(&0, map.get(&60));
+}
+
+#[test]
+fn with_capacity_and_hasher_hashmap() {
+    let map = DefaultHashMap::<i8, i8, SeededState>::with_capacity_and_hasher(
+        20,
+        SeededState::with_seed(7),
+    );
+
+    assert!(map.capacity() >= 20);
+    assert!(map.is_empty());
+}
+
 #[test]
 fn drain_hashmap() {
     let mut map = DefaultHashMap::<i8, i8>::new();
@@ -293,8 +318,8 @@ fn contains_key_hashmap() {
     map.insert(1, 2);
     map.insert(3, 4);
 
-    assert_eq!(map.contains_key(&1), true);
-    assert_eq!(map.contains_key(&2), false);
+    assert!(map.contains_key(&1));
+    assert!(!map.contains_key(&2));
 }
 
 #[test]
@@ -327,8 +352,8 @@ fn is_empty_hashmap() {
 
     let map1 = DefaultHashMap::<i8, Vec<i8>>::new();
 
-    assert_eq!(map.is_empty(), false);
-    assert_eq!(map1.is_empty(), true);
+    assert!(!map.is_empty());
+    assert!(map1.is_empty());
 }
 
 #[test]
@@ -362,6 +387,19 @@ fn into_iter_default_hashmap() {
     assert_eq!(v, correct_v);
 }
 
+#[test]
+fn into_iter_key_without_clone_hashmap() {
+    #[derive(Debug, Hash, PartialEq, Eq)]
+    struct Key(u8);
+
+    let mut map = DefaultHashMap::<Key, u8>::new();
+    map.insert(Key(1), 2);
+
+    let v: Vec<(Key, u8)> = map.into_iter().collect();
+
+    assert_eq!(vec![(Key(1), 2)], v);
+}
+
 #[test]
 fn from_iter_hashmap() {
     let data = [(1, 1), (2, 2), (3, 3), (4, 4)];
@@ -458,6 +496,18 @@ fn index_hashmap_no_panic() {
     assert_eq!(0, default_value);
 }
 
+#[test]
+fn index_mut_hashmap() {
+    let mut map: DefaultHashMap<&str, u32> = DefaultHashMap::new();
+    for word in "a b a c a".split(' ') {
+        map[&word] += 1;
+    }
+
+    assert_eq!(3, map[&"a"]);
+    assert_eq!(1, map[&"c"]);
+    assert_eq!(3, map.len());
+}
+
 #[test]
 fn hashmap_into_defaulthashmap() {
     let mut hashmap: HashMap<u8, i8> = HashMap::new();
@@ -528,6 +578,97 @@ fn macro_test_hashmap() {
     assert_eq!(map1, _map1);
 }
 
+#[test]
+fn macro_arrow_hashmap() {
+    let map: DefaultHashMap<String, i8> =
+        defaulthashmap!("a".to_string() => 1, "b".to_string() => 2,);
+    let mixed: DefaultHashMap<String, i8> = defaulthashmap!(
+        format!("c{}", 1),
+        "d".to_string() => 4,
+        String::from("e")
+    );
+
+    let mut _map: DefaultHashMap<String, i8> = DefaultHashMap::new();
+    let _ = _map.insert("a".to_string(), 1);
+    let _ = _map.insert("b".to_string(), 2);
+
+    let mut _mixed: DefaultHashMap<String, i8> = DefaultHashMap::new();
+    let _ = _mixed.get_mut(&"c1".to_string());
+    let _ = _mixed.insert("d".to_string(), 4);
+    let _ = _mixed.get_mut(&"e".to_string());
+
+    assert_eq!(map, _map);
+    assert_eq!(mixed, _mixed);
+    assert_eq!(3, mixed.len());
+}
+
+#[test]
+fn macro_arrow_many_entries_hashmap() {
+    #[rustfmt::skip]
+    let map: DefaultHashMap<i32, i32> = defaulthashmap!(
+        0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7, 8 => 8, 9 => 9, 10 => 10,
+        11 => 11, 12 => 12, 13 => 13, 14 => 14, 15 => 15, 16 => 16, 17 => 17, 18 => 18, 19 => 19,
+        20 => 20, 21 => 21, 22 => 22, 23 => 23, 24 => 24, 25 => 25, 26 => 26, 27 => 27, 28 => 28,
+        29 => 29, 30 => 30, 31 => 31, 32 => 32, 33 => 33, 34 => 34, 35 => 35, 36 => 36, 37 => 37,
+        38 => 38, 39 => 39, 40 => 40, 41 => 41, 42 => 42, 43 => 43, 44 => 44, 45 => 45, 46 => 46,
+        47 => 47, 48 => 48, 49 => 49, 50 => 50, 51 => 51, 52 => 52, 53 => 53, 54 => 54, 55 => 55,
+        56 => 56, 57 => 57, 58 => 58, 59 => 59, 60 => 60, 61 => 61, 62 => 62, 63 => 63, 64 => 64,
+        65 => 65, 66 => 66, 67 => 67, 68 => 68, 69 => 69, 70 => 70, 71 => 71, 72 => 72, 73 => 73,
+        74 => 74, 75 => 75, 76 => 76, 77 => 77, 78 => 78, 79 => 79, 80 => 80, 81 => 81, 82 => 82,
+        83 => 83, 84 => 84, 85 => 85, 86 => 86, 87 => 87, 88 => 88, 89 => 89, 90 => 90, 91 => 91,
+        92 => 92, 93 => 93, 94 => 94, 95 => 95, 96 => 96, 97 => 97, 98 => 98, 99 => 99, 100 => 100,
+        101 => 101, 102 => 102, 103 => 103, 104 => 104, 105 => 105, 106 => 106, 107 => 107,
+        108 => 108, 109 => 109, 110 => 110, 111 => 111, 112 => 112, 113 => 113, 114 => 114,
+        115 => 115, 116 => 116, 117 => 117, 118 => 118, 119 => 119, 120 => 120, 121 => 121,
+        122 => 122, 123 => 123, 124 => 124, 125 => 125, 126 => 126, 127 => 127, 128 => 128,
+        129 => 129, 130 => 130, 131 => 131, 132 => 132, 133 => 133, 134 => 134, 135 => 135,
+        136 => 136, 137 => 137, 138 => 138, 139 => 139, 140 => 140, 141 => 141, 142 => 142,
+        143 => 143, 144 => 144, 145 => 145, 146 => 146, 147 => 147, 148 => 148, 149 => 149,
+        150 => 150, 151 => 151, 152 => 152, 153 => 153, 154 => 154, 155 => 155, 156 => 156,
+        157 => 157, 158 => 158, 159 => 159, 160 => 160, 161 => 161, 162 => 162, 163 => 163,
+        164 => 164, 165 => 165, 166 => 166, 167 => 167, 168 => 168, 169 => 169, 170 => 170,
+        171 => 171, 172 => 172, 173 => 173, 174 => 174, 175 => 175, 176 => 176, 177 => 177,
+        178 => 178, 179 => 179, 180 => 180, 181 => 181, 182 => 182, 183 => 183, 184 => 184,
+        185 => 185, 186 => 186, 187 => 187, 188 => 188, 189 => 189, 190 => 190, 191 => 191,
+        192 => 192, 193 => 193, 194 => 194, 195 => 195, 196 => 196, 197 => 197, 198 => 198,
+        199 => 199,
+    );
+
+    assert_eq!(200, map.len());
+    assert_eq!(&199, map.get(&199));
+    assert_eq!(&0, map.get(&200));
+}
+
+#[test]
+fn macro_mixed_many_entries_hashmap() {
+    #[rustfmt::skip]
+    let map: DefaultHashMap<i32, i32> = defaulthashmap!(
+        0, 1 => 1, 2, 3 => 3, 4, 5 => 5, 6, 7 => 7, 8, 9 => 9, 10, 11 => 11, 12, 13 => 13, 14,
+        15 => 15, 16, 17 => 17, 18, 19 => 19, 20, 21 => 21, 22, 23 => 23, 24, 25 => 25, 26,
+        27 => 27, 28, 29 => 29, 30, 31 => 31, 32, 33 => 33, 34, 35 => 35, 36, 37 => 37, 38,
+        39 => 39, 40, 41 => 41, 42, 43 => 43, 44, 45 => 45, 46, 47 => 47, 48, 49 => 49, 50,
+        51 => 51, 52, 53 => 53, 54, 55 => 55, 56, 57 => 57, 58, 59 => 59, 60, 61 => 61, 62,
+        63 => 63, 64, 65 => 65, 66, 67 => 67, 68, 69 => 69, 70, 71 => 71, 72, 73 => 73, 74,
+        75 => 75, 76, 77 => 77, 78, 79 => 79, 80, 81 => 81, 82, 83 => 83, 84, 85 => 85, 86,
+        87 => 87, 88, 89 => 89, 90, 91 => 91, 92, 93 => 93, 94, 95 => 95, 96, 97 => 97, 98,
+        99 => 99, 100, 101 => 101, 102, 103 => 103, 104, 105 => 105, 106, 107 => 107, 108,
+        109 => 109, 110, 111 => 111, 112, 113 => 113, 114, 115 => 115, 116, 117 => 117, 118,
+        119 => 119, 120, 121 => 121, 122, 123 => 123, 124, 125 => 125, 126, 127 => 127, 128,
+        129 => 129, 130, 131 => 131, 132, 133 => 133, 134, 135 => 135, 136, 137 => 137, 138,
+        139 => 139, 140, 141 => 141, 142, 143 => 143, 144, 145 => 145, 146, 147 => 147, 148,
+        149 => 149, 150, 151 => 151, 152, 153 => 153, 154, 155 => 155, 156, 157 => 157, 158,
+        159 => 159, 160, 161 => 161, 162, 163 => 163, 164, 165 => 165, 166, 167 => 167, 168,
+        169 => 169, 170, 171 => 171, 172, 173 => 173, 174, 175 => 175, 176, 177 => 177, 178,
+        179 => 179, 180, 181 => 181, 182, 183 => 183, 184, 185 => 185, 186, 187 => 187, 188,
+        189 => 189, 190, 191 => 191, 192, 193 => 193, 194, 195 => 195, 196, 197 => 197, 198,
+        199 => 199,
+    );
+
+    assert_eq!(200, map.len());
+    assert_eq!(&0, map.get(&198));
+    assert_eq!(&199, map.get(&199));
+}
+
 #[test]
 fn macro_test_slight_change_hashmap() {
     let map: DefaultHashMap<i8, i8> = defaulthashmap!(1, 2, 3);
@@ -547,3 +688,570 @@ fn macro_test_slight_change_hashmap() {
     assert_eq!(map, _map);
     assert_eq!(map1, _map1);
 }
+
+#[test]
+fn builder_default_value_hashmap() {
+    let mut map: DefaultHashMap<i8, i8> = DefaultHashMap::builder()
+        .capacity(20)
+        .default_value(-1)
+        .build();
+
+    assert!(map.capacity() >= 20);
+    assert_eq!(&-1, map.get(&1));
+
+    *map.get_mut(&1) += 5;
+
+    assert_eq!(&4, map.get(&1));
+    assert_eq!(4, map.remove(&1));
+    assert_eq!(-1, map.remove(&1));
+}
+
+#[test]
+fn builder_default_fn_hashmap() {
+    let mut map: DefaultHashMap<i8, Vec<i8>> = DefaultHashMap::builder()
+        .default_value(vec![0])
+        .default_fn(|| vec![1, 2])
+        .build();
+
+    map.get_mut(&1).push(3);
+
+    assert_eq!(&vec![1, 2, 3], map.get(&1));
+    assert_eq!(&vec![1, 2], map.get(&2));
+}
+
+#[test]
+fn builder_policy_hashmap() {
+    let mut map: DefaultHashMap<i8, i8> = DefaultHashMap::builder()
+        .policy(Policy::InsertOnRead)
+        .build();
+    let mut map1: DefaultHashMap<i8, i8> = DefaultHashMap::builder().build();
+
+    assert_eq!(&0, map.lookup(&1));
+    assert_eq!(&0, map1.lookup(&1));
+
+    assert!(map.contains_key(&1));
+    assert!(!map1.contains_key(&1));
+}
+
+#[test]
+fn builder_shrink_policy_hashmap() {
+    let mut map: DefaultHashMap<u32, u32> = DefaultHashMap::builder()
+        .shrink_policy(ShrinkPolicy::new(0.25).min_capacity(64))
+        .build();
+    let mut plain = DefaultHashMap::<u32, u32>::new();
+    map.extend((0..5000).map(|i| (i, i)));
+    plain.extend((0..5000).map(|i| (i, i)));
+
+    map.retain(|key, _| *key < 4000);
+    assert!(map.capacity() >= 4000);
+
+    map.retain_values(|value| *value < 10);
+    plain.retain_values(|value| *value < 10);
+    assert!(map.capacity() < 200);
+    assert!(plain.capacity() >= 5000);
+    assert_eq!(10, map.len());
+
+    map.extend((0..5000).map(|i| (i, i)));
+    map.clear();
+    assert!(map.capacity() >= 64);
+    assert!(map.capacity() < 200);
+
+    map.extend((0..5000).map(|i| (i, i)));
+    let removed = map.remove_many(0..4990);
+    assert_eq!(4990, removed.len());
+    assert!(map.capacity() < 200);
+}
+
+#[test]
+fn clear_shrink_policy_hashmap() {
+    let mut map: DefaultHashMap<u32, u32> = DefaultHashMap::builder()
+        .shrink_policy(ShrinkPolicy::new(0.5).min_capacity(16))
+        .build();
+    let mut plain = DefaultHashMap::<u32, u32>::new();
+    map.extend((0..1000).map(|i| (i, i)));
+    plain.extend((0..1000).map(|i| (i, i)));
+
+    map.clear();
+    plain.clear();
+
+    assert!(map.is_empty());
+    assert!(map.capacity() >= 16);
+    assert!(map.capacity() < 100);
+    assert!(plain.capacity() >= 1000);
+}
+
+#[test]
+#[should_panic(expected = "between 0 and 1")]
+fn builder_shrink_policy_invalid_hashmap() {
+    let _ = ShrinkPolicy::new(1.5);
+}
+
+#[test]
+fn diff_apply_patch_hashmap() {
+    let old: DefaultHashMap<i8, i8> = defaulthashmap!((0, 0), (1, 1), (2, 2), (3, 3));
+    let new: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 20), (4, 4), (5, 0));
+
+    let diff = old.diff(&new);
+    let mut changed = diff.changed().to_vec();
+    changed.sort();
+
+    assert_eq!(vec![(2, 2, 20)], changed);
+    assert_eq!(&[3], diff.removed());
+    assert_eq!(&[(4, 4)], diff.added());
+
+    let mut mirror = old.clone();
+    mirror.apply_patch(diff);
+    for key in 0..6 {
+        assert_eq!(new.get(&key), mirror.get(&key));
+    }
+    assert!(mirror.diff(&new).is_empty());
+}
+
+#[test]
+fn fill_range_hashmap() {
+    let mut map: DefaultHashMap<i8, i8> = DefaultHashMap::builder().default_value(-1).build();
+    map.insert(2, 20);
+    map.fill_range(0..4);
+
+    let expected: DefaultHashMap<i8, i8> = defaulthashmap!((0, -1), (1, -1), (2, 20), (3, -1));
+
+    assert_eq!(4, map.len());
+    for key in 0..4 {
+        assert_eq!(expected.get(&key), map.get(&key));
+    }
+}
+
+#[test]
+fn shard_by_hashmap() {
+    let mut map: DefaultHashMap<i32, i32> = DefaultHashMap::builder().default_fn(|| -1).build();
+    for i in 0..20 {
+        map.insert(i, i);
+    }
+
+    let shards = map.shard_by(3, |key| *key as usize);
+
+    assert_eq!(3, shards.len());
+    for (index, shard) in shards.iter().enumerate() {
+        assert!(shard.keys().all(|key| *key as usize % 3 == index));
+        assert_eq!(&-1, shard.get(&100));
+    }
+    assert_eq!(20, shards.iter().map(|shard| shard.len()).sum::<usize>());
+}
+
+#[test]
+fn shard_hashmap() {
+    let map: DefaultHashMap<String, usize> = (0..50).map(|i| (i.to_string(), i)).collect();
+
+    let shards = map.clone().shard(5);
+
+    let mut merged = DefaultHashMap::new();
+    for shard in shards {
+        for (key, value) in shard {
+            merged.insert(key, value);
+        }
+    }
+
+    assert_eq!(map, merged);
+}
+
+#[test]
+fn to_sorted_vec_by_hashmap() {
+    let mut tally = DefaultHashMap::<char, usize>::new();
+    for c in "mississippi".chars() {
+        *tally.get_mut(&c) += 1;
+    }
+
+    let descending = tally.to_sorted_vec_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    assert_eq!(vec![('i', 4), ('s', 4), ('p', 2), ('m', 1)], descending);
+    assert_eq!(('m', 1), tally.to_sorted_vec_by_value()[0]);
+}
+
+#[test]
+fn value_counts_hashmap() {
+    let map: DefaultHashMap<u8, String> = (0..10)
+        .map(|i| (i, if i % 3 == 0 { "fizz" } else { "" }.to_string()))
+        .collect();
+
+    let counts = map.value_counts();
+
+    assert_eq!(2, counts.len());
+    assert_eq!(&4, counts.get(&&"fizz".to_string()));
+    assert_eq!(&6, counts.get(&&String::new()));
+}
+
+#[test]
+fn swap_values_hashmap() {
+    let mut map: DefaultHashMap<&str, Vec<u8>> = DefaultHashMap::new();
+    map.insert("a", vec![1]);
+    map.insert("b", vec![2, 3]);
+
+    map.swap_values(&"a", &"b");
+
+    assert_eq!(&vec![2, 3], map.get(&"a"));
+    assert_eq!(&vec![1], map.get(&"b"));
+
+    map.swap_values(&"a", &"a");
+
+    assert_eq!(&vec![2, 3], map.get(&"a"));
+
+    map.swap_values(&"c", &"d");
+
+    assert!(map.contains_key(&"c"));
+    assert!(map.contains_key(&"d"));
+}
+
+#[test]
+fn insert_many_hashmap() {
+    let mut map = DefaultHashMap::<u16, u16>::new();
+    map.insert_many((0..1000).map(|i| (i, i)));
+
+    assert_eq!(1000, map.len());
+    assert!(map.capacity() >= 1000);
+
+    let previous = map.insert_many_returning((998..1002).map(|i| (i, 0)));
+
+    assert_eq!(vec![Some(998), Some(999), None, None], previous);
+    assert_eq!(1002, map.len());
+    assert_eq!(&0, map.get(&999));
+}
+
+#[test]
+fn extend_hashmap() {
+    let mut map: DefaultHashMap<u16, u16> = DefaultHashMap::builder().default_value(1).build();
+    map.extend((0..500).map(|i| (i, i)));
+
+    assert_eq!(500, map.len());
+    assert!(map.capacity() >= 500);
+
+    map.extend(vec![(0, 7), (600, 6)]);
+    map.extend_exact(10, (1000..1010).map(|i| (i, 0)));
+
+    assert_eq!(511, map.len());
+    assert_eq!(&7, map.get(&0));
+    assert_eq!(&1, map.get(&2000));
+}
+
+#[test]
+fn update_many_hashmap() {
+    let mut map: DefaultHashMap<String, Vec<usize>> =
+        DefaultHashMap::builder().default_fn(|| vec![0]).build();
+    map.insert("a".to_string(), vec![]);
+
+    let keys = ["a", "b", "b", "c"].map(String::from);
+    map.update_many(keys, |key, value| value.push(key.len()));
+
+    assert_eq!(&vec![1], map.get("a"));
+    assert_eq!(&vec![0, 1, 1], map.get("b"));
+    assert_eq!(&vec![0, 1], map.get("c"));
+    assert_eq!(3, map.len());
+}
+
+#[test]
+fn remove_many_hashmap() {
+    let mut map: DefaultHashMap<String, u8> = DefaultHashMap::builder().default_fn(|| 7).build();
+    map.insert("a".to_string(), 1);
+    map.insert("b".to_string(), 2);
+
+    let keys = ["b".to_string(), "c".to_string()];
+
+    assert_eq!(vec![2, 7], map.remove_many(&keys));
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn key_set_operations_hashmap() {
+    let old: DefaultHashMap<u8, u8> = (0..5).map(|i| (i, i)).collect();
+    let new: DefaultHashMap<u8, u8> = (3..8).map(|i| (i, i * 10)).collect();
+
+    let mut only_old: Vec<_> = old.difference(&new).collect();
+    only_old.sort();
+    let mut both: Vec<_> = old.intersection(&new).collect();
+    both.sort();
+    let mut either: Vec<_> = old.symmetric_difference(&new).collect();
+    either.sort();
+
+    assert_eq!(vec![(&0, &0), (&1, &1), (&2, &2)], only_old);
+    assert_eq!(vec![(&3, &3, &30), (&4, &4, &40)], both);
+    assert_eq!(
+        vec![
+            (&0, &0),
+            (&1, &1),
+            (&2, &2),
+            (&5, &50),
+            (&6, &60),
+            (&7, &70)
+        ],
+        either
+    );
+}
+
+#[test]
+fn predicates_hashmap() {
+    let map: DefaultHashMap<&str, u32> = [("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+    let empty = DefaultHashMap::<&str, u32>::new();
+
+    assert!(map.all(|key, value| key.len() == 1 && *value > 0));
+    assert!(map.any(|key, _| *key == "c"));
+    assert!(empty.all(|_, _| false));
+    assert!(!empty.any(|_, _| true));
+    assert_eq!(Some((&"b", &2)), map.find(|_, value| value % 2 == 0));
+    assert_eq!(None, empty.find(|_, _| true));
+}
+
+#[test]
+fn reverse_lookup_hashmap() {
+    let map: DefaultHashMap<u8, bool> = (0..6).map(|i| (i, i % 3 == 0)).collect();
+
+    let mut multiples: Vec<&u8> = map.keys_with_value(&true).collect();
+    multiples.sort();
+
+    assert_eq!(vec![&0, &3], multiples);
+    assert!(map.contains_value(&false));
+    assert!(!DefaultHashMap::<u8, bool>::new().contains_value(&false));
+}
+
+#[test]
+fn clear_to_default_hashmap() {
+    let mut map: DefaultHashMap<u8, Vec<u8>> = DefaultHashMap::builder()
+        .default_fn(|| Vec::with_capacity(4))
+        .build();
+    map.get_mut(&1).push(1);
+    map.get_mut(&2).extend([1, 2, 3]);
+
+    map.clear_to_default();
+
+    assert_eq!(2, map.len());
+    assert!(map
+        .values()
+        .all(|value| value.is_empty() && value.capacity() >= 4));
+}
+
+#[test]
+fn ensure_keys_hashmap() {
+    let mut map: DefaultHashMap<String, Vec<u8>> = DefaultHashMap::new();
+    map.get_mut(&"a".to_string()).push(1);
+
+    map.ensure_keys(["a", "b"].iter().map(|key| key.to_string()));
+
+    assert_eq!(2, map.len());
+    assert_eq!(&vec![1], map.get("a"));
+    assert!(map.get("b").is_empty());
+}
+
+#[test]
+fn clone_from_hashmap() {
+    let source: DefaultHashMap<u32, Vec<u32>> = (0..100).map(|i| (i, vec![i])).collect();
+    let mut target = source.clone();
+    let capacity = target.capacity();
+
+    target.get_mut(&3).push(4);
+    assert_eq!(vec![5], target.remove(&5));
+    target.clone_from(&source);
+
+    assert_eq!(source, target);
+    assert_eq!(capacity, target.capacity());
+
+    let mut other: DefaultHashMap<u32, Vec<u32>> =
+        DefaultHashMap::builder().default_value(vec![1]).build();
+    other.clone_from(&source);
+
+    assert_eq!(source, other);
+    assert_eq!(&Vec::<u32>::new(), other.get(&1000));
+}
+
+#[test]
+fn cloned_iterators_hashmap() {
+    let map: DefaultHashMap<String, Vec<u8>> =
+        [("a".to_string(), vec![1]), ("b".to_string(), vec![2])]
+            .into_iter()
+            .collect();
+
+    let mut keys: Vec<String> = map.keys_cloned().collect();
+    keys.sort();
+    let mut values: Vec<Vec<u8>> = map.values_cloned().collect();
+    values.sort();
+
+    assert_eq!(vec!["a".to_string(), "b".to_string()], keys);
+    assert_eq!(vec![vec![1], vec![2]], values);
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn retain_values_hashmap() {
+    let mut map: DefaultHashMap<u8, u32> = (0..10).map(|i| (i, u32::from(i % 2))).collect();
+
+    map.retain_values(|count| {
+        *count *= 10;
+        *count != 0
+    });
+
+    assert_eq!(5, map.len());
+    assert!(map.values().all(|count| *count == 10));
+}
+
+#[test]
+fn pop_by_value_hashmap() {
+    let mut tally: DefaultHashMap<char, u32> = DefaultHashMap::new();
+    for c in "abracadabra".chars() {
+        *tally.get_mut(&c) += 1;
+    }
+
+    assert_eq!(Some(('a', 5)), tally.pop_max_by_value());
+    assert_eq!(Some(2), tally.pop_max_by_value().map(|(_, count)| count));
+    assert_eq!(Some(2), tally.pop_max_by_value().map(|(_, count)| count));
+    assert_eq!(Some(1), tally.pop_min_by_value().map(|(_, count)| count));
+    assert_eq!(Some(1), tally.pop_min_by_value().map(|(_, count)| count));
+    assert_eq!(None, tally.pop_min_by_value());
+}
+
+#[test]
+fn nested_groups_hashmap() {
+    let rows = (0..12).map(|i| (i % 2, i % 3, i));
+
+    let grouped: DefaultHashMap<u8, DefaultHashMap<u8, Vec<u8>>> =
+        DefaultHashMap::from_nested_groups(rows.clone());
+    let summed: DefaultHashMap<u8, DefaultHashMap<u8, u32>> =
+        DefaultHashMap::from_nested_fold(rows, |sum, value| *sum += u32::from(value));
+
+    assert_eq!(2, grouped.len());
+    assert_eq!(&vec![0, 6], grouped.get(&0).get(&0));
+    assert_eq!(&vec![1, 7], grouped.get(&1).get(&1));
+    assert_eq!(&10, summed.get(&0).get(&2));
+    assert_eq!(&0, summed.get(&5).get(&5));
+}
+
+#[test]
+fn get_mut_cow_hashmap() {
+    let mut map = DefaultHashMap::<String, Vec<u8>>::new();
+
+    map.get_mut_cow(Cow::Borrowed("a")).push(1);
+    map.get_mut_cow(Cow::Borrowed("a")).push(2);
+    map.get_mut_cow(Cow::<str>::Owned(String::from("b")))
+        .push(3);
+
+    assert_eq!(&vec![1, 2], map.get("a"));
+    assert_eq!(&vec![3], map.get("b"));
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn get_disjoint_mut_or_default_hashmap() {
+    let mut map: DefaultHashMap<u8, Vec<u8>> =
+        DefaultHashMap::builder().default_value(vec![0]).build();
+    map.insert(1, vec![1, 2]);
+
+    let [a, b, c] = map.get_disjoint_mut_or_default([&1, &2, &3]);
+    b.append(a);
+    c.push(3);
+
+    assert_eq!(&Vec::<u8>::new(), map.get(&1));
+    assert_eq!(&vec![0, 1, 2], map.get(&2));
+    assert_eq!(&vec![0, 3], map.get(&3));
+    assert_eq!(3, map.len());
+}
+
+#[test]
+#[should_panic]
+fn get_disjoint_mut_or_default_duplicate_hashmap() {
+    let mut map = DefaultHashMap::<u8, u8>::new();
+    let _ = map.get_disjoint_mut_or_default([&1, &1]);
+}
+
+#[test]
+fn insert_into_hashmap() {
+    let mut map = DefaultHashMap::<String, u64>::new();
+
+    assert_eq!(None, map.insert_into("a", 1u8));
+    assert_eq!(Some(1), map.insert_into("a", 2u32));
+    assert_eq!(None, map.insert_into(String::from("b"), 3u64));
+
+    assert_eq!(&2, map.get("a"));
+    assert_eq!(&3, map.get("b"));
+}
+
+#[test]
+fn insert_or_previous_hashmap() {
+    let mut map: DefaultHashMap<&str, Vec<u8>> =
+        DefaultHashMap::builder().default_value(vec![0]).build();
+
+    assert_eq!(vec![0], map.insert_or_previous("a", vec![1]));
+    assert_eq!(vec![1], map.insert_or_previous("a", vec![2]));
+    assert_eq!(&vec![2], map.get(&"a"));
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn try_from_iter_hashmap() {
+    let map = DefaultHashMap::<u8, u8>::try_from_iter((0..4).map(|i| (i, i * 2))).unwrap();
+    assert_eq!(4, map.len());
+    assert_eq!(&6, map.get(&3));
+
+    let error = DefaultHashMap::<u8, u8>::try_from_iter([(1, 1), (2, 2), (2, 3), (1, 4)]);
+    assert_eq!(2, error.unwrap_err().into_key());
+}
+
+#[test]
+fn from_iter_merge_hashmap() {
+    let pairs = [("a", vec![1]), ("b", vec![2]), ("a", vec![3, 4])];
+
+    let map =
+        DefaultHashMap::<&str, Vec<u8>>::from_iter_merge(pairs, |values, more| values.extend(more));
+
+    assert_eq!(&vec![1, 3, 4], map.get(&"a"));
+    assert_eq!(&vec![2], map.get(&"b"));
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn with_default_value_hashmap() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Score(i32);
+
+    let mut map = DefaultHashMap::with_default_value(Score(-1));
+    assert_eq!(&Score(-1), map.get(&"missing"));
+    assert!(map.is_empty());
+
+    map.get_mut(&"a").0 += 2;
+    assert_eq!(&Score(1), map.get(&"a"));
+    assert_eq!(Score(-1), map.remove(&"b"));
+
+    map.insert("c", Score(5));
+    map.swap_values(&"a", &"c");
+    assert_eq!(&Score(5), map.get(&"a"));
+    assert_eq!(&Score(1), map.get(&"c"));
+
+    let copy = map.clone();
+    map.clear_to_default();
+    assert_eq!(&Score(-1), map.get(&"a"));
+    assert_eq!(&Score(5), copy.get(&"a"));
+    assert_ne!(map, copy);
+
+    let shards = copy.shard_by(2, |key| key.len());
+    assert_eq!(&Score(-1), shards[1].get(&"missing"));
+}
+
+#[test]
+fn get_mut_clones_key_only_on_miss_hashmap() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq, Hash)]
+    struct Key(u8);
+
+    impl Clone for Key {
+        fn clone(&self) -> Self {
+            CLONES.fetch_add(1, Ordering::SeqCst);
+            Key(self.0)
+        }
+    }
+
+    let mut map = DefaultHashMap::<Key, u32>::new();
+    for _ in 0..3 {
+        *map.get_mut(&Key(1)) += 1;
+    }
+
+    assert_eq!(1, CLONES.load(Ordering::SeqCst));
+    assert_eq!(&3, map.get(&Key(1)));
+}
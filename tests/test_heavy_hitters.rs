@@ -0,0 +1,89 @@
+use defaultdict::*;
+
+#[test]
+fn heavy_hitters_exact_below_capacity() {
+    let mut hitters = HeavyHitters::new(5);
+    for key in [1, 2, 1, 3, 1, 2] {
+        hitters.add(&key);
+    }
+
+    assert_eq!(vec![(&1, 3), (&2, 2), (&3, 1)], hitters.most_common(5));
+    assert_eq!(0, hitters.error(&1));
+    assert_eq!(0, hitters.count(&4));
+    assert_eq!(3, hitters.len());
+}
+
+#[test]
+fn heavy_hitters_finds_frequent_keys() {
+    let mut hitters = HeavyHitters::new(10);
+    for i in 0..10_000u32 {
+        let key = if i % 3 == 0 {
+            0
+        } else if i % 5 == 0 {
+            1
+        } else {
+            i
+        };
+        hitters.add(&key);
+    }
+
+    let top: Vec<u32> = hitters
+        .most_common(2)
+        .into_iter()
+        .map(|(key, _)| *key)
+        .collect();
+
+    assert_eq!(vec![0, 1], top);
+    assert!(hitters.count(&0) >= 3334);
+    assert!(hitters.count(&0) - hitters.error(&0) <= 3334);
+    assert_eq!(10, hitters.len());
+    assert_eq!(10, hitters.capacity());
+    assert_eq!(10_000, hitters.total());
+}
+
+#[test]
+#[should_panic]
+fn heavy_hitters_zero_capacity() {
+    let _ = HeavyHitters::<u8>::new(0);
+}
+
+#[test]
+fn heavy_hitters_evicts_smallest_count() {
+    let mut hitters = HeavyHitters::new(3);
+    hitters.add_n(&"a", 5);
+    hitters.add_n(&"b", 2);
+    hitters.add_n(&"c", 7);
+    hitters.add(&"d");
+
+    assert_eq!(0, hitters.count(&"b"));
+    assert_eq!(3, hitters.count(&"d"));
+    assert_eq!(2, hitters.error(&"d"));
+    assert_eq!(
+        vec![(&"c", 7), (&"a", 5), (&"d", 3)],
+        hitters.most_common(3)
+    );
+}
+
+#[test]
+fn heavy_hitters_many_distinct_keys() {
+    let mut hitters = HeavyHitters::new(100);
+    for i in 0..100_000u64 {
+        hitters.add(&i);
+        hitters.add(&u64::MAX);
+    }
+
+    assert_eq!(vec![(&u64::MAX, 100_000)], hitters.most_common(1));
+    assert_eq!(100, hitters.len());
+    assert_eq!(200_000, hitters.total());
+}
+
+#[test]
+fn heavy_hitters_saturates() {
+    let mut hitters = HeavyHitters::new(1);
+    hitters.add_n(&'a', u64::MAX);
+    hitters.add_n(&'a', 1);
+    hitters.add(&'b');
+
+    assert_eq!(u64::MAX, hitters.count(&'b'));
+    assert_eq!(u64::MAX, hitters.total());
+}
@@ -0,0 +1,80 @@
+use defaultdict::*;
+
+#[test]
+fn width_histogram() {
+    let mut histogram = Histogram::with_width(2.5);
+    for sample in [0.0, 2.4, 2.5, -0.1, -2.5, f64::NAN] {
+        histogram.record(sample);
+    }
+
+    assert_eq!(5, histogram.total());
+    assert_eq!(&2, histogram.counts().get(&0));
+    assert_eq!(&1, histogram.counts().get(&1));
+    assert_eq!(&2, histogram.counts().get(&-1));
+    assert_eq!((-2.5, 0.0), histogram.bin_range(-1));
+}
+
+#[test]
+fn edges_histogram() {
+    let histogram = Histogram::with_edges(vec![5.0, 1.0, f64::NAN, 1.0]);
+
+    assert_eq!(0, histogram.bin(0.5));
+    assert_eq!(1, histogram.bin(1.0));
+    assert_eq!(1, histogram.bin(4.9));
+    assert_eq!(2, histogram.bin(5.0));
+    assert_eq!((1.0, 5.0), histogram.bin_range(1));
+    assert_eq!((5.0, f64::INFINITY), histogram.bin_range(2));
+}
+
+#[test]
+fn percentile_histogram() {
+    let mut histogram = Histogram::with_edges(vec![0.0, 10.0, 20.0]);
+
+    assert_eq!(None, histogram.percentile(50.0));
+
+    for _ in 0..10 {
+        histogram.record(5.0);
+        histogram.record(15.0);
+    }
+
+    assert_eq!(Some(0.0), histogram.percentile(0.0));
+    assert_eq!(Some(10.0), histogram.percentile(50.0));
+    assert_eq!(Some(15.0), histogram.percentile(75.0));
+    assert_eq!(Some(20.0), histogram.percentile(100.0));
+}
+
+#[test]
+#[should_panic(expected = "same bins")]
+fn merge_mismatch_histogram() {
+    let mut a = Histogram::with_width(1.0);
+    a.merge(&Histogram::with_width(2.0));
+}
+
+#[test]
+fn infinite_samples_histogram() {
+    let mut width = Histogram::with_width(1.0);
+    width.record(f64::INFINITY);
+    width.record(f64::NEG_INFINITY);
+    width.record(1e300);
+
+    assert_eq!(1, width.total());
+    assert_eq!(Some(i64::MAX as f64), width.percentile(100.0));
+    assert_eq!(
+        (i64::MIN as f64, (i64::MIN + 1) as f64),
+        width.bin_range(i64::MIN)
+    );
+
+    let mut edges = Histogram::with_edges(vec![0.0]);
+    edges.record(f64::INFINITY);
+    edges.record(f64::NEG_INFINITY);
+
+    assert_eq!(2, edges.total());
+    assert_eq!(Some(0.0), edges.percentile(100.0));
+    assert_eq!(f64::NEG_INFINITY, edges.bin_range(i64::MIN).0);
+}
+
+#[test]
+#[should_panic(expected = "at least one bin edge")]
+fn empty_edges_histogram() {
+    let _ = Histogram::with_edges(vec![f64::NAN]);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "im")]
+
+use defaultdict::*;
+
+#[test]
+fn versions_share_im() {
+    let mut versions = vec![ImDefaultHashMap::<i8, i8>::new()];
+    for i in 0..10 {
+        let next = versions.last().unwrap().update(i, i);
+        versions.push(next);
+    }
+
+    for (len, version) in versions.iter().enumerate() {
+        assert_eq!(len, version.len());
+    }
+    assert_eq!(&0, versions[5].get(&7));
+    assert_eq!(&7, versions[10].get(&7));
+}
+
+#[test]
+fn modify_without_im() {
+    let map = ImDefaultHashMap::<&str, i8>::new().modify("a", |value| *value += 2);
+    let removed = map.without(&"a");
+
+    assert_eq!(&2, map.get(&"a"));
+    assert!(!removed.contains_key(&"a"));
+    assert_eq!(&0, removed.get(&"a"));
+}
+
+#[test]
+fn convert_im() {
+    let map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+    let persistent = ImDefaultHashMap::from(map.clone());
+
+    assert_eq!(map, DefaultHashMap::from(persistent));
+}
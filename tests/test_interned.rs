@@ -0,0 +1,49 @@
+use defaultdict::*;
+
+use std::sync::Arc;
+
+#[test]
+fn shared_keys_interned() {
+    let interner = Interner::new();
+    let mut a: InternedHashMap<i32> = InternedHashMap::with_interner(interner.clone());
+    let mut b: InternedHashMap<i32> = InternedHashMap::with_interner(interner.clone());
+
+    for line in ["GET /", "POST /login", "GET /", "GET /"] {
+        *a.get_mut(line) += 1;
+        *b.get_mut(line) += 10;
+    }
+
+    assert_eq!(&3, a.get("GET /"));
+    assert_eq!(&30, b.get("GET /"));
+    assert_eq!(2, interner.len());
+    assert!(Arc::ptr_eq(
+        &interner.intern("GET /"),
+        &interner.intern("GET /")
+    ));
+}
+
+#[test]
+fn remove_unused_interned() {
+    let mut map = InternedHashMap::new();
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    assert_eq!(1, map.remove("a"));
+    assert_eq!(2, map.interner().len());
+    assert_eq!(1, map.interner().remove_unused());
+    assert_eq!(1, map.interner().len());
+    assert_eq!(&2, map.get("b"));
+}
+
+#[test]
+fn insert_existing_interned() {
+    let mut map = InternedHashMap::new();
+
+    assert_eq!(None, map.insert("a", String::from("x")));
+    assert_eq!(Some(String::from("x")), map.insert("a", String::from("y")));
+    assert_eq!(1, map.interner().len());
+    assert_eq!(
+        vec![("a", &String::from("y"))],
+        map.iter().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,66 @@
+use defaultdict::*;
+
+#[test]
+fn journal_records_mutations() {
+    let mut map: JournaledHashMap<i8, i8> = JournaledHashMap::journaled();
+    map.insert(1, 1);
+    map.insert(1, 2);
+    let _ = map.get_mut(&2);
+    map.update(3, |value| *value += 3);
+    let _ = map.remove(&1);
+    let _ = map.remove(&4);
+
+    let golden = vec![
+        JournalOp::Insert(1, 1),
+        JournalOp::Insert(1, 2),
+        JournalOp::Insert(2, 0),
+        JournalOp::Insert(3, 3),
+        JournalOp::Remove(1),
+    ];
+    assert_eq!(golden, map.listener().ops());
+}
+
+#[test]
+fn journal_replay_mirrors_map() {
+    let mut map: JournaledHashMap<u8, Vec<u8>> = JournaledHashMap::journaled();
+    for i in 0..20 {
+        map.update(i % 7, |values| values.push(i));
+    }
+    map.retain(|key, _| key % 2 == 0);
+
+    let mut mirror = DefaultHashMap::new();
+    mirror.replay(map.listener().ops().iter().cloned());
+
+    assert_eq!(map.as_map(), &mirror);
+}
+
+#[test]
+fn journal_drain_incremental() {
+    let mut map: JournaledHashMap<&str, u8> = JournaledHashMap::journaled();
+    let mut mirror = DefaultHashMap::new();
+
+    map.insert("a", 1);
+    mirror.replay(map.listener_mut().drain());
+    map.clear();
+    map.insert("b", 2);
+    mirror.replay(map.listener_mut().drain());
+
+    assert!(map.listener().is_empty());
+    assert_eq!(map.into_inner(), mirror);
+}
+
+#[test]
+fn journal_replay_get_mut() {
+    let mut map: JournaledHashMap<&str, u32> = JournaledHashMap::journaled();
+    *map.get_mut(&"a") += 5;
+    map.insert("b", 1);
+    *map.get_mut(&"b") += 5;
+    *map.get_mut(&"a") = 9;
+
+    let mut mirror = DefaultHashMap::new();
+    mirror.replay(map.listener().ops().iter().cloned());
+
+    assert_eq!(map.as_map(), &mirror);
+    assert_eq!(&9, mirror.get(&"a"));
+    assert_eq!(&6, mirror.get(&"b"));
+}
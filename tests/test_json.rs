@@ -0,0 +1,45 @@
+#![cfg(feature = "json")]
+
+use defaultdict::*;
+
+#[test]
+fn merge_from_json_hashmap() {
+    let mut map: DefaultHashMap<String, u32> = defaulthashmap!(("a".into(), 1));
+
+    map.merge_from_json(r#"{"a": 2, "b": 3}"#, ConflictPolicy::Overwrite)
+        .unwrap();
+    assert_eq!(&2, map.get("a"));
+    assert_eq!(&3, map.get("b"));
+
+    assert!(map
+        .merge_from_json(r#"{"c": 4} trailing"#, ConflictPolicy::Overwrite)
+        .is_err());
+    assert!(map
+        .merge_from_json(r#"{"b": 30}"#, ConflictPolicy::Error)
+        .is_err());
+    assert_eq!(&3, map.get("b"));
+}
+
+#[test]
+fn merge_from_json_borrowed_keys_hashmap() {
+    let json = String::from(r#"{"pears": 5}"#);
+    let mut map: DefaultHashMap<&str, u32> = DefaultHashMap::new();
+    map.insert("apples", 3);
+
+    map.merge_from_json(&json, ConflictPolicy::KeepExisting)
+        .unwrap();
+
+    assert_eq!(&3, map.get(&"apples"));
+    assert_eq!(&5, map.get(&"pears"));
+}
+
+#[test]
+fn merge_from_json_btree() {
+    let mut map: DefaultBTreeMap<String, u32> = defaultbtreemap!(("a".into(), 1));
+
+    map.merge_from_json(r#"{"a": 2, "b": 3}"#, ConflictPolicy::KeepExisting)
+        .unwrap();
+
+    assert_eq!(&1, map.get(&"a".to_string()));
+    assert_eq!(&3, map.get(&"b".to_string()));
+}
@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use defaultdict::*;
+
+#[test]
+fn lru_evicts_least_recently_used() {
+    let mut evicted = Vec::new();
+    {
+        let mut map =
+            LruDefaultMap::new(2, |key, value, reason| evicted.push((key, value, reason)));
+        map.insert('a', 1);
+        map.insert('b', 2);
+        assert_eq!(&1, map.get(&'a'));
+        map.insert('c', 3);
+        *map.get_mut(&'d') += 4;
+
+        assert_eq!(2, map.len());
+        assert_eq!(vec![(&'c', &3), (&'d', &4)], map.iter().collect::<Vec<_>>());
+    }
+
+    assert_eq!(
+        vec![
+            ('b', 2, EvictionReason::Capacity),
+            ('a', 1, EvictionReason::Capacity)
+        ],
+        evicted
+    );
+}
+
+#[test]
+fn lru_overwrite_and_peek() {
+    let mut evicted = Vec::new();
+    {
+        let mut map = LruDefaultMap::new(2, |key, value, _| evicted.push((key, value)));
+        map.insert("a", 1);
+        map.insert("b", 2);
+        assert_eq!(Some(1), map.insert("a", 10));
+        assert_eq!(&2, map.peek(&"b"));
+        assert_eq!(&0, map.peek(&"z"));
+        map.insert("c", 3);
+
+        assert!(map.contains_key(&"a"));
+        assert!(!map.contains_key(&"b"));
+        assert_eq!(&0, map.get(&"b"));
+        assert_eq!(2, map.len());
+        assert_eq!(2, map.capacity());
+    }
+
+    assert_eq!(vec![("b", 2)], evicted);
+}
+
+#[test]
+fn lru_remove_is_not_evicted() {
+    let mut evicted = 0;
+    {
+        let mut map = LruDefaultMap::new(1, |_, _, _| evicted += 1);
+        map.insert(1, 10);
+
+        assert_eq!(10, map.remove(&1));
+        assert_eq!(0, map.remove(&1));
+        assert!(map.is_empty());
+
+        map.insert(2, 20);
+        map.insert(3, 30);
+    }
+
+    assert_eq!(1, evicted);
+}
+
+#[test]
+fn lru_evicts_expired() {
+    let mut evicted = Vec::new();
+    {
+        let mut map =
+            LruDefaultMap::new(10, |key, value, reason| evicted.push((key, value, reason)))
+                .ttl(Duration::from_secs(60));
+        map.insert("a", 1);
+        map.insert("b", 2);
+        let now = Instant::now();
+
+        map.evict_expired_at(now);
+        assert_eq!(2, map.len());
+
+        map.evict_expired_at(now + Duration::from_secs(61));
+        assert!(map.is_empty());
+    }
+
+    assert_eq!(
+        vec![
+            ("a", 1, EvictionReason::Expired),
+            ("b", 2, EvictionReason::Expired)
+        ],
+        evicted
+    );
+}
+
+#[test]
+fn lru_zero_ttl_expires_on_use() {
+    let mut evicted = Vec::new();
+    {
+        let mut map = LruDefaultMap::new(10, |key, _, reason| evicted.push((key, reason)))
+            .ttl(Duration::ZERO);
+        map.insert(1, 1);
+
+        assert_eq!(&0, map.get(&1));
+        assert!(map.is_empty());
+    }
+
+    assert_eq!(vec![(1, EvictionReason::Expired)], evicted);
+}
+
+#[test]
+#[should_panic]
+fn lru_zero_capacity() {
+    let _ = LruDefaultMap::<u8, u8, _>::new(0, |_, _, _| {});
+}
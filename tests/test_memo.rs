@@ -0,0 +1,67 @@
+use defaultdict::*;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn loads_once_memo() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let mut map: MemoMap<u64, u64, ()> = MemoMap::new(move |key| {
+        counter.set(counter.get() + 1);
+        Ok(key * key)
+    });
+
+    assert_eq!(Ok(&9), map.get(&3));
+    assert_eq!(Ok(&9), map.get(&3));
+    assert_eq!(Ok(&16), map.get(&4));
+
+    assert_eq!(2, calls.get());
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn error_not_cached_memo() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let mut map: MemoMap<u64, u64, &str> = MemoMap::new(move |key| {
+        counter.set(counter.get() + 1);
+        if counter.get() == 1 {
+            Err("unavailable")
+        } else {
+            Ok(*key)
+        }
+    });
+
+    assert_eq!(Err("unavailable"), map.get(&1));
+    assert!(!map.contains_key(&1));
+    assert_eq!(Ok(&1), map.get(&1));
+    assert_eq!(2, calls.get());
+}
+
+#[test]
+fn invalidate_memo() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let mut map: MemoMap<u64, u64, ()> = MemoMap::new(move |key| {
+        counter.set(counter.get() + 1);
+        Ok(*key)
+    });
+
+    let _ = map.get(&1);
+    assert_eq!(Some(1), map.invalidate(&1));
+    let _ = map.get(&1);
+    map.clear();
+    let _ = map.get(&1);
+
+    assert_eq!(3, calls.get());
+}
+
+#[test]
+fn insert_memo() {
+    let mut map: MemoMap<u64, u64, ()> = MemoMap::new(|_| panic!("loader should not run"));
+
+    assert_eq!(None, map.insert(1, 5));
+    assert_eq!(Ok(&5), map.get(&1));
+    assert_eq!(Some(&5), map.get_cached(&1));
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "metrics")]
+
+use defaultdict::*;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+
+#[derive(Default)]
+struct TestRecorder {
+    counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+    gauges: Mutex<HashMap<String, Arc<AtomicU64>>>,
+}
+
+impl TestRecorder {
+    fn counter(&self, name: &str) -> u64 {
+        self.counters
+            .lock()
+            .unwrap()
+            .get(name)
+            .map_or(0, |value| value.load(Ordering::Relaxed))
+    }
+
+    fn gauge(&self, name: &str) -> f64 {
+        self.gauges
+            .lock()
+            .unwrap()
+            .get(name)
+            .map_or(0.0, |value| f64::from_bits(value.load(Ordering::Relaxed)))
+    }
+}
+
+fn handle_name(key: &Key) -> String {
+    let labels: Vec<String> = key
+        .labels()
+        .map(|label| format!("{}={}", label.key(), label.value()))
+        .collect();
+    format!("{}{{{}}}", key.name(), labels.join(","))
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let mut counters = self.counters.lock().unwrap();
+        Counter::from_arc(counters.entry(handle_name(key)).or_default().clone())
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        let mut gauges = self.gauges.lock().unwrap();
+        Gauge::from_arc(gauges.entry(handle_name(key)).or_default().clone())
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+#[test]
+fn counts_metrics() {
+    let recorder = TestRecorder::default();
+
+    metrics::with_local_recorder(&recorder, || {
+        let mut map = ObservedHashMap::new(MetricsListener::new("test"));
+        map.insert(1, 1);
+        map.insert(1, 2);
+        *map.get_mut(&2) += 1;
+        let _ = map.get(&1);
+        let _ = map.get(&3);
+        let _ = map.remove(&1);
+    });
+
+    assert_eq!(1, recorder.counter("defaultdict_inserts_total{map=test}"));
+    assert_eq!(
+        1,
+        recorder.counter("defaultdict_overwrites_total{map=test}")
+    );
+    assert_eq!(1, recorder.counter("defaultdict_defaults_total{map=test}"));
+    assert_eq!(1, recorder.counter("defaultdict_removals_total{map=test}"));
+    assert_eq!(2, recorder.counter("defaultdict_reads_total{map=test}"));
+    assert_eq!(1, recorder.counter("defaultdict_misses_total{map=test}"));
+    assert_eq!(1.0, recorder.gauge("defaultdict_entries{map=test}"));
+}
+
+#[test]
+fn name_metrics() {
+    let listener = MetricsListener::new("words");
+
+    assert_eq!("words", listener.name());
+}
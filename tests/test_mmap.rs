@@ -0,0 +1,55 @@
+#![cfg(feature = "mmap")]
+
+use defaultdict::*;
+
+use std::path::PathBuf;
+
+fn temporary_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("defaultdict_{}_{}", name, std::process::id()))
+}
+
+#[test]
+fn roundtrip_mmap() {
+    let path = temporary_path("roundtrip_mmap");
+    let builder: MmapMapBuilder<u16, i64> = (0..1000).map(|i| (i * 2, i as i64)).collect();
+    builder.write(&path).unwrap();
+
+    let map: MmapDefaultMap<u16, i64> = MmapDefaultMap::open(&path).unwrap();
+
+    assert_eq!(1000, map.len());
+    for i in 0..1000 {
+        assert_eq!(i as i64, map.get(&(i * 2)).unwrap());
+        assert_eq!(0, map.get(&(i * 2 + 1)).unwrap());
+    }
+    let keys: Vec<u16> = map.iter().map(|pair| pair.unwrap().0).collect();
+    assert_eq!((0..1000).map(|i| i * 2).collect::<Vec<u16>>(), keys);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn empty_with_default_mmap() {
+    let path = temporary_path("empty_mmap");
+    MmapMapBuilder::<String, String>::new()
+        .default_value(String::from("missing"))
+        .write(&path)
+        .unwrap();
+
+    let map: MmapDefaultMap<String, String> = MmapDefaultMap::open(&path).unwrap();
+
+    assert!(map.is_empty());
+    assert!(!map.contains_key(&String::from("a")).unwrap());
+    assert_eq!("missing", map.get(&String::from("a")).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn invalid_file_mmap() {
+    let path = temporary_path("invalid_mmap");
+    std::fs::write(&path, b"not a map at all, just some bytes").unwrap();
+
+    assert!(MmapDefaultMap::<u8, u8>::open(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
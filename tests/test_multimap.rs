@@ -0,0 +1,37 @@
+use defaultdict::*;
+
+#[test]
+fn multimap_collects_values() {
+    let mut map: DefaultMultiMap<u8, char> = DefaultMultiMap::new();
+    map.get_mut(&1).push('a');
+    map.get_mut(&1).push('b');
+
+    assert_eq!(&vec!['a', 'b'], map.get(&1));
+    assert!(map.get(&2).is_empty());
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn query_string_roundtrip() {
+    let query = "b=2&a=x%20y&a=%2B&empty=&flag";
+
+    let map: DefaultMultiMap<String, String> = DefaultMultiMap::from_query_str(query);
+
+    assert_eq!(&vec!["x y", "+"], map.get("a"));
+    assert_eq!(&vec![""], map.get("empty"));
+    assert_eq!(&vec![""], map.get("flag"));
+    assert_eq!("a=x+y&a=%2B&b=2&empty=&flag=", map.to_query_string());
+
+    let again: DefaultMultiMap<String, String> =
+        DefaultMultiMap::from_query_str(&map.to_query_string());
+    assert_eq!(map, again);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn query_string_empty() {
+    let map: DefaultMultiMap<String, String> = DefaultMultiMap::from_query_str("");
+
+    assert!(map.is_empty());
+    assert_eq!("", map.to_query_string());
+}
@@ -0,0 +1,15 @@
+#![cfg(feature = "nohash")]
+
+use defaultdict::*;
+
+#[test]
+fn int_map_nohash() {
+    let mut map: DefaultIntMap<u64, u64> = DefaultIntMap::default();
+    for i in 0..100 {
+        *map.get_mut(&(i % 10)) += i;
+    }
+
+    assert_eq!(10, map.len());
+    assert_eq!(&450, map.get(&0));
+    assert_eq!(&0, map.get(&100));
+}
@@ -0,0 +1,35 @@
+use defaultdict::*;
+
+#[test]
+fn lowercase_normalized() {
+    let mut map: NormalizedHashMap<String, i32, Lowercase> = NormalizedHashMap::new(Lowercase);
+    for word in ["Apple", "APPLE", "apple", "Pear"] {
+        *map.get_mut(word) += 1;
+    }
+
+    assert_eq!(2, map.len());
+    assert_eq!(&3, map.get("aPPLE"));
+    assert_eq!(&1, map.get(&String::from("PEAR")));
+    assert!(map.contains_key("pear"));
+}
+
+#[test]
+fn trim_normalized() {
+    let mut map = NormalizedHashMap::new(Trim);
+    map.insert("  key\t", 1);
+
+    assert_eq!(&1, map.get("key"));
+    assert_eq!(1, map.remove(" key "));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn closure_normalized() {
+    let mut map = NormalizedHashMap::new(|key: &i32| key.rem_euclid(10));
+    map.insert(&13, "three");
+
+    assert_eq!(&"three", map.get(&3));
+    assert_eq!(&"three", map.get(&-7));
+    assert_eq!(&"", map.get(&4));
+    assert_eq!(&3, map.as_map().keys().next().unwrap());
+}
@@ -0,0 +1,125 @@
+use defaultdict::*;
+
+#[derive(Debug, Default, PartialEq)]
+struct Events {
+    inserted: Vec<(i8, i8)>,
+    overwritten: Vec<(i8, i8, i8)>,
+    removed: Vec<(i8, i8)>,
+    defaulted: Vec<i8>,
+}
+
+impl Listener<i8, i8> for Events {
+    fn on_insert(&mut self, key: &i8, value: &i8) {
+        self.inserted.push((*key, *value));
+    }
+
+    fn on_overwrite(&mut self, key: &i8, old: &i8, new: &i8) {
+        self.overwritten.push((*key, *old, *new));
+    }
+
+    fn on_remove(&mut self, key: &i8, value: &i8) {
+        self.removed.push((*key, *value));
+    }
+
+    fn on_default(&mut self, key: &i8, _value: &i8) {
+        self.defaulted.push(*key);
+    }
+}
+
+#[test]
+fn insert_overwrite_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    map.insert(1, 1);
+    map.insert(1, 2);
+
+    assert_eq!(vec![(1, 1)], map.listener().inserted);
+    assert_eq!(vec![(1, 1, 2)], map.listener().overwritten);
+}
+
+#[test]
+fn remove_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    map.insert(1, 1);
+
+    assert_eq!(1, map.remove(&1));
+    assert_eq!(0, map.remove(&1));
+    assert_eq!(vec![(1, 1)], map.listener().removed);
+}
+
+#[test]
+fn get_mut_default_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    *map.get_mut(&1) += 1;
+    *map.get_mut(&1) += 1;
+
+    assert_eq!(&2, map.get(&1));
+    assert_eq!(vec![1], map.listener().defaulted);
+    assert_eq!(vec![(1, 1, 2)], map.listener().overwritten);
+    assert!(map.listener().inserted.is_empty());
+}
+
+#[test]
+fn get_mut_existing_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    map.insert(1, 10);
+    {
+        let mut value = map.get_mut(&1);
+        *value += 1;
+        *value *= 2;
+        assert_eq!(&1, value.key());
+    }
+
+    assert_eq!(&22, map.get(&1));
+    assert_eq!(vec![(1, 10, 22)], map.listener().overwritten);
+    assert!(map.listener().defaulted.is_empty());
+}
+
+#[test]
+fn get_mut_unchanged_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    map.insert(1, 10);
+    {
+        let mut value = map.get_mut(&1);
+        *value += 1;
+        *value -= 1;
+    }
+    let _ = map.get_mut(&1);
+
+    assert_eq!(&10, map.get(&1));
+    assert!(map.listener().overwritten.is_empty());
+
+    let _ = map.get_mut(&2);
+
+    assert_eq!(vec![2], map.listener().defaulted);
+    assert!(map.contains_key(&2));
+}
+
+#[test]
+fn retain_clear_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    for i in 0..4 {
+        map.insert(i, i);
+    }
+
+    map.retain(|key, _| key % 2 == 0);
+    let mut removed = map.listener_mut().removed.drain(..).collect::<Vec<_>>();
+    removed.sort();
+    assert_eq!(vec![(1, 1), (3, 3)], removed);
+
+    map.clear();
+    let mut removed = map.listener().removed.clone();
+    removed.sort();
+    assert_eq!(vec![(0, 0), (2, 2)], removed);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn update_observed() {
+    let mut map = ObservedHashMap::new(Events::default());
+    map.update(1, |value| *value += 1);
+    map.update(1, |value| *value += 1);
+
+    assert_eq!(vec![(1, 1)], map.listener().inserted);
+    assert_eq!(vec![(1, 1, 2)], map.listener().overwritten);
+    assert_eq!(&2, map.get(&1));
+}
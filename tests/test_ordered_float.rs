@@ -0,0 +1,73 @@
+#![cfg(feature = "ordered-float")]
+
+use defaultdict::*;
+
+use ordered_float::{NotNan, OrderedFloat};
+
+#[test]
+fn histogram_ordered_float() {
+    let mut map = DefaultBTreeMap::<OrderedFloat<f64>, u32>::new();
+    for sample in [2.5, -1.0, 2.5, f64::NAN, 0.0] {
+        *map.get_mut_float(sample) += 1;
+    }
+
+    assert_eq!(&2, map.get_float(2.5));
+    assert_eq!(&1, map.get_float(f64::NAN));
+    assert!(map.contains_float(-1.0));
+    assert!(!map.contains_float(1.0));
+
+    let keys: Vec<f64> = map.iter_float().map(|(key, _)| key).collect();
+    assert_eq!(4, keys.len());
+    assert_eq!(vec![-1.0, 0.0, 2.5], keys[..3]);
+    assert!(keys[3].is_nan());
+
+    let range: Vec<(f64, &u32)> = map.range_float(-0.5..=2.5).collect();
+    assert_eq!(vec![(0.0, &1), (2.5, &2)], range);
+
+    assert_eq!(2, map.remove_float(2.5));
+    assert_eq!(0, map.remove_float(2.5));
+}
+
+#[test]
+fn not_nan_btree_ordered_float() {
+    let mut map = DefaultBTreeMap::<NotNan<f32>, u32>::new();
+    map.insert_float(1.5, 3);
+
+    assert_eq!(&3, map.get_float(1.5));
+    assert_eq!(&0, map.get_float(f32::NAN));
+    assert!(!map.contains_float(f32::NAN));
+    assert_eq!(0, map.remove_float(f32::NAN));
+    assert_eq!(1, map.len());
+}
+
+#[test]
+#[should_panic(expected = "NaN can not be used as a key of this map")]
+fn not_nan_insert_panics_ordered_float() {
+    let mut map = DefaultHashMap::<NotNan<f64>, u32>::new();
+    map.insert_float(f64::NAN, 1);
+}
+
+#[test]
+fn hashmap_ordered_float() {
+    let mut map = DefaultHashMap::<OrderedFloat<f32>, Vec<&str>>::new();
+    map.get_mut_float(0.0).push("zero");
+    map.get_mut_float(-0.0).push("negative zero");
+    map.get_mut_float(f32::NAN).push("nan");
+
+    assert_eq!(2, map.len());
+    assert_eq!(&vec!["zero", "negative zero"], map.get_float(0.0));
+    assert_eq!(&vec!["nan"], map.get_float(f32::NAN));
+    assert_eq!(None, map.insert_float(1.0, vec!["one"]));
+    assert_eq!(Some(vec!["one"]), map.insert_float(1.0, vec![]));
+
+    let mut keys: Vec<f32> = map
+        .iter_float()
+        .map(|(key, _)| key)
+        .filter(|key| !key.is_nan())
+        .collect();
+    keys.sort_by(f32::total_cmp);
+    assert_eq!(vec![0.0, 1.0], keys);
+
+    assert_eq!(vec!["nan"], map.remove_float(f32::NAN));
+    assert!(!map.contains_float(f32::NAN));
+}
@@ -0,0 +1,60 @@
+use defaultdict::*;
+
+struct Request {
+    endpoint: &'static str,
+    status: u16,
+    latency: u32,
+}
+
+fn requests() -> Vec<Request> {
+    [
+        ("/a", 200, 10),
+        ("/a", 500, 40),
+        ("/b", 200, 5),
+        ("/a", 200, 20),
+    ]
+    .into_iter()
+    .map(|(endpoint, status, latency)| Request {
+        endpoint,
+        status,
+        latency,
+    })
+    .collect()
+}
+
+#[test]
+fn crosstab_pivot() {
+    let pivot = Pivot::from_records(
+        requests(),
+        |request| request.endpoint,
+        |request| request.status,
+        |latencies: &mut Vec<u32>, request| latencies.push(request.latency),
+    );
+
+    assert_eq!(&vec![10, 20], pivot.cell(&"/a", &200));
+    assert!(pivot.cell(&"/b", &500).is_empty());
+
+    let rows: Vec<&&str> = pivot.cells().keys().collect();
+
+    assert_eq!(vec![&"/a", &"/b"], rows);
+}
+
+#[test]
+fn totals_pivot() {
+    let pivot = Pivot::from_records(
+        requests(),
+        |request| request.endpoint,
+        |request| request.status,
+        |count: &mut u32, _| *count += 1,
+    );
+
+    let rows = pivot.row_totals(|total, cell| *total += cell);
+    let columns = pivot.column_totals(|total, cell| *total += cell);
+
+    assert_eq!(&3, rows.get(&"/a"));
+    assert_eq!(&1, rows.get(&"/b"));
+    assert_eq!(&3, columns.get(&200));
+    assert_eq!(&1, columns.get(&500));
+    assert_eq!(4, pivot.grand_total(|total, cell| *total += cell));
+    assert_eq!(2, pivot.into_cells().len());
+}
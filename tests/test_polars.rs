@@ -0,0 +1,46 @@
+#![cfg(feature = "polars")]
+
+use defaultdict::*;
+
+use polars_core::prelude::{Column, DataFrame};
+
+#[test]
+fn dataframe_roundtrip() {
+    let map: DefaultHashMap<String, f64> =
+        defaulthashmap!(("a".to_string(), 1.5), ("b".to_string(), -2.0));
+
+    let frame = map.to_dataframe();
+    let back = DefaultHashMap::<String, f64>::from_dataframe(&frame, "key", "value").unwrap();
+
+    assert_eq!((2, 2), frame.shape());
+    assert_eq!(map, back);
+    assert_eq!(&0.0, back.get("c"));
+}
+
+#[test]
+fn from_dataframe_named_columns() {
+    let frame = DataFrame::new(vec![
+        Column::new("id".into(), [1i64, 2, 1]),
+        Column::new("flag".into(), [true, false, false]),
+    ])
+    .unwrap();
+
+    let map = DefaultHashMap::<i64, bool>::from_dataframe(&frame, "id", "flag").unwrap();
+
+    assert_eq!(2, map.len());
+    assert_eq!(&false, map.get(&1));
+    assert_eq!(&false, map.get(&2));
+}
+
+#[test]
+fn from_dataframe_rejects_invalid_input() {
+    let frame = DataFrame::new(vec![
+        Column::new("key".into(), ["a", "b"]),
+        Column::new("value".into(), [Some(1i64), None]),
+    ])
+    .unwrap();
+
+    assert!(DefaultHashMap::<String, i64>::from_dataframe(&frame, "key", "value").is_err());
+    assert!(DefaultHashMap::<String, i64>::from_dataframe(&frame, "key", "missing").is_err());
+    assert!(DefaultHashMap::<String, String>::from_dataframe(&frame, "key", "value").is_err());
+}
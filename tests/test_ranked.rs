@@ -0,0 +1,63 @@
+use defaultdict::*;
+
+use std::collections::BTreeMap;
+
+#[test]
+fn matches_btreemap_ranked() {
+    let mut map = RankedDefaultMap::<u32, u32>::new();
+    let mut reference = BTreeMap::new();
+    let mut state: u32 = 12345;
+    for step in 0..5000 {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        let key = (state >> 16) % 500;
+        if step % 3 == 0 {
+            assert_eq!(reference.remove(&key).unwrap_or_default(), map.remove(&key));
+        } else {
+            assert_eq!(reference.insert(key, step), map.insert(key, step));
+        }
+    }
+
+    assert_eq!(reference.len(), map.len());
+    assert!(reference.iter().eq(map.iter()));
+    for (index, (key, value)) in reference.iter().enumerate() {
+        assert_eq!(index, map.rank(key));
+        assert_eq!(Some((key, value)), map.select(index));
+        assert_eq!(value, map.get(key));
+    }
+    assert_eq!(None, map.select(map.len()));
+}
+
+#[test]
+fn defaults_ranked() {
+    let mut map = RankedDefaultMap::<String, Vec<u8>>::default();
+
+    assert!(map.is_empty());
+    assert_eq!(&Vec::<u8>::new(), map.get(&"a".to_string()));
+
+    map.get_mut(&"b".to_string()).push(1);
+    map.get_mut(&"b".to_string()).push(2);
+
+    assert_eq!(1, map.len());
+    assert_eq!(0, map.rank(&"b".to_string()));
+    assert_eq!(1, map.rank(&"c".to_string()));
+    assert_eq!(vec![1, 2], map.remove(&"b".to_string()));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn sorted_insertion_ranked() {
+    let map: RankedDefaultMap<u32, u32> = (0..100_000).map(|i| (i, i * 2)).collect();
+
+    assert_eq!(100_000, map.len());
+    assert_eq!(Some((&50_000, &100_000)), map.select(50_000));
+    assert_eq!(75_000, map.rank(&75_000));
+    assert_eq!(
+        "{0: 0, 1: 2}",
+        format!(
+            "{:?}",
+            (0..2)
+                .map(|i| (i, i * 2))
+                .collect::<RankedDefaultMap<u32, u32>>()
+        )
+    );
+}
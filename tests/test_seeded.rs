@@ -0,0 +1,37 @@
+use defaultdict::*;
+
+use std::hash::BuildHasher;
+
+#[test]
+fn same_seed_same_order_seeded() {
+    let mut a = DefaultHashMap::with_seed(1234);
+    let mut b = DefaultHashMap::with_seed(1234);
+    for i in 0..1000 {
+        a.insert(i.to_string(), i);
+        b.insert(i.to_string(), i);
+    }
+
+    assert!(a.keys().eq(b.keys()));
+}
+
+#[test]
+fn different_keys_seeded() {
+    let a = SeededState::with_keys(1, 2);
+    let b = SeededState::with_keys(2, 1);
+
+    assert_eq!(
+        a.hash_one(10_u64),
+        SeededState::with_keys(1, 2).hash_one(10_u64)
+    );
+    assert_ne!(a.hash_one(10_u64), b.hash_one(10_u64));
+}
+
+#[test]
+fn with_seed_matches_keys_seeded() {
+    assert_eq!(SeededState::with_seed(5), SeededState::with_keys(5, 0));
+
+    let mut map: DefaultHashMap<&str, i32, SeededState> = DefaultHashMap::with_keys(5, 0);
+    *map.get_mut(&"a") += 1;
+
+    assert_eq!(&1, map.get(&"a"));
+}
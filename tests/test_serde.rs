@@ -0,0 +1,137 @@
+#![cfg(feature = "serde")]
+
+use defaultdict::*;
+
+use serde::Deserialize;
+
+#[test]
+fn serde_roundtrip_hashmap() {
+    let map: DefaultHashMap<String, u32> = defaulthashmap!(("a".into(), 1), ("b".into(), 2));
+
+    let json = serde_json::to_string(&map).unwrap();
+    let back: DefaultHashMap<String, u32> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(map, back);
+    assert_eq!(&0, back.get("c"));
+}
+
+#[test]
+fn serde_roundtrip_btree() {
+    let map: DefaultBTreeMap<u8, Vec<u8>> = [(2, vec![2]), (1, vec![1])].into_iter().collect();
+
+    let json = serde_json::to_string(&map).unwrap();
+    let back: DefaultBTreeMap<u8, Vec<u8>> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(r#"{"1":[1],"2":[2]}"#, json);
+    assert_eq!(map, back);
+}
+
+#[test]
+fn serde_borrowed_keys_hashmap() {
+    let json = String::from(r#"{"apples": 3, "pears": 5}"#);
+
+    let map: DefaultHashMap<&str, u32> = serde_json::from_str(&json).unwrap();
+
+    let key = *map.keys().find(|key| **key == "apples").unwrap();
+    assert!(json.as_bytes().as_ptr_range().contains(&key.as_ptr()));
+    assert_eq!(&5, map.get(&"pears"));
+    assert_eq!(&0, map.get(&"plums"));
+}
+
+#[test]
+fn serde_borrowed_keys_btree() {
+    let json = String::from(r#"{"b": 2, "a": 1}"#);
+
+    let map: DefaultBTreeMap<&str, u32> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        vec![("a", 1), ("b", 2)],
+        map.into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn serde_borrow_attribute() {
+    #[derive(Deserialize)]
+    struct Inventory<'a> {
+        #[serde(borrow)]
+        stock: DefaultHashMap<&'a str, u32>,
+        #[serde(borrow)]
+        prices: DefaultBTreeMap<&'a str, u32>,
+    }
+
+    let json = String::from(r#"{"stock": {"apples": 3}, "prices": {"apples": 10}}"#);
+
+    let inventory: Inventory = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(&3, inventory.stock.get(&"apples"));
+    assert_eq!(&10, inventory.prices.get(&"apples"));
+    assert_eq!(&0, inventory.stock.get(&"pears"));
+}
+
+#[test]
+fn merge_from_deserializer_hashmap() {
+    let mut map: DefaultHashMap<String, u32> = defaulthashmap!(("a".into(), 1), ("b".into(), 2));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"b": 20, "c": 30}"#);
+    map.merge_from_deserializer(&mut input, ConflictPolicy::Overwrite)
+        .unwrap();
+    assert_eq!(3, map.len());
+    assert_eq!(&1, map.get("a"));
+    assert_eq!(&20, map.get("b"));
+    assert_eq!(&30, map.get("c"));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"a": 10, "d": 40}"#);
+    map.merge_from_deserializer(&mut input, ConflictPolicy::KeepExisting)
+        .unwrap();
+    assert_eq!(&1, map.get("a"));
+    assert_eq!(&40, map.get("d"));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"e": 50, "a": 10, "f": 60}"#);
+    let error = map
+        .merge_from_deserializer(&mut input, ConflictPolicy::Error)
+        .unwrap_err();
+    assert!(error.to_string().contains("the key is already present"));
+    assert_eq!(&1, map.get("a"));
+    assert_eq!(&50, map.get("e"));
+    assert!(!map.contains_key(&"f".to_string()));
+}
+
+#[test]
+fn merge_from_deserializer_keeps_default_hashmap() {
+    let mut map: DefaultHashMap<String, u32> = DefaultHashMap::builder().default_value(7).build();
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"a": 1}"#);
+    map.merge_from_deserializer(&mut input, ConflictPolicy::default())
+        .unwrap();
+
+    assert_eq!(&1, map.get("a"));
+    assert_eq!(&7, map.get("missing"));
+}
+
+#[test]
+fn merge_from_deserializer_btree() {
+    let mut map: DefaultBTreeMap<u8, Vec<u8>> = defaultbtreemap!((1, vec![1]), (2, vec![2]));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"2": [20], "3": [30]}"#);
+    map.merge_from_deserializer(&mut input, ConflictPolicy::KeepExisting)
+        .unwrap();
+    assert_eq!(&vec![2], map.get(&2));
+    assert_eq!(&vec![30], map.get(&3));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"1": [10]}"#);
+    map.merge_from_deserializer(&mut input, ConflictPolicy::Overwrite)
+        .unwrap();
+    assert_eq!(&vec![10], map.get(&1));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"3": [3]}"#);
+    assert!(map
+        .merge_from_deserializer(&mut input, ConflictPolicy::Error)
+        .is_err());
+    assert_eq!(&vec![30], map.get(&3));
+
+    let mut input = serde_json::Deserializer::from_str("[1, 2]");
+    assert!(map
+        .merge_from_deserializer(&mut input, ConflictPolicy::Overwrite)
+        .is_err());
+}
@@ -0,0 +1,93 @@
+use defaultdict::*;
+
+use std::sync::Arc;
+
+#[test]
+fn dedup_shared_values() {
+    let mut map = SharedValueMap::<u32, String>::new();
+    for id in 0..100 {
+        map.insert(id, format!("group-{}", id % 3));
+    }
+
+    assert_eq!(100, map.len());
+    assert_eq!(3, map.distinct_values());
+    assert!(Arc::ptr_eq(
+        map.get_shared(&0).unwrap(),
+        map.get_shared(&99).unwrap()
+    ));
+    assert_eq!("group-1", map.get(&4));
+    assert_eq!("", map.get(&100));
+    assert_eq!(None, map.get_shared(&100));
+}
+
+#[test]
+fn release_shared_values() {
+    let mut map: SharedValueMap<&str, u8> = [("a", 1), ("b", 1), ("c", 2)].into_iter().collect();
+    assert_eq!(2, map.distinct_values());
+
+    assert_eq!(2, map.remove(&"c"));
+    assert_eq!(1, map.distinct_values());
+    assert_eq!(0, map.remove(&"c"));
+
+    assert_eq!(Some(1), map.insert("a", 3));
+    assert_eq!(2, map.distinct_values());
+    assert_eq!(Some(1), map.insert("b", 3));
+    assert_eq!(1, map.distinct_values());
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(0, map.distinct_values());
+}
+
+#[test]
+fn update_shared_values() {
+    let mut map = SharedValueMap::<&str, Vec<u8>>::new();
+    map.insert("a", vec![1]);
+    map.insert("b", vec![1]);
+
+    map.update("a", |value| value.push(2));
+    map.update("c", |value| value.push(3));
+
+    assert_eq!(&vec![1, 2], map.get(&"a"));
+    assert_eq!(&vec![1], map.get(&"b"));
+    assert_eq!(&vec![3], map.get(&"c"));
+    assert_eq!(3, map.distinct_values());
+
+    map.update("a", |value| {
+        value.pop();
+    });
+    assert_eq!(2, map.distinct_values());
+    assert!(Arc::ptr_eq(
+        map.get_shared(&"a").unwrap(),
+        map.get_shared(&"b").unwrap()
+    ));
+
+    let mut entries: Vec<(&&str, &Vec<u8>)> = map.iter().collect();
+    entries.sort();
+    assert_eq!(
+        vec![(&"a", &vec![1]), (&"b", &vec![1]), (&"c", &vec![3])],
+        entries
+    );
+}
+
+#[test]
+fn outliving_clone_shared_values() {
+    let mut map = SharedValueMap::<&str, String>::new();
+    map.insert("a", "same".to_string());
+    map.insert("b", "same".to_string());
+    let kept = Arc::clone(map.get_shared(&"a").unwrap());
+
+    assert_eq!("same", map.remove(&"a"));
+    assert_eq!("same", map.remove(&"b"));
+    assert_eq!(0, map.distinct_values());
+
+    map.insert("c", "same".to_string());
+    map.insert("d", "other".to_string());
+    assert_eq!(
+        Some("other".to_string()),
+        map.insert("d", "same".to_string())
+    );
+
+    assert_eq!(1, map.distinct_values());
+    assert_eq!("same", *kept);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "sled")]
+
+use defaultdict::*;
+
+fn temporary_map<K: Codec, V: Codec + Default + Clone>() -> PersistentDefaultMap<K, V> {
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    PersistentDefaultMap::from_tree(db.open_tree("test").unwrap())
+}
+
+#[test]
+fn insert_get_remove_sled() {
+    let map = temporary_map::<u32, String>();
+
+    assert_eq!(None, map.insert(&1, &String::from("a")).unwrap());
+    assert_eq!(
+        Some(String::from("a")),
+        map.insert(&1, &String::from("b")).unwrap()
+    );
+    assert_eq!("b", map.get(&1).unwrap());
+    assert_eq!("", map.get(&2).unwrap());
+    assert_eq!("b", map.remove(&1).unwrap());
+    assert_eq!("", map.remove(&1).unwrap());
+    assert!(map.is_empty());
+}
+
+#[test]
+fn update_sled() {
+    let map = temporary_map::<u8, i64>();
+    for _ in 0..5 {
+        map.update(&7, |value| *value -= 1).unwrap();
+    }
+
+    assert_eq!(-5, map.get(&7).unwrap());
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn survives_reopen_sled() {
+    let path = std::env::temp_dir().join(format!("defaultdict_sled_{}", std::process::id()));
+    {
+        let map: PersistentDefaultMap<u8, u8> = PersistentDefaultMap::open(&path).unwrap();
+        map.insert(&1, &2).unwrap();
+        map.flush().unwrap();
+    }
+    {
+        let map: PersistentDefaultMap<u8, u8> = PersistentDefaultMap::open(&path).unwrap();
+        assert_eq!(2, map.get(&1).unwrap());
+    }
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn corrupt_value_sled() {
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    let tree = db.open_tree("test").unwrap();
+    tree.insert([1u8], vec![1u8]).unwrap();
+    let map: PersistentDefaultMap<u8, u32> = PersistentDefaultMap::from_tree(tree);
+
+    assert!(map.get(&1).is_err());
+    assert!(map.update(&1, |value| *value += 1).is_err());
+}
@@ -0,0 +1,67 @@
+use defaultdict::*;
+
+#[test]
+fn inline_small_map() {
+    let mut map: SmallDefaultMap<i32, i32, 4> = SmallDefaultMap::new();
+    for i in 0..4 {
+        *map.get_mut(&i) += i;
+    }
+
+    assert!(map.is_inline());
+    assert_eq!(4, map.len());
+    for i in 0..4 {
+        assert_eq!(&i, map.get(&i));
+    }
+}
+
+#[test]
+fn spill_small_map() {
+    let mut map: SmallDefaultMap<i32, i32, 4> = (0..4).map(|i| (i, i)).collect();
+
+    assert_eq!(None, map.insert(4, 4));
+    assert!(!map.is_inline());
+    assert_eq!(5, map.len());
+
+    let mut pairs: Vec<(i32, i32)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+    pairs.sort_unstable();
+
+    assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)], pairs);
+}
+
+#[test]
+fn overwrite_full_small_map() {
+    let mut map: SmallDefaultMap<i32, i32, 2> = SmallDefaultMap::new();
+    map.insert(1, 1);
+    map.insert(2, 2);
+
+    assert_eq!(Some(1), map.insert(1, 10));
+    assert!(map.is_inline());
+    assert_eq!(&10, map.get(&1));
+}
+
+#[test]
+fn remove_small_map() {
+    let mut map: SmallDefaultMap<i32, i32, 4> = (0..4).map(|i| (i, i * 10)).collect();
+
+    assert_eq!(10, map.remove(&1));
+    assert_eq!(0, map.remove(&1));
+    assert_eq!(3, map.len());
+    assert!(!map.contains_key(&1));
+    assert_eq!(&30, map.get(&3));
+
+    map.insert(5, 50);
+    map.insert(6, 60);
+
+    assert!(!map.is_inline());
+    assert_eq!(50, map.remove(&5));
+}
+
+#[test]
+fn clear_small_map() {
+    let mut map: SmallDefaultMap<String, i32> = SmallDefaultMap::new();
+    map.insert(String::from("a"), 1);
+    map.clear();
+
+    assert!(map.is_empty());
+    assert_eq!(&0, map.get(&String::from("a")));
+}
@@ -0,0 +1,98 @@
+use defaultdict::*;
+
+use std::cmp::Ordering;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+struct ByLastChar;
+
+impl SortBy<String> for ByLastChar {
+    fn cmp(a: &String, b: &String) -> Ordering {
+        a.chars().last().cmp(&b.chars().last())
+    }
+}
+
+#[test]
+fn descending_sorted() {
+    let mut map = DefaultSortedMap::<i32, i32, Descending>::new();
+    for key in [3, 1, 4, 1, 5, 9, 2, 6] {
+        *map.get_mut(&key) += 1;
+    }
+
+    let keys: Vec<&i32> = map.keys().collect();
+    assert_eq!(vec![&9, &6, &5, &4, &3, &2, &1], keys);
+    assert_eq!(&2, map.get(&1));
+    assert_eq!(&0, map.get(&7));
+    assert_eq!(Some((&9, &1)), map.first_key_value());
+    assert_eq!(Some((&1, &2)), map.last_key_value());
+
+    let range: Vec<&i32> = map
+        .range((Included(6), Included(3)))
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(vec![&6, &5, &4, &3], range);
+}
+
+#[test]
+fn natural_matches_btree_sorted() {
+    let sorted: DefaultSortedMap<u8, u8> = (0..20).rev().map(|key| (key, key * 2)).collect();
+    let btree: DefaultBTreeMap<u8, u8> = (0..20).map(|key| (key, key * 2)).collect();
+
+    assert!(sorted.iter().eq(&btree));
+    assert!(sorted
+        .range((Excluded(3), Included(7)))
+        .eq(btree.range((Excluded(3), Included(7)))));
+    assert!(sorted.range((Unbounded, Excluded(2))).eq(btree.range(..2)));
+}
+
+#[test]
+fn case_insensitive_sorted() {
+    let mut map = DefaultSortedMap::<String, u32, CaseInsensitive>::new();
+    assert_eq!(None, map.insert("Banana".to_string(), 1));
+    assert_eq!(Some(1), map.insert("BANANA".to_string(), 2));
+    map.insert("apple".to_string(), 3);
+    map.insert("Cherry".to_string(), 4);
+
+    assert_eq!(3, map.len());
+    assert!(map.contains_key(&"banana".to_string()));
+    let keys: Vec<&String> = map.keys().collect();
+    assert_eq!(vec!["apple", "Banana", "Cherry"], keys);
+
+    assert_eq!(2, map.remove(&"bAnAnA".to_string()));
+    assert_eq!(0, map.remove(&"banana".to_string()));
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn custom_comparator_sorted() {
+    let mut map = DefaultSortedMap::<String, Vec<u8>, Descending<ByLastChar>>::new();
+    map.get_mut(&"ab".to_string()).push(1);
+    map.get_mut(&"xb".to_string()).push(2);
+    map.get_mut(&"ac".to_string()).push(3);
+
+    let entries: Vec<(&String, &Vec<u8>)> = map.iter().collect();
+    assert_eq!(
+        vec![
+            (&"ac".to_string(), &vec![3]),
+            (&"ab".to_string(), &vec![1, 2])
+        ],
+        entries
+    );
+}
+
+#[test]
+fn clone_eq_sorted() {
+    let mut map: DefaultSortedMap<&str, u8, CaseInsensitive> =
+        [("a", 1), ("B", 2)].into_iter().collect();
+    let copy = map.clone();
+    assert_eq!(map, copy);
+
+    for (_, value) in map.iter_mut() {
+        *value += 1;
+    }
+    assert_ne!(map, copy);
+    assert_eq!(vec![&2, &3], map.values().collect::<Vec<_>>());
+    assert_eq!(r#"{"a": 2, "B": 3}"#, format!("{map:?}"));
+
+    map.clear();
+    assert!(map.is_empty());
+}
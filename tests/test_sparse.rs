@@ -0,0 +1,43 @@
+use defaultdict::*;
+
+fn vector(pairs: &[(&'static str, f64)]) -> DefaultHashMap<&'static str, f64> {
+    pairs.iter().copied().collect()
+}
+
+#[test]
+fn dot_sparse() {
+    let a = vector(&[("a", 1.0), ("b", 2.0), ("c", 3.0)]);
+    let b = vector(&[("b", 4.0)]);
+
+    assert_eq!(8.0, a.dot(&b));
+    assert_eq!(8.0, b.dot(&a));
+    assert_eq!(0.0, a.dot(&DefaultHashMap::new()));
+}
+
+#[test]
+fn dot_ignores_default_value() {
+    let a: DefaultHashMap<&str, f64> = DefaultHashMap::builder().default_value(1.0).build();
+    let b = vector(&[("x", 5.0)]);
+
+    assert_eq!(0.0, a.dot(&b));
+}
+
+#[test]
+fn cosine_similarity_sparse() {
+    let a = vector(&[("x", 1.0), ("y", 0.0)]);
+    let b = vector(&[("x", 1.0), ("y", 1.0)]);
+
+    let similarity = a.cosine_similarity(&b).unwrap();
+
+    assert!((similarity - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+}
+
+#[test]
+fn scale_and_norm() {
+    let mut v = vector(&[("x", 6.0), ("y", 8.0)]);
+
+    v.scale(1.0 / v.norm());
+
+    assert!((v.norm() - 1.0).abs() < 1e-12);
+    assert!((v.get(&"x") - 0.6).abs() < 1e-12);
+}
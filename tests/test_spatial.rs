@@ -0,0 +1,95 @@
+use defaultdict::*;
+
+#[test]
+fn neighbors_across_cells() {
+    let mut grid = SpatialDefaultGrid::new(1.0);
+    for x in -5..=5 {
+        for y in -5..=5 {
+            grid.insert((x as f64, y as f64), (x, y));
+        }
+    }
+
+    let mut near: Vec<(i32, i32)> = grid
+        .neighbors((0.0, 0.0), 1.0)
+        .map(|(_, item)| *item)
+        .collect();
+    near.sort();
+
+    assert_eq!(vec![(-1, 0), (0, -1), (0, 0), (0, 1), (1, 0)], near);
+    assert_eq!(121, grid.len());
+}
+
+#[test]
+fn neighbors_brute_force() {
+    let mut grid = SpatialDefaultGrid::new(3.0);
+    let points: Vec<(f64, f64)> = (0..200)
+        .map(|i| {
+            (
+                ((i * 37) % 101) as f64 * 0.7 - 30.0,
+                ((i * 53) % 97) as f64 * 0.4 - 15.0,
+            )
+        })
+        .collect();
+    for (index, point) in points.iter().enumerate() {
+        grid.insert(*point, index);
+    }
+
+    let center = (2.5, -1.5);
+    let radius = 7.5;
+    let mut found: Vec<usize> = grid
+        .neighbors(center, radius)
+        .map(|(_, item)| *item)
+        .collect();
+    found.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|index| {
+            let (x, y) = points[*index];
+            (x - center.0).powi(2) + (y - center.1).powi(2) <= radius * radius
+        })
+        .collect();
+
+    assert!(!expected.is_empty());
+    assert_eq!(expected, found);
+}
+
+#[test]
+fn retain_and_clear_grid() {
+    let mut grid = SpatialDefaultGrid::new(5.0);
+    grid.insert((1.0, 1.0), 1);
+    grid.insert((2.0, 2.0), 2);
+    grid.insert((20.0, 20.0), 3);
+
+    grid.retain(|_, item| item % 2 == 1);
+
+    assert_eq!(2, grid.len());
+    assert_eq!(1, grid.cell_items((0, 0)).len());
+    assert!(grid.cell_items((9, 9)).is_empty());
+    assert_eq!(2, grid.iter().count());
+
+    grid.clear();
+
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn neighbors_huge_radius_scans_occupied_cells() {
+    let mut grid = SpatialDefaultGrid::new(0.001);
+    grid.insert((1.0, 1.0), "a");
+    grid.insert((-1e12, 3e12), "b");
+
+    let mut all: Vec<&str> = grid
+        .neighbors((0.0, 0.0), f64::MAX)
+        .map(|(_, item)| *item)
+        .collect();
+    all.sort();
+
+    assert_eq!(vec!["a", "b"], all);
+    assert_eq!(1, grid.neighbors((0.0, 0.0), 1e6).count());
+}
+
+#[test]
+#[should_panic(expected = "the radius must be finite")]
+fn neighbors_infinite_radius() {
+    let grid = SpatialDefaultGrid::<u8>::new(1.0);
+    let _ = grid.neighbors((0.0, 0.0), f64::INFINITY).count();
+}
@@ -0,0 +1,45 @@
+use defaultdict::*;
+
+#[test]
+fn references_survive_growth_stable() {
+    let map = DefaultStableMap::<u32, Vec<u32>>::new();
+
+    let held: Vec<&Vec<u32>> = (0..10).map(|i| map.get_or_insert(i, vec![i])).collect();
+    for key in 10..10_000 {
+        let _ = map.get_or_default(key);
+    }
+
+    for (i, value) in (0..10).zip(held) {
+        assert_eq!(&vec![i], value);
+        assert!(std::ptr::eq(value, map.get(&i)));
+    }
+    assert_eq!(10_000, map.len());
+}
+
+#[test]
+fn defaults_stable() {
+    let mut map = DefaultStableMap::<String, u32>::default();
+
+    assert_eq!(&0, map.get("a"));
+    assert!(!map.contains_key("a"));
+
+    *map.get_mut(String::from("a")) += 2;
+    assert_eq!(&2, map.get_or_default(String::from("a")));
+    assert_eq!(&2, map.get_or_insert(String::from("a"), 5));
+
+    assert_eq!(2, map.remove("a"));
+    assert_eq!(0, map.remove("a"));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn into_map_stable() {
+    let map = DefaultStableMap::<u8, u8>::new();
+    map.get_or_insert(1, 10);
+    map.get_or_insert(2, 20);
+
+    let plain = map.into_map();
+
+    assert_eq!(2, plain.len());
+    assert_eq!(&20, plain.get(&2));
+}
@@ -0,0 +1,49 @@
+use defaultdict::*;
+
+#[test]
+fn record_stats() {
+    let mut latencies: DefaultStatsMap<&str> = DefaultStatsMap::new();
+    for (endpoint, latency) in [
+        ("a", 10.0),
+        ("b", 3.0),
+        ("a", 30.0),
+        ("a", 20.0),
+        ("a", f64::NAN),
+    ] {
+        latencies.record(endpoint, latency);
+    }
+
+    let a = latencies.get(&"a");
+
+    assert_eq!(3, a.count());
+    assert_eq!(60.0, a.sum());
+    assert_eq!(Some(10.0), a.min());
+    assert_eq!(Some(30.0), a.max());
+    assert_eq!(Some(20.0), a.mean());
+
+    let missing = latencies.get(&"c");
+
+    assert_eq!(None, missing.mean());
+    assert_eq!(None, missing.min());
+}
+
+#[test]
+fn merge_stats() {
+    let mut a: DefaultStatsMap<u8> = DefaultStatsMap::new();
+    let mut b: DefaultStatsMap<u8> = DefaultStatsMap::new();
+    a.record(1, -1.0);
+    b.record(1, 2.0);
+    b.record(2, 4.0);
+
+    a.merge_stats(&b);
+
+    assert_eq!(Some(-1.0), a.get(&1).min());
+    assert_eq!(Some(2.0), a.get(&1).max());
+    assert_eq!(Some(0.5), a.get(&1).mean());
+    assert_eq!(b.get(&2), a.get(&2));
+
+    let mut empty = Stats::default();
+    empty.merge(&Stats::default());
+
+    assert_eq!(Stats::default(), empty);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "arc-swap")]
+
+use defaultdict::*;
+
+#[test]
+fn swap_map_concurrent_updates() {
+    let map = SwapDefaultMap::<u8, u32>::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for i in 0..100 {
+                    map.update(|next| *next.get_mut(&(i % 5)) += 1);
+                }
+            });
+        }
+        scope.spawn(|| {
+            for _ in 0..100 {
+                let total: u32 = map.read(|current| current.values().sum());
+                assert!(total <= 400);
+            }
+        });
+    });
+
+    for key in 0..5 {
+        assert_eq!(80, map.get_cloned(&key));
+    }
+    assert_eq!(0, map.get_cloned(&5));
+}
+
+#[test]
+fn swap_map_snapshot_isolated() {
+    let map: SwapDefaultMap<&str, Vec<u8>> =
+        SwapDefaultMap::from_map(defaulthashmap!(("a", vec![1])));
+    let snapshot = map.snapshot();
+
+    map.update(|next| next.get_mut(&"a").push(2));
+    let previous = map.publish(DefaultHashMap::new());
+
+    assert_eq!(&vec![1], snapshot.get(&"a"));
+    assert_eq!(&vec![1, 2], previous.get(&"a"));
+    assert!(!map.contains_key(&"a"));
+    assert!(map.into_inner().is_empty());
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "testing")]
+
+use defaultdict::testing::*;
+use defaultdict::*;
+
+use proptest::prelude::*;
+
+#[test]
+fn testing_hashmap_matches_model() {
+    check_equivalence(DefaultHashMap::<u8, i32>::new, 0..10u8, any::<i32>());
+}
+
+#[test]
+fn testing_btreemap_matches_model() {
+    check_equivalence(DefaultBTreeMap::<u8, String>::new, 0..10u8, "[a-c]{0,2}");
+}
+
+#[test]
+fn testing_model_semantics() {
+    let mut model = ModelMap::<&str, u32>::new();
+
+    assert_eq!(&0, model.get(&"a"));
+    assert_eq!(0, model.len());
+
+    *model.get_mut(&"a") += 2;
+    model.insert("b", 1);
+
+    assert_eq!(&[("a", 2), ("b", 1)], model.entries());
+    assert_eq!(2, model.remove(&"a"));
+    assert_eq!(0, model.remove(&"a"));
+}
+
+/// A map that forgets every insert, to check the harness catches it.
+struct Forgetful(DefaultHashMap<u8, u8>);
+
+impl DefaultMap<u8, u8> for Forgetful {
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn contains_key(&self, key: &u8) -> bool {
+        self.0.contains_key(key)
+    }
+
+    fn get(&self, key: &u8) -> &u8 {
+        self.0.get(key)
+    }
+
+    fn get_mut(&mut self, key: &u8) -> &mut u8 {
+        self.0.get_mut(key)
+    }
+
+    fn insert(&mut self, _: u8, _: u8) {}
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &u8) -> u8 {
+        self.0.remove(key)
+    }
+}
+
+#[test]
+fn testing_detects_differences() {
+    let ops = vec![MapOp::Insert(1, 5), MapOp::Get(1)];
+
+    assert!(check_against_model(DefaultHashMap::new(), ops.clone()).is_ok());
+    assert!(check_against_model(Forgetful(DefaultHashMap::new()), ops).is_err());
+}
+
+#[test]
+#[should_panic(expected = "disagrees with the model")]
+fn testing_check_equivalence_panics() {
+    check_equivalence(|| Forgetful(DefaultHashMap::new()), 0..4u8, 1..4u8);
+}
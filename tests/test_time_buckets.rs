@@ -0,0 +1,88 @@
+use defaultdict::*;
+
+#[test]
+fn aggregate_time_buckets() {
+    let mut totals = TimeBuckets::<u64>::new(1000);
+    for (timestamp, bytes) in [(1_500, 10), (1_999, 5), (4_000, 7), (-1, 1)] {
+        *totals.bucket_mut(timestamp) += bytes;
+    }
+
+    let contiguous: Vec<(i64, u64)> = totals
+        .iter_contiguous()
+        .map(|(start, total)| (start, *total))
+        .collect();
+
+    assert_eq!(
+        vec![
+            (-1000, 1),
+            (0, 0),
+            (1000, 15),
+            (2000, 0),
+            (3000, 0),
+            (4000, 7)
+        ],
+        contiguous
+    );
+    assert_eq!(3, totals.buckets().len());
+}
+
+#[test]
+fn iter_range_time_buckets() {
+    let buckets = TimeBuckets::from_items(5, [(3, 'a'), (12, 'b')]);
+
+    let starts: Vec<i64> = buckets.iter_range(4, 11).map(|(start, _)| start).collect();
+
+    assert_eq!(vec![0, 5, 10], starts);
+    assert_eq!(0, buckets.iter_range(10, 10).count());
+    assert_eq!(0, buckets.iter_range(10, 3).count());
+    assert_eq!(&vec!['b'], buckets.get(14));
+}
+
+#[test]
+fn empty_time_buckets() {
+    let buckets = TimeBuckets::<Vec<u8>>::new(60);
+
+    assert_eq!(0, buckets.iter_contiguous().count());
+    assert_eq!(60, buckets.width());
+}
+
+#[test]
+fn extreme_time_buckets() {
+    let mut buckets = TimeBuckets::<u32>::new(1000);
+    *buckets.bucket_mut(i64::MAX) += 1;
+    *buckets.bucket_mut(i64::MIN) += 2;
+
+    let last = i64::MAX - i64::MAX % 1000;
+
+    assert_eq!(last, buckets.bucket_start(i64::MAX));
+    assert_eq!(i64::MIN, buckets.bucket_start(i64::MIN));
+    assert_eq!(&2, buckets.get(i64::MIN + 1));
+
+    let tail: Vec<(i64, u32)> = buckets
+        .iter_range(last - 1000, i64::MAX)
+        .map(|(start, count)| (start, *count))
+        .collect();
+
+    assert_eq!(vec![(last - 1000, 0), (last, 1)], tail);
+
+    let head: Vec<i64> = buckets
+        .iter_range(i64::MIN, i64::MIN + 1000)
+        .map(|(start, _)| start)
+        .collect();
+
+    assert_eq!(vec![i64::MIN, i64::MIN - i64::MIN % 1000], head);
+    assert_eq!(
+        (i64::MAX / 1000) * 2 + 2,
+        buckets.iter_contiguous().size_hint().0 as i64
+    );
+}
+
+#[test]
+fn opposite_signs_time_buckets() {
+    let buckets = TimeBuckets::from_items(7, [(-8, 'a'), (8, 'b')]);
+
+    let starts: Vec<i64> = buckets.iter_contiguous().map(|(start, _)| start).collect();
+
+    assert_eq!(vec![-14, -7, 0, 7], starts);
+    assert_eq!(0, buckets.iter_range(i64::MAX, i64::MIN).count());
+}
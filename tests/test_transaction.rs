@@ -0,0 +1,44 @@
+use defaultdict::*;
+
+#[test]
+fn rollback_transaction() {
+    let mut map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+
+    let mut tx = map.transaction();
+    tx.insert(1, 10);
+    tx.insert(1, 20);
+    let _ = tx.remove(&2);
+    *tx.get_mut(&3) += 3;
+    tx.insert(4, 4);
+    tx.rollback();
+
+    let golden: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+    assert_eq!(golden, map);
+}
+
+#[test]
+fn commit_transaction() {
+    let mut map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1), (2, 2));
+
+    let mut tx = map.transaction();
+    tx.insert(1, 10);
+    let _ = tx.remove(&2);
+    *tx.get_mut(&3) += 3;
+    tx.commit();
+
+    let golden: DefaultHashMap<i8, i8> = defaulthashmap!((1, 10), (3, 3));
+    assert_eq!(golden, map);
+}
+
+#[test]
+fn drop_rolls_back_transaction() {
+    let mut map: DefaultHashMap<i8, i8> = defaulthashmap!((1, 1));
+
+    {
+        let mut tx = map.transaction();
+        tx.insert(1, 10);
+        assert_eq!(&10, tx.get(&1));
+    }
+
+    assert_eq!(&1, map.get(&1));
+}
@@ -0,0 +1,88 @@
+use defaultdict::*;
+
+#[test]
+fn shared_prefix_trie() {
+    let mut map = DefaultTrieMap::new();
+    for (index, key) in [
+        "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "r", "",
+    ]
+    .iter()
+    .enumerate()
+    {
+        assert_eq!(None, map.insert(key, index));
+    }
+
+    assert_eq!(8, map.len());
+    assert_eq!(&1, map.get("romanus"));
+    assert_eq!(&6, map.get("r"));
+    assert_eq!(&7, map.get(""));
+    assert_eq!(&0, map.get("roman"));
+    assert!(!map.contains_key("rom"));
+
+    let keys: Vec<String> = map.iter().map(|(key, _)| key).collect();
+
+    assert_eq!(
+        vec!["", "r", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon"],
+        keys
+    );
+}
+
+#[test]
+fn iter_prefix_trie() {
+    let map: DefaultTrieMap<u8> = [("/a/b", 1), ("/a/bc", 2), ("/a/c", 3), ("/b", 4)]
+        .into_iter()
+        .collect();
+
+    let prefixed = |prefix: &str| -> Vec<u8> { map.iter_prefix(prefix).map(|(_, v)| *v).collect() };
+
+    assert_eq!(vec![1, 2, 3], prefixed("/a"));
+    assert_eq!(vec![1, 2], prefixed("/a/b"));
+    assert_eq!(vec![2], prefixed("/a/bc"));
+    assert_eq!(Vec::<u8>::new(), prefixed("/a/bcd"));
+    assert_eq!(Vec::<u8>::new(), prefixed("/c"));
+    assert_eq!(vec![1, 2, 3, 4], prefixed(""));
+}
+
+#[test]
+fn remove_merges_trie() {
+    let mut map = DefaultTrieMap::new();
+    map.insert("test", 1);
+    map.insert("team", 2);
+    map.insert("te", 3);
+
+    assert_eq!(3, map.remove("te"));
+    assert_eq!(0, map.remove("te"));
+    assert_eq!(0, map.remove("tea"));
+    assert_eq!(2, map.len());
+    assert_eq!(1, map.remove("test"));
+
+    let mut expected = DefaultTrieMap::new();
+    expected.insert("team", 2);
+
+    assert_eq!(expected, map);
+}
+
+#[test]
+fn get_mut_trie() {
+    let mut map: DefaultTrieMap<Vec<char>> = DefaultTrieMap::new();
+    for word in ["hello", "help", "hello"] {
+        map.get_mut(word).push(word.chars().last().unwrap());
+    }
+
+    assert_eq!(&vec!['o', 'o'], map.get("hello"));
+    assert_eq!(&vec!['p'], &map["help"]);
+    assert_eq!(None, map.try_get_mut("hel"));
+    assert_eq!(2, map.len());
+}
+
+#[test]
+fn unicode_trie() {
+    let mut map = DefaultTrieMap::new();
+    map.insert("héllo", 1);
+    map.insert("hé", 2);
+    map.insert("hë", 3);
+
+    let keys: Vec<String> = map.iter_prefix("h").map(|(key, _)| key).collect();
+
+    assert_eq!(vec!["hé", "héllo", "hë"], keys);
+}
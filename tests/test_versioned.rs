@@ -0,0 +1,90 @@
+use defaultdict::*;
+
+#[test]
+fn snapshot_isolation_versioned() {
+    let mut map = VersionedHashMap::<i8, i8>::new();
+    for i in 0..5 {
+        map.insert(i, i);
+    }
+    let snapshot = map.snapshot();
+
+    map.clear();
+    *map.get_mut(&1) += 10;
+
+    let mut collected: Vec<(&i8, &i8)> = snapshot.iter().collect();
+    collected.sort();
+
+    assert_eq!(
+        vec![(&0, &0), (&1, &1), (&2, &2), (&3, &3), (&4, &4)],
+        collected
+    );
+    assert_eq!(&10, map.get(&1));
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn generation_versioned() {
+    let mut map = VersionedHashMap::<i8, i8>::new();
+    map.insert(1, 1);
+    let _ = map.remove(&1);
+
+    assert_eq!(2, map.generation());
+    assert_eq!(2, map.snapshot().generation());
+}
+
+#[test]
+fn history_versioned() {
+    let mut map = VersionedHashMap::<i8, i8>::new();
+    let _ = map.snapshot();
+    let _ = map.snapshot();
+    map.insert(1, 1);
+    let _ = map.snapshot();
+
+    let generations: Vec<u64> = map
+        .history()
+        .map(|snapshot| snapshot.generation())
+        .collect();
+    assert_eq!(vec![0, 1], generations);
+
+    map.truncate_history(0);
+    assert_eq!(0, map.history().count());
+}
+
+#[test]
+fn changed_keys_versioned() {
+    let mut map = VersionedHashMap::<i8, i8>::new();
+    map.insert(1, 1);
+    let old = map.snapshot();
+    map.insert(2, 2);
+    let new = map.snapshot();
+
+    let changed: Vec<&i8> = old.changed_keys(&new).collect();
+    assert_eq!(vec![&2], changed);
+
+    let changed: Vec<&i8> = new.changed_keys(&old).collect();
+    assert_eq!(vec![&2], changed);
+}
+
+#[test]
+fn max_history_versioned() {
+    let mut map = VersionedHashMap::<i8, i8>::new().with_max_history(3);
+    for i in 0..20 {
+        map.insert(i, i);
+        let _ = map.snapshot();
+    }
+
+    let generations: Vec<u64> = map
+        .history()
+        .map(|snapshot| snapshot.generation())
+        .collect();
+    assert_eq!(vec![18, 19, 20], generations);
+    assert_eq!(&17, map.history().next().unwrap().get(&17));
+    assert_eq!(&0, map.history().next().unwrap().get(&18));
+
+    let mut map = VersionedHashMap::<i8, i8>::new().with_max_history(0);
+    let snapshot = map.snapshot();
+    map.insert(1, 1);
+
+    assert_eq!(0, map.history().count());
+    assert_eq!(&0, snapshot.get(&1));
+}
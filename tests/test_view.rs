@@ -0,0 +1,29 @@
+use defaultdict::*;
+
+#[test]
+fn alternative_default_view() {
+    let mut map: DefaultHashMap<String, i32> = DefaultHashMap::new();
+    map.insert("a".to_string(), 1);
+
+    let missing = map.view_with_default(&-1);
+    let zero = map.view_with_default(&0);
+
+    assert_eq!(&1, missing.get("a"));
+    assert_eq!(&-1, missing.get("b"));
+    assert_eq!(&0, zero.get("b"));
+    assert_eq!(-1, missing[&"b".to_string()]);
+    assert_eq!(1, missing.len());
+    assert!(!map.contains_key(&"b".to_string()));
+}
+
+#[test]
+fn iter_view() {
+    let map: DefaultHashMap<i8, i8> = (0..3).map(|i| (i, i * 2)).collect();
+    let view = map.view_with_default(&100);
+
+    let mut collected: Vec<(&i8, &i8)> = view.into_iter().collect();
+    collected.sort();
+
+    assert_eq!(vec![(&0, &0), (&1, &2), (&2, &4)], collected);
+    assert_eq!(&100, view.default_value());
+}
@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+use defaultdict::*;
+
+#[test]
+fn sliding_window_windowed() {
+    let second = Duration::from_secs(1);
+    let mut counter = WindowedCounter::new(second, 3);
+    let start = Instant::now();
+
+    for offset in 0..5 {
+        counter.record_at(1, start + second * offset);
+        counter.record_at(2, start + second * offset);
+    }
+
+    assert_eq!(3, counter.count_at(&1, start + second * 4));
+    assert_eq!(2, counter.count_at(&1, start + second * 5));
+    assert_eq!(0, counter.count_at(&1, start + second * 7));
+    assert_eq!(3, counter.count_at(&2, start + second * 4));
+}
+
+#[test]
+fn expire_windowed() {
+    let second = Duration::from_secs(1);
+    let mut counter = WindowedCounter::new(second, 2);
+    let start = Instant::now();
+
+    counter.record_at("a", start);
+    counter.expire_at(start + second);
+
+    assert!(!counter.is_empty());
+
+    counter.expire_at(start + second * 2);
+
+    assert!(counter.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "at least one bucket")]
+fn zero_buckets_windowed() {
+    let _: WindowedCounter<u8> = WindowedCounter::new(Duration::from_secs(1), 0);
+}